@@ -0,0 +1,38 @@
+//! Position in a text that is being parsed, shared by the file formats
+
+/// Position in a text, with its line for errors
+pub(crate) struct Cursor<'a> {
+    text: &'a str,
+    position: usize,
+    /// Line of `position`, starting at 1
+    line: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(text: &'a str) -> Cursor<'a> {
+        Cursor {
+            text,
+            position: 0,
+            line: 1,
+        }
+    }
+
+    pub(crate) fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    pub(crate) fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Move forward by `len` bytes, counting the lines that are passed
+    pub(crate) fn advance(&mut self, len: usize) {
+        self.line += self.rest()[..len].matches('\n').count();
+        self.position += len;
+    }
+
+    pub(crate) fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.advance(rest.len() - rest.trim_start().len());
+    }
+}
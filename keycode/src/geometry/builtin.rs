@@ -0,0 +1,766 @@
+//! Key positions of the built-in physical layouts, in reading order
+
+use super::{KeyRect, KeyRotation, PhysicalKey};
+use crate::KeyMappingId;
+
+const fn key(id: KeyMappingId, row: u8, x: f32, y: f32, width: f32, height: f32) -> PhysicalKey {
+    PhysicalKey {
+        id,
+        row,
+        rect: KeyRect::new(x, y, width, height),
+        secondary: None,
+        rotation: KeyRotation::NONE,
+    }
+}
+
+pub(super) const ANSI_104: &[PhysicalKey] = &[
+    key(KeyMappingId::Escape, 0, 0.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F1, 0, 2.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F2, 0, 3.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F3, 0, 4.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F4, 0, 5.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F5, 0, 6.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F6, 0, 7.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F7, 0, 8.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F8, 0, 9.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F9, 0, 11.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F10, 0, 12.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F11, 0, 13.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F12, 0, 14.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::PrintScreen, 0, 15.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::ScrollLock, 0, 16.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Pause, 0, 17.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Backquote, 1, 0.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit1, 1, 1.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit2, 1, 2.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit3, 1, 3.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit4, 1, 4.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit5, 1, 5.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit6, 1, 6.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit7, 1, 7.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit8, 1, 8.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit9, 1, 9.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit0, 1, 10.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Minus, 1, 11.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Equal, 1, 12.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Backspace, 1, 13.0, 1.5, 2.0, 1.0),
+    key(KeyMappingId::Insert, 1, 15.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Home, 1, 16.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::PageUp, 1, 17.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumLock, 1, 18.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadDivide, 1, 19.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadMultiply, 1, 20.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadSubtract, 1, 21.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Tab, 2, 0.0, 2.5, 1.5, 1.0),
+    key(KeyMappingId::UsQ, 2, 1.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsW, 2, 2.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsE, 2, 3.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsR, 2, 4.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsT, 2, 5.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsY, 2, 6.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsU, 2, 7.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsI, 2, 8.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsO, 2, 9.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsP, 2, 10.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketLeft, 2, 11.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketRight, 2, 12.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Backslash, 2, 13.5, 2.5, 1.5, 1.0),
+    key(KeyMappingId::Del, 2, 15.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::End, 2, 16.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::PageDown, 2, 17.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad7, 2, 18.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad8, 2, 19.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad9, 2, 20.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadAdd, 2, 21.5, 2.5, 1.0, 2.0),
+    key(KeyMappingId::CapsLock, 3, 0.0, 3.5, 1.75, 1.0),
+    key(KeyMappingId::UsA, 3, 1.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsS, 3, 2.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsD, 3, 3.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsF, 3, 4.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsG, 3, 5.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsH, 3, 6.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsJ, 3, 7.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsK, 3, 8.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsL, 3, 9.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Semicolon, 3, 10.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Quote, 3, 11.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Enter, 3, 12.75, 3.5, 2.25, 1.0),
+    key(KeyMappingId::Numpad4, 3, 18.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad5, 3, 19.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad6, 3, 20.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftLeft, 4, 0.0, 4.5, 2.25, 1.0),
+    key(KeyMappingId::UsZ, 4, 2.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsX, 4, 3.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsC, 4, 4.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsV, 4, 5.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsB, 4, 6.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsN, 4, 7.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsM, 4, 8.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Comma, 4, 9.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Period, 4, 10.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Slash, 4, 11.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftRight, 4, 12.25, 4.5, 2.75, 1.0),
+    key(KeyMappingId::ArrowUp, 4, 16.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad1, 4, 18.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad2, 4, 19.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad3, 4, 20.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadEnter, 4, 21.5, 4.5, 1.0, 2.0),
+    key(KeyMappingId::ControlLeft, 5, 0.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaLeft, 5, 1.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::AltLeft, 5, 2.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::Space, 5, 3.75, 5.5, 6.25, 1.0),
+    key(KeyMappingId::AltRight, 5, 10.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaRight, 5, 11.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ContextMenu, 5, 12.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ControlRight, 5, 13.75, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ArrowLeft, 5, 15.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowDown, 5, 16.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowRight, 5, 17.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad0, 5, 18.5, 5.5, 2.0, 1.0),
+    key(KeyMappingId::NumpadDecimal, 5, 20.5, 5.5, 1.0, 1.0),
+];
+
+pub(super) const ISO_105: &[PhysicalKey] = &[
+    key(KeyMappingId::Escape, 0, 0.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F1, 0, 2.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F2, 0, 3.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F3, 0, 4.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F4, 0, 5.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F5, 0, 6.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F6, 0, 7.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F7, 0, 8.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F8, 0, 9.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F9, 0, 11.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F10, 0, 12.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F11, 0, 13.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F12, 0, 14.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::PrintScreen, 0, 15.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::ScrollLock, 0, 16.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Pause, 0, 17.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Backquote, 1, 0.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit1, 1, 1.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit2, 1, 2.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit3, 1, 3.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit4, 1, 4.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit5, 1, 5.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit6, 1, 6.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit7, 1, 7.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit8, 1, 8.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit9, 1, 9.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit0, 1, 10.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Minus, 1, 11.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Equal, 1, 12.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Backspace, 1, 13.0, 1.5, 2.0, 1.0),
+    key(KeyMappingId::Insert, 1, 15.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Home, 1, 16.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::PageUp, 1, 17.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumLock, 1, 18.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadDivide, 1, 19.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadMultiply, 1, 20.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadSubtract, 1, 21.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Tab, 2, 0.0, 2.5, 1.5, 1.0),
+    key(KeyMappingId::UsQ, 2, 1.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsW, 2, 2.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsE, 2, 3.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsR, 2, 4.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsT, 2, 5.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsY, 2, 6.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsU, 2, 7.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsI, 2, 8.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsO, 2, 9.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsP, 2, 10.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketLeft, 2, 11.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketRight, 2, 12.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Enter, 2, 13.75, 2.5, 1.25, 2.0)
+        .with_secondary(KeyRect::new(13.5, 2.5, 1.5, 1.0)),
+    key(KeyMappingId::Del, 2, 15.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::End, 2, 16.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::PageDown, 2, 17.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad7, 2, 18.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad8, 2, 19.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad9, 2, 20.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadAdd, 2, 21.5, 2.5, 1.0, 2.0),
+    key(KeyMappingId::CapsLock, 3, 0.0, 3.5, 1.75, 1.0),
+    key(KeyMappingId::UsA, 3, 1.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsS, 3, 2.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsD, 3, 3.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsF, 3, 4.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsG, 3, 5.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsH, 3, 6.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsJ, 3, 7.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsK, 3, 8.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsL, 3, 9.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Semicolon, 3, 10.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Quote, 3, 11.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Backslash, 3, 12.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad4, 3, 18.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad5, 3, 19.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad6, 3, 20.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftLeft, 4, 0.0, 4.5, 1.25, 1.0),
+    key(KeyMappingId::IntlBackslash, 4, 1.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsZ, 4, 2.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsX, 4, 3.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsC, 4, 4.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsV, 4, 5.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsB, 4, 6.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsN, 4, 7.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsM, 4, 8.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Comma, 4, 9.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Period, 4, 10.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Slash, 4, 11.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftRight, 4, 12.25, 4.5, 2.75, 1.0),
+    key(KeyMappingId::ArrowUp, 4, 16.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad1, 4, 18.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad2, 4, 19.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad3, 4, 20.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadEnter, 4, 21.5, 4.5, 1.0, 2.0),
+    key(KeyMappingId::ControlLeft, 5, 0.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaLeft, 5, 1.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::AltLeft, 5, 2.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::Space, 5, 3.75, 5.5, 6.25, 1.0),
+    key(KeyMappingId::AltRight, 5, 10.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaRight, 5, 11.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ContextMenu, 5, 12.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ControlRight, 5, 13.75, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ArrowLeft, 5, 15.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowDown, 5, 16.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowRight, 5, 17.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad0, 5, 18.5, 5.5, 2.0, 1.0),
+    key(KeyMappingId::NumpadDecimal, 5, 20.5, 5.5, 1.0, 1.0),
+];
+
+pub(super) const JIS_109: &[PhysicalKey] = &[
+    key(KeyMappingId::Escape, 0, 0.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F1, 0, 2.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F2, 0, 3.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F3, 0, 4.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F4, 0, 5.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F5, 0, 6.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F6, 0, 7.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F7, 0, 8.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F8, 0, 9.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F9, 0, 11.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F10, 0, 12.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F11, 0, 13.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F12, 0, 14.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::PrintScreen, 0, 15.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::ScrollLock, 0, 16.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Pause, 0, 17.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Backquote, 1, 0.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit1, 1, 1.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit2, 1, 2.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit3, 1, 3.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit4, 1, 4.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit5, 1, 5.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit6, 1, 6.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit7, 1, 7.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit8, 1, 8.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit9, 1, 9.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit0, 1, 10.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Minus, 1, 11.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Equal, 1, 12.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::IntlYen, 1, 13.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Backspace, 1, 14.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Insert, 1, 15.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Home, 1, 16.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::PageUp, 1, 17.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumLock, 1, 18.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadDivide, 1, 19.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadMultiply, 1, 20.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadSubtract, 1, 21.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Tab, 2, 0.0, 2.5, 1.5, 1.0),
+    key(KeyMappingId::UsQ, 2, 1.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsW, 2, 2.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsE, 2, 3.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsR, 2, 4.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsT, 2, 5.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsY, 2, 6.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsU, 2, 7.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsI, 2, 8.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsO, 2, 9.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsP, 2, 10.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketLeft, 2, 11.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketRight, 2, 12.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Enter, 2, 13.75, 2.5, 1.25, 2.0)
+        .with_secondary(KeyRect::new(13.5, 2.5, 1.5, 1.0)),
+    key(KeyMappingId::Del, 2, 15.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::End, 2, 16.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::PageDown, 2, 17.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad7, 2, 18.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad8, 2, 19.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad9, 2, 20.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadAdd, 2, 21.5, 2.5, 1.0, 2.0),
+    key(KeyMappingId::CapsLock, 3, 0.0, 3.5, 1.75, 1.0),
+    key(KeyMappingId::UsA, 3, 1.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsS, 3, 2.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsD, 3, 3.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsF, 3, 4.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsG, 3, 5.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsH, 3, 6.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsJ, 3, 7.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsK, 3, 8.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsL, 3, 9.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Semicolon, 3, 10.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Quote, 3, 11.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Backslash, 3, 12.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad4, 3, 18.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad5, 3, 19.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad6, 3, 20.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftLeft, 4, 0.0, 4.5, 2.25, 1.0),
+    key(KeyMappingId::UsZ, 4, 2.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsX, 4, 3.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsC, 4, 4.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsV, 4, 5.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsB, 4, 6.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsN, 4, 7.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsM, 4, 8.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Comma, 4, 9.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Period, 4, 10.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Slash, 4, 11.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::IntlRo, 4, 12.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftRight, 4, 13.25, 4.5, 1.75, 1.0),
+    key(KeyMappingId::ArrowUp, 4, 16.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad1, 4, 18.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad2, 4, 19.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad3, 4, 20.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadEnter, 4, 21.5, 4.5, 1.0, 2.0),
+    key(KeyMappingId::ControlLeft, 5, 0.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaLeft, 5, 1.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::AltLeft, 5, 2.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::NonConvert, 5, 3.75, 5.5, 1.25, 1.0),
+    key(KeyMappingId::Space, 5, 5.0, 5.5, 2.5, 1.0),
+    key(KeyMappingId::Convert, 5, 7.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::KanaMode, 5, 8.75, 5.5, 1.25, 1.0),
+    key(KeyMappingId::AltRight, 5, 10.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaRight, 5, 11.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ContextMenu, 5, 12.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ControlRight, 5, 13.75, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ArrowLeft, 5, 15.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowDown, 5, 16.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowRight, 5, 17.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad0, 5, 18.5, 5.5, 2.0, 1.0),
+    key(KeyMappingId::NumpadDecimal, 5, 20.5, 5.5, 1.0, 1.0),
+];
+
+pub(super) const ABNT2_107: &[PhysicalKey] = &[
+    key(KeyMappingId::Escape, 0, 0.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F1, 0, 2.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F2, 0, 3.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F3, 0, 4.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F4, 0, 5.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F5, 0, 6.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F6, 0, 7.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F7, 0, 8.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F8, 0, 9.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F9, 0, 11.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F10, 0, 12.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F11, 0, 13.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F12, 0, 14.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::PrintScreen, 0, 15.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::ScrollLock, 0, 16.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Pause, 0, 17.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Backquote, 1, 0.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit1, 1, 1.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit2, 1, 2.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit3, 1, 3.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit4, 1, 4.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit5, 1, 5.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit6, 1, 6.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit7, 1, 7.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit8, 1, 8.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit9, 1, 9.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit0, 1, 10.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Minus, 1, 11.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Equal, 1, 12.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Backspace, 1, 13.0, 1.5, 2.0, 1.0),
+    key(KeyMappingId::Insert, 1, 15.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Home, 1, 16.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::PageUp, 1, 17.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumLock, 1, 18.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadDivide, 1, 19.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadMultiply, 1, 20.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadSubtract, 1, 21.5, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Tab, 2, 0.0, 2.5, 1.5, 1.0),
+    key(KeyMappingId::UsQ, 2, 1.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsW, 2, 2.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsE, 2, 3.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsR, 2, 4.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsT, 2, 5.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsY, 2, 6.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsU, 2, 7.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsI, 2, 8.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsO, 2, 9.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsP, 2, 10.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketLeft, 2, 11.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketRight, 2, 12.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Enter, 2, 13.75, 2.5, 1.25, 2.0)
+        .with_secondary(KeyRect::new(13.5, 2.5, 1.5, 1.0)),
+    key(KeyMappingId::Del, 2, 15.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::End, 2, 16.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::PageDown, 2, 17.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad7, 2, 18.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad8, 2, 19.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad9, 2, 20.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadAdd, 2, 21.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::CapsLock, 3, 0.0, 3.5, 1.75, 1.0),
+    key(KeyMappingId::UsA, 3, 1.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsS, 3, 2.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsD, 3, 3.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsF, 3, 4.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsG, 3, 5.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsH, 3, 6.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsJ, 3, 7.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsK, 3, 8.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsL, 3, 9.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Semicolon, 3, 10.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Quote, 3, 11.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Backslash, 3, 12.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad4, 3, 18.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad5, 3, 19.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad6, 3, 20.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadComma, 3, 21.5, 3.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftLeft, 4, 0.0, 4.5, 1.25, 1.0),
+    key(KeyMappingId::IntlBackslash, 4, 1.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsZ, 4, 2.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsX, 4, 3.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsC, 4, 4.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsV, 4, 5.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsB, 4, 6.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsN, 4, 7.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsM, 4, 8.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Comma, 4, 9.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Period, 4, 10.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Slash, 4, 11.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::IntlRo, 4, 12.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftRight, 4, 13.25, 4.5, 1.75, 1.0),
+    key(KeyMappingId::ArrowUp, 4, 16.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad1, 4, 18.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad2, 4, 19.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad3, 4, 20.5, 4.5, 1.0, 1.0),
+    key(KeyMappingId::NumpadEnter, 4, 21.5, 4.5, 1.0, 2.0),
+    key(KeyMappingId::ControlLeft, 5, 0.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaLeft, 5, 1.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::AltLeft, 5, 2.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::Space, 5, 3.75, 5.5, 6.25, 1.0),
+    key(KeyMappingId::AltRight, 5, 10.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaRight, 5, 11.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ContextMenu, 5, 12.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ControlRight, 5, 13.75, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ArrowLeft, 5, 15.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowDown, 5, 16.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowRight, 5, 17.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::Numpad0, 5, 18.5, 5.5, 2.0, 1.0),
+    key(KeyMappingId::NumpadDecimal, 5, 20.5, 5.5, 1.0, 1.0),
+];
+
+pub(super) const ANSI_TKL: &[PhysicalKey] = &[
+    key(KeyMappingId::Escape, 0, 0.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F1, 0, 2.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F2, 0, 3.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F3, 0, 4.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F4, 0, 5.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F5, 0, 6.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F6, 0, 7.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F7, 0, 8.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F8, 0, 9.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F9, 0, 11.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F10, 0, 12.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F11, 0, 13.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F12, 0, 14.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::PrintScreen, 0, 15.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::ScrollLock, 0, 16.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Pause, 0, 17.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Backquote, 1, 0.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit1, 1, 1.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit2, 1, 2.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit3, 1, 3.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit4, 1, 4.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit5, 1, 5.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit6, 1, 6.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit7, 1, 7.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit8, 1, 8.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit9, 1, 9.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit0, 1, 10.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Minus, 1, 11.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Equal, 1, 12.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Backspace, 1, 13.0, 1.5, 2.0, 1.0),
+    key(KeyMappingId::Insert, 1, 15.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Home, 1, 16.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::PageUp, 1, 17.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Tab, 2, 0.0, 2.5, 1.5, 1.0),
+    key(KeyMappingId::UsQ, 2, 1.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsW, 2, 2.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsE, 2, 3.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsR, 2, 4.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsT, 2, 5.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsY, 2, 6.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsU, 2, 7.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsI, 2, 8.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsO, 2, 9.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsP, 2, 10.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketLeft, 2, 11.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketRight, 2, 12.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Backslash, 2, 13.5, 2.5, 1.5, 1.0),
+    key(KeyMappingId::Del, 2, 15.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::End, 2, 16.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::PageDown, 2, 17.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::CapsLock, 3, 0.0, 3.5, 1.75, 1.0),
+    key(KeyMappingId::UsA, 3, 1.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsS, 3, 2.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsD, 3, 3.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsF, 3, 4.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsG, 3, 5.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsH, 3, 6.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsJ, 3, 7.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsK, 3, 8.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsL, 3, 9.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Semicolon, 3, 10.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Quote, 3, 11.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Enter, 3, 12.75, 3.5, 2.25, 1.0),
+    key(KeyMappingId::ShiftLeft, 4, 0.0, 4.5, 2.25, 1.0),
+    key(KeyMappingId::UsZ, 4, 2.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsX, 4, 3.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsC, 4, 4.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsV, 4, 5.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsB, 4, 6.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsN, 4, 7.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsM, 4, 8.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Comma, 4, 9.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Period, 4, 10.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Slash, 4, 11.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftRight, 4, 12.25, 4.5, 2.75, 1.0),
+    key(KeyMappingId::ArrowUp, 4, 16.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::ControlLeft, 5, 0.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaLeft, 5, 1.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::AltLeft, 5, 2.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::Space, 5, 3.75, 5.5, 6.25, 1.0),
+    key(KeyMappingId::AltRight, 5, 10.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaRight, 5, 11.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ContextMenu, 5, 12.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ControlRight, 5, 13.75, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ArrowLeft, 5, 15.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowDown, 5, 16.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowRight, 5, 17.25, 5.5, 1.0, 1.0),
+];
+
+pub(super) const ISO_TKL: &[PhysicalKey] = &[
+    key(KeyMappingId::Escape, 0, 0.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F1, 0, 2.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F2, 0, 3.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F3, 0, 4.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F4, 0, 5.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F5, 0, 6.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F6, 0, 7.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F7, 0, 8.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F8, 0, 9.5, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F9, 0, 11.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F10, 0, 12.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F11, 0, 13.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::F12, 0, 14.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::PrintScreen, 0, 15.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::ScrollLock, 0, 16.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Pause, 0, 17.25, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Backquote, 1, 0.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit1, 1, 1.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit2, 1, 2.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit3, 1, 3.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit4, 1, 4.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit5, 1, 5.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit6, 1, 6.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit7, 1, 7.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit8, 1, 8.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit9, 1, 9.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Digit0, 1, 10.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Minus, 1, 11.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Equal, 1, 12.0, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Backspace, 1, 13.0, 1.5, 2.0, 1.0),
+    key(KeyMappingId::Insert, 1, 15.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Home, 1, 16.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::PageUp, 1, 17.25, 1.5, 1.0, 1.0),
+    key(KeyMappingId::Tab, 2, 0.0, 2.5, 1.5, 1.0),
+    key(KeyMappingId::UsQ, 2, 1.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsW, 2, 2.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsE, 2, 3.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsR, 2, 4.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsT, 2, 5.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsY, 2, 6.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsU, 2, 7.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsI, 2, 8.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsO, 2, 9.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::UsP, 2, 10.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketLeft, 2, 11.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::BracketRight, 2, 12.5, 2.5, 1.0, 1.0),
+    key(KeyMappingId::Enter, 2, 13.75, 2.5, 1.25, 2.0)
+        .with_secondary(KeyRect::new(13.5, 2.5, 1.5, 1.0)),
+    key(KeyMappingId::Del, 2, 15.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::End, 2, 16.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::PageDown, 2, 17.25, 2.5, 1.0, 1.0),
+    key(KeyMappingId::CapsLock, 3, 0.0, 3.5, 1.75, 1.0),
+    key(KeyMappingId::UsA, 3, 1.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsS, 3, 2.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsD, 3, 3.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsF, 3, 4.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsG, 3, 5.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsH, 3, 6.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsJ, 3, 7.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsK, 3, 8.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::UsL, 3, 9.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Semicolon, 3, 10.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Quote, 3, 11.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::Backslash, 3, 12.75, 3.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftLeft, 4, 0.0, 4.5, 1.25, 1.0),
+    key(KeyMappingId::IntlBackslash, 4, 1.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsZ, 4, 2.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsX, 4, 3.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsC, 4, 4.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsV, 4, 5.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsB, 4, 6.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsN, 4, 7.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::UsM, 4, 8.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Comma, 4, 9.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Period, 4, 10.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::Slash, 4, 11.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::ShiftRight, 4, 12.25, 4.5, 2.75, 1.0),
+    key(KeyMappingId::ArrowUp, 4, 16.25, 4.5, 1.0, 1.0),
+    key(KeyMappingId::ControlLeft, 5, 0.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaLeft, 5, 1.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::AltLeft, 5, 2.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::Space, 5, 3.75, 5.5, 6.25, 1.0),
+    key(KeyMappingId::AltRight, 5, 10.0, 5.5, 1.25, 1.0),
+    key(KeyMappingId::MetaRight, 5, 11.25, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ContextMenu, 5, 12.5, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ControlRight, 5, 13.75, 5.5, 1.25, 1.0),
+    key(KeyMappingId::ArrowLeft, 5, 15.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowDown, 5, 16.25, 5.5, 1.0, 1.0),
+    key(KeyMappingId::ArrowRight, 5, 17.25, 5.5, 1.0, 1.0),
+];
+
+pub(super) const ANSI_60: &[PhysicalKey] = &[
+    key(KeyMappingId::Escape, 0, 0.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit1, 0, 1.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit2, 0, 2.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit3, 0, 3.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit4, 0, 4.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit5, 0, 5.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit6, 0, 6.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit7, 0, 7.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit8, 0, 8.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit9, 0, 9.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit0, 0, 10.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Minus, 0, 11.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Equal, 0, 12.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Backspace, 0, 13.0, 0.0, 2.0, 1.0),
+    key(KeyMappingId::Tab, 1, 0.0, 1.0, 1.5, 1.0),
+    key(KeyMappingId::UsQ, 1, 1.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsW, 1, 2.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsE, 1, 3.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsR, 1, 4.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsT, 1, 5.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsY, 1, 6.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsU, 1, 7.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsI, 1, 8.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsO, 1, 9.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsP, 1, 10.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::BracketLeft, 1, 11.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::BracketRight, 1, 12.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::Backslash, 1, 13.5, 1.0, 1.5, 1.0),
+    key(KeyMappingId::CapsLock, 2, 0.0, 2.0, 1.75, 1.0),
+    key(KeyMappingId::UsA, 2, 1.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsS, 2, 2.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsD, 2, 3.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsF, 2, 4.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsG, 2, 5.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsH, 2, 6.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsJ, 2, 7.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsK, 2, 8.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsL, 2, 9.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::Semicolon, 2, 10.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::Quote, 2, 11.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::Enter, 2, 12.75, 2.0, 2.25, 1.0),
+    key(KeyMappingId::ShiftLeft, 3, 0.0, 3.0, 2.25, 1.0),
+    key(KeyMappingId::UsZ, 3, 2.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsX, 3, 3.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsC, 3, 4.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsV, 3, 5.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsB, 3, 6.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsN, 3, 7.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsM, 3, 8.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::Comma, 3, 9.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::Period, 3, 10.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::Slash, 3, 11.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::ShiftRight, 3, 12.25, 3.0, 2.75, 1.0),
+    key(KeyMappingId::ControlLeft, 4, 0.0, 4.0, 1.25, 1.0),
+    key(KeyMappingId::MetaLeft, 4, 1.25, 4.0, 1.25, 1.0),
+    key(KeyMappingId::AltLeft, 4, 2.5, 4.0, 1.25, 1.0),
+    key(KeyMappingId::Space, 4, 3.75, 4.0, 6.25, 1.0),
+    key(KeyMappingId::AltRight, 4, 10.0, 4.0, 1.25, 1.0),
+    key(KeyMappingId::MetaRight, 4, 11.25, 4.0, 1.25, 1.0),
+    key(KeyMappingId::ContextMenu, 4, 12.5, 4.0, 1.25, 1.0),
+    key(KeyMappingId::ControlRight, 4, 13.75, 4.0, 1.25, 1.0),
+];
+
+pub(super) const ISO_60: &[PhysicalKey] = &[
+    key(KeyMappingId::Escape, 0, 0.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit1, 0, 1.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit2, 0, 2.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit3, 0, 3.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit4, 0, 4.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit5, 0, 5.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit6, 0, 6.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit7, 0, 7.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit8, 0, 8.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit9, 0, 9.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Digit0, 0, 10.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Minus, 0, 11.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Equal, 0, 12.0, 0.0, 1.0, 1.0),
+    key(KeyMappingId::Backspace, 0, 13.0, 0.0, 2.0, 1.0),
+    key(KeyMappingId::Tab, 1, 0.0, 1.0, 1.5, 1.0),
+    key(KeyMappingId::UsQ, 1, 1.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsW, 1, 2.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsE, 1, 3.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsR, 1, 4.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsT, 1, 5.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsY, 1, 6.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsU, 1, 7.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsI, 1, 8.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsO, 1, 9.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::UsP, 1, 10.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::BracketLeft, 1, 11.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::BracketRight, 1, 12.5, 1.0, 1.0, 1.0),
+    key(KeyMappingId::Enter, 1, 13.75, 1.0, 1.25, 2.0)
+        .with_secondary(KeyRect::new(13.5, 1.0, 1.5, 1.0)),
+    key(KeyMappingId::CapsLock, 2, 0.0, 2.0, 1.75, 1.0),
+    key(KeyMappingId::UsA, 2, 1.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsS, 2, 2.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsD, 2, 3.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsF, 2, 4.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsG, 2, 5.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsH, 2, 6.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsJ, 2, 7.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsK, 2, 8.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::UsL, 2, 9.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::Semicolon, 2, 10.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::Quote, 2, 11.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::Backslash, 2, 12.75, 2.0, 1.0, 1.0),
+    key(KeyMappingId::ShiftLeft, 3, 0.0, 3.0, 1.25, 1.0),
+    key(KeyMappingId::IntlBackslash, 3, 1.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsZ, 3, 2.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsX, 3, 3.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsC, 3, 4.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsV, 3, 5.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsB, 3, 6.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsN, 3, 7.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::UsM, 3, 8.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::Comma, 3, 9.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::Period, 3, 10.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::Slash, 3, 11.25, 3.0, 1.0, 1.0),
+    key(KeyMappingId::ShiftRight, 3, 12.25, 3.0, 2.75, 1.0),
+    key(KeyMappingId::ControlLeft, 4, 0.0, 4.0, 1.25, 1.0),
+    key(KeyMappingId::MetaLeft, 4, 1.25, 4.0, 1.25, 1.0),
+    key(KeyMappingId::AltLeft, 4, 2.5, 4.0, 1.25, 1.0),
+    key(KeyMappingId::Space, 4, 3.75, 4.0, 6.25, 1.0),
+    key(KeyMappingId::AltRight, 4, 10.0, 4.0, 1.25, 1.0),
+    key(KeyMappingId::MetaRight, 4, 11.25, 4.0, 1.25, 1.0),
+    key(KeyMappingId::ContextMenu, 4, 12.5, 4.0, 1.25, 1.0),
+    key(KeyMappingId::ControlRight, 4, 13.75, 4.0, 1.25, 1.0),
+];
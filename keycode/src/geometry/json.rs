@@ -0,0 +1,228 @@
+//! Minimal JSON reader for keyboard-layout-editor files
+//!
+//! Also reads the relaxed form that keyboard-layout-editor shows as raw data, where the top level
+//! array's brackets may be left out and object keys don't have to be quoted.
+
+use super::ParseKleError;
+use crate::cursor::Cursor;
+use alloc::{format, string::String, vec::Vec};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<(usize, Value)>),
+    Object(Vec<(String, Value)>),
+}
+
+/// Deepest nesting of arrays and objects that is read
+const MAX_DEPTH: usize = 64;
+
+struct Reader<'a> {
+    cursor: Cursor<'a>,
+}
+
+impl<'a> Reader<'a> {
+    fn error(&self, message: impl Into<String>) -> ParseKleError {
+        ParseKleError::new(self.cursor.line(), message)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.cursor.skip_whitespace();
+        if self.cursor.rest().starts_with(c) {
+            self.cursor.advance(c.len_utf8());
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseKleError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", c)))
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, ParseKleError> {
+        self.cursor.skip_whitespace();
+        if depth > MAX_DEPTH {
+            return Err(self.error(format!(
+                "arrays and objects are nested more than {} deep",
+                MAX_DEPTH
+            )));
+        }
+        let rest = self.cursor.rest();
+        match rest.chars().next() {
+            Some('[') => {
+                self.cursor.advance(1);
+                self.array(depth).map(Value::Array)
+            }
+            Some('{') => {
+                self.cursor.advance(1);
+                self.object(depth).map(Value::Object)
+            }
+            Some('"') => self.string().map(Value::String),
+            Some(_) => {
+                let word = self.word();
+                match word {
+                    "null" => Ok(Value::Null),
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    _ => word
+                        .parse()
+                        .map(Value::Number)
+                        .map_err(|_| self.error(format!("unexpected `{}`", word))),
+                }
+            }
+            None => Err(self.error("unexpected end of file")),
+        }
+    }
+
+    /// Read the items of an array, after its opening bracket, with the line of each item
+    fn array(&mut self, depth: usize) -> Result<Vec<(usize, Value)>, ParseKleError> {
+        let mut items = Vec::new();
+        if self.eat(']') {
+            return Ok(items);
+        }
+        loop {
+            self.cursor.skip_whitespace();
+            items.push((self.cursor.line(), self.value(depth + 1)?));
+            if self.eat(']') {
+                return Ok(items);
+            }
+            self.expect(',')?;
+            // Allow a trailing comma
+            if self.eat(']') {
+                return Ok(items);
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Vec<(String, Value)>, ParseKleError> {
+        let mut members = Vec::new();
+        if self.eat('}') {
+            return Ok(members);
+        }
+        loop {
+            self.cursor.skip_whitespace();
+            let key = if self.cursor.rest().starts_with('"') {
+                self.string()?
+            } else {
+                match self.word() {
+                    "" => return Err(self.error("expected an object key")),
+                    word => String::from(word),
+                }
+            };
+            self.expect(':')?;
+            members.push((key, self.value(depth + 1)?));
+            if self.eat('}') {
+                return Ok(members);
+            }
+            self.expect(',')?;
+            if self.eat('}') {
+                return Ok(members);
+            }
+        }
+    }
+
+    /// Read a number, keyword or unquoted object key
+    fn word(&mut self) -> &'a str {
+        let rest = self.cursor.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '$' | '+' | '-' | '.')))
+            .unwrap_or(rest.len());
+        self.cursor.advance(len);
+        &rest[..len]
+    }
+
+    fn string(&mut self) -> Result<String, ParseKleError> {
+        let line = self.cursor.line();
+        self.cursor.advance(1);
+        let mut string = String::new();
+        let mut chars = self.cursor.rest().char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.cursor.advance(index + 1);
+                    return Ok(string);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, c)| c) {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                            let unit = u16::from_str_radix(&hex, 16).ok();
+                            // Characters outside the basic multilingual plane are escaped as a
+                            // pair of surrogates
+                            let low_unit = match unit {
+                                Some(0xd800..=0xdbff) if chars.as_str().starts_with("\\u") => {
+                                    let hex: String =
+                                        chars.by_ref().skip(2).take(4).map(|(_, c)| c).collect();
+                                    u16::from_str_radix(&hex, 16).ok()
+                                }
+                                _ => None,
+                            };
+                            core::char::decode_utf16(unit.into_iter().chain(low_unit))
+                                .next()
+                                .and_then(Result::ok)
+                                .ok_or_else(|| {
+                                    ParseKleError::new(
+                                        line,
+                                        format!("invalid unicode escape `\\u{}`", hex),
+                                    )
+                                })?
+                        }
+                        Some(c @ ('"' | '\\' | '/')) => c,
+                        _ => return Err(ParseKleError::new(line, "invalid escape in string")),
+                    };
+                    string.push(escaped);
+                }
+                c => string.push(c),
+            }
+        }
+        Err(ParseKleError::new(line, "unterminated string"))
+    }
+}
+
+/// Parse a JSON document into the items of its top level array, with the line of each item
+pub(crate) fn parse(json: &str) -> Result<Vec<(usize, Value)>, ParseKleError> {
+    let mut reader = Reader {
+        cursor: Cursor::new(json),
+    };
+    let mut items = Vec::new();
+    loop {
+        reader.cursor.skip_whitespace();
+        items.push((reader.cursor.line(), reader.value(0)?));
+        if !reader.eat(',') {
+            break;
+        }
+    }
+    reader.cursor.skip_whitespace();
+    if !reader.cursor.rest().is_empty() {
+        return Err(reader.error("unexpected content after the layout"));
+    }
+
+    // The raw data form leaves out the brackets of the top level array, so a single array is
+    // only the top level array if it holds rows rather than keys
+    let top_level = matches!(
+        items.as_slice(),
+        [(_, Value::Array(inner))]
+            if inner.iter().all(|(_, item)| matches!(item, Value::Array(_) | Value::Object(_)))
+    );
+    match items.pop() {
+        Some((_, Value::Array(inner))) if top_level => Ok(inner),
+        Some(item) => {
+            items.push(item);
+            Ok(items)
+        }
+        None => Ok(items),
+    }
+}
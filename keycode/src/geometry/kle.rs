@@ -0,0 +1,403 @@
+//! Import of keyboard-layout-editor (KLE) JSON files
+//!
+//! Keys are bound to [`KeyMappingId`]s through their legends. A legend can be the name of a code
+//! or an id, like `KeyA` or `UsA`, which is the best match. Otherwise legends are compared with the
+//! [labels](crate::label) of the keys of the keyboard usage page and the shifted characters of
+//! the US layout, so that `!` and `1` name the `Digit1` key, and `7` and `Home` on a number pad
+//! name the `Numpad7` key once the `Digit7` and `Home` keys are taken. Unlabelled keys that are
+//! at least 4 units wide are space bars.
+//!
+//! Keys that appear on several keyboards, like `Shift` or `Enter`, are assigned from left to right
+//! and top to bottom. Keys specific to ISO or JIS keyboards are best named by their code, as their
+//! legends depend on the language of the keyboard.
+
+use super::{
+    json::{self, Value},
+    KeyRect, KeyRotation, PhysicalKey, PhysicalLayout,
+};
+use crate::{label::LabelStyle, KeyMap, KeyMappingCode, KeyMappingId};
+use alloc::{format, string::String, vec::Vec};
+use core::{convert::TryFrom, fmt};
+
+/// USB HID usage page of keyboard keys, which legends match by label
+const KEYBOARD_USAGE_PAGE: u16 = 0x07;
+
+/// Width from which an unlabelled key is taken for a space bar
+const SPACE_BAR_WIDTH: f32 = 4.0;
+
+/// Characters of the US layout's keys with shift held
+const US_SHIFTED: [(KeyMappingId, &str); 21] = [
+    (KeyMappingId::Backquote, "~"),
+    (KeyMappingId::Digit1, "!"),
+    (KeyMappingId::Digit2, "@"),
+    (KeyMappingId::Digit3, "#"),
+    (KeyMappingId::Digit4, "$"),
+    (KeyMappingId::Digit5, "%"),
+    (KeyMappingId::Digit6, "^"),
+    (KeyMappingId::Digit7, "&"),
+    (KeyMappingId::Digit8, "*"),
+    (KeyMappingId::Digit9, "("),
+    (KeyMappingId::Digit0, ")"),
+    (KeyMappingId::Minus, "_"),
+    (KeyMappingId::Equal, "+"),
+    (KeyMappingId::BracketLeft, "{"),
+    (KeyMappingId::BracketRight, "}"),
+    (KeyMappingId::Backslash, "|"),
+    (KeyMappingId::Semicolon, ":"),
+    (KeyMappingId::Quote, "\""),
+    (KeyMappingId::Comma, "<"),
+    (KeyMappingId::Period, ">"),
+    (KeyMappingId::Slash, "?"),
+];
+
+/// Prefix of the short labels of number pad keys, which legends may leave out
+const NUMPAD_LABEL_PREFIX: &str = "Num ";
+
+// How well the legends of a key match an id
+const MATCH_NUMPAD: u8 = 1;
+const MATCH_LABEL: u8 = 2;
+const MATCH_NAME: u8 = u8::MAX;
+
+/// Error returned when a keyboard-layout-editor file can't be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseKleError {
+    /// Line of the file that could not be parsed, starting at 1
+    pub line: usize,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl ParseKleError {
+    pub(crate) fn new(line: usize, message: impl Into<String>) -> ParseKleError {
+        ParseKleError {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseKleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl core::error::Error for ParseKleError {}
+
+/// A key whose legends don't name any key that is left
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnmatchedKey {
+    /// Legends of the key, without the empty ones
+    pub legends: Vec<String>,
+    /// Row of the key, counting from the top row at 0
+    pub row: u8,
+    /// Outline of the key, before rotation
+    pub rect: KeyRect,
+    /// Second part of keys that aren't rectangular, before rotation
+    pub secondary: Option<KeyRect>,
+    /// Rotation of the key, applied to both rectangles
+    pub rotation: KeyRotation,
+}
+
+/// A physical layout imported from keyboard-layout-editor
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KleLayout {
+    /// Name from the layout's metadata, empty if it has none
+    pub name: String,
+    /// Keys bound to a [`KeyMappingId`], from left to right and top to bottom
+    pub keys: Vec<PhysicalKey>,
+    /// Keys that couldn't be bound to a [`KeyMappingId`], in the same order
+    pub unmatched: Vec<UnmatchedKey>,
+}
+
+/// Properties that carry over from key to key while reading the rows
+#[derive(Default)]
+struct Cursor {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    x2: f32,
+    y2: f32,
+    width2: Option<f32>,
+    height2: Option<f32>,
+    rotation: KeyRotation,
+    decal: bool,
+}
+
+impl Cursor {
+    fn reset_key(&mut self) {
+        self.width = 1.0;
+        self.height = 1.0;
+        self.x2 = 0.0;
+        self.y2 = 0.0;
+        self.width2 = None;
+        self.height2 = None;
+        self.decal = false;
+    }
+
+    fn apply(&mut self, line: usize, properties: &[(String, Value)]) -> Result<(), ParseKleError> {
+        let number = |name: &str, value: &Value| match value {
+            Value::Number(number) => Ok(*number as f32),
+            _ => Err(ParseKleError::new(
+                line,
+                format!("property `{}` is not a number", name),
+            )),
+        };
+
+        // The rotation comes first, as changing its center moves the cursor there
+        for (name, value) in properties {
+            match name.as_str() {
+                "r" => self.rotation.angle = number(name, value)?,
+                "rx" => {
+                    self.rotation.x = number(name, value)?;
+                    self.x = self.rotation.x;
+                    self.y = self.rotation.y;
+                }
+                "ry" => {
+                    self.rotation.y = number(name, value)?;
+                    self.x = self.rotation.x;
+                    self.y = self.rotation.y;
+                }
+                _ => {}
+            }
+        }
+        for (name, value) in properties {
+            match name.as_str() {
+                "x" => self.x += number(name, value)?,
+                "y" => self.y += number(name, value)?,
+                "w" => self.width = number(name, value)?,
+                "h" => self.height = number(name, value)?,
+                "x2" => self.x2 = number(name, value)?,
+                "y2" => self.y2 = number(name, value)?,
+                "w2" => self.width2 = Some(number(name, value)?),
+                "h2" => self.height2 = Some(number(name, value)?),
+                "d" => self.decal = *value == Value::Bool(true),
+                // Colors, fonts, alignment and the other looks of a key
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A key read from the rows, before it is bound to an id
+struct KleKey {
+    legends: Vec<String>,
+    row: u8,
+    rect: KeyRect,
+    secondary: Option<KeyRect>,
+    rotation: KeyRotation,
+}
+
+impl KleKey {
+    /// Find the ids that the legends could name, with how well they match, best first
+    fn candidates(&self) -> Vec<(u8, KeyMappingId)> {
+        let mut candidates: Vec<(u8, KeyMappingId)> = self
+            .legends
+            .iter()
+            .filter_map(|legend| parse_name(legend))
+            .map(|id| (MATCH_NAME, id))
+            .collect();
+
+        candidates.extend(
+            KeyMap::in_usb_page(KEYBOARD_USAGE_PAGE).filter_map(|key_map| {
+                let labels = [LabelStyle::Pc, LabelStyle::Mac].map(|style| key_map.label(style));
+                let shifted = US_SHIFTED
+                    .iter()
+                    .find(|(id, _)| *id == key_map.id)
+                    .map(|(_, shifted)| *shifted);
+                let names_key = |legend: &&String| {
+                    labels.iter().any(|label| {
+                        legend.eq_ignore_ascii_case(label.short)
+                            || legend.eq_ignore_ascii_case(label.long)
+                            || label.glyph == Some(legend.as_str())
+                    }) || shifted == Some(legend.as_str())
+                };
+                let names_numpad_key = |legend: &String| {
+                    labels[0]
+                        .short
+                        .strip_prefix(NUMPAD_LABEL_PREFIX)
+                        .is_some_and(|label| legend.eq_ignore_ascii_case(label))
+                };
+
+                let matches = self.legends.iter().filter(names_key).count();
+                if matches > 0 {
+                    Some((MATCH_LABEL + matches as u8 - 1, key_map.id))
+                } else if self.legends.iter().any(names_numpad_key) {
+                    Some((MATCH_NUMPAD, key_map.id))
+                } else {
+                    None
+                }
+            }),
+        );
+
+        if self.legends.is_empty() && self.rect.width >= SPACE_BAR_WIDTH {
+            candidates.push((MATCH_LABEL, KeyMappingId::Space));
+        }
+
+        // Keep the best match of each id
+        candidates.sort_by(|(a, _), (b, _)| b.cmp(a));
+        let mut unique: Vec<(u8, KeyMappingId)> = Vec::with_capacity(candidates.len());
+        for (score, id) in candidates {
+            if !unique.iter().any(|(_, unique_id)| *unique_id == id) {
+                unique.push((score, id));
+            }
+        }
+        unique
+    }
+}
+
+/// Parse a legend that is the name of a code or an id, like `KeyA` or `UsA`
+fn parse_name(legend: &str) -> Option<KeyMappingId> {
+    match legend.parse::<KeyMappingCode>() {
+        Ok(code) => Some(KeyMap::from(code).id),
+        Err(_) => KeyMappingId::ALL
+            .iter()
+            .find(|id| id.name() == legend)
+            .copied(),
+    }
+}
+
+/// Read the keys of the rows, in order
+fn read_keys(rows: &[(usize, Vec<(usize, Value)>)]) -> Result<Vec<KleKey>, ParseKleError> {
+    let mut keys = Vec::new();
+    let mut cursor = Cursor::default();
+    cursor.reset_key();
+
+    for (row, (_, items)) in rows.iter().enumerate() {
+        let row = u8::try_from(row).unwrap_or(u8::MAX);
+        for (line, item) in items {
+            let legends = match item {
+                Value::Object(properties) => {
+                    cursor.apply(*line, properties)?;
+                    continue;
+                }
+                Value::String(legends) => legends,
+                _ => {
+                    return Err(ParseKleError::new(
+                        *line,
+                        "expected a key's legends or properties",
+                    ))
+                }
+            };
+
+            if !cursor.decal {
+                let rect = KeyRect::new(cursor.x, cursor.y, cursor.width, cursor.height);
+                let secondary = KeyRect::new(
+                    cursor.x + cursor.x2,
+                    cursor.y + cursor.y2,
+                    cursor.width2.unwrap_or(cursor.width),
+                    cursor.height2.unwrap_or(cursor.height),
+                );
+                keys.push(KleKey {
+                    legends: legends
+                        .split('\n')
+                        .map(str::trim)
+                        .filter(|legend| !legend.is_empty())
+                        .map(String::from)
+                        .collect(),
+                    row,
+                    rect,
+                    secondary: (secondary != rect).then_some(secondary),
+                    rotation: cursor.rotation,
+                });
+            }
+            cursor.x += cursor.width;
+            cursor.reset_key();
+        }
+        cursor.y += 1.0;
+        cursor.x = cursor.rotation.x;
+    }
+    Ok(keys)
+}
+
+/// Bind keys to ids, best matches first
+///
+/// Among equally good matches, keys with fewer ids left to choose from go first, then keys that
+/// come first.
+fn bind_keys(keys: &[KleKey]) -> Vec<Option<KeyMappingId>> {
+    let candidates: Vec<Vec<(u8, KeyMappingId)>> = keys.iter().map(KleKey::candidates).collect();
+    let mut bound: Vec<Option<KeyMappingId>> = alloc::vec![None; keys.len()];
+    let mut done = alloc::vec![false; keys.len()];
+
+    loop {
+        let is_free = |id: &KeyMappingId| !bound.contains(&Some(*id));
+        let best = candidates
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !done[*index])
+            .filter_map(|(index, candidates)| {
+                let mut free = candidates.iter().filter(|(_, id)| is_free(id));
+                let (score, id) = free.next()?;
+                Some((*score, free.count(), index, *id))
+            })
+            .min_by_key(|(score, others, index, _)| (u8::MAX - score, *others, *index));
+
+        match best {
+            Some((_, _, index, id)) => {
+                bound[index] = Some(id);
+                done[index] = true;
+            }
+            None => return bound,
+        }
+    }
+}
+
+impl KleLayout {
+    /// Parse a keyboard-layout-editor JSON file, or its raw data
+    pub fn parse(json: &str) -> Result<KleLayout, ParseKleError> {
+        let mut name = String::new();
+        let mut rows = Vec::new();
+        for (index, (line, item)) in json::parse(json)?.into_iter().enumerate() {
+            match item {
+                Value::Array(row) => rows.push((line, row)),
+                Value::Object(metadata) if index == 0 => {
+                    if let Some((_, Value::String(value))) =
+                        metadata.into_iter().find(|(key, _)| key == "name")
+                    {
+                        name = value;
+                    }
+                }
+                _ => return Err(ParseKleError::new(line, "expected a row of keys")),
+            }
+        }
+
+        let keys = read_keys(&rows)?;
+        let bound = bind_keys(&keys);
+
+        let mut layout = KleLayout {
+            name,
+            ..KleLayout::default()
+        };
+        for (key, id) in keys.into_iter().zip(bound) {
+            match id {
+                Some(id) => layout.keys.push(PhysicalKey {
+                    id,
+                    row: key.row,
+                    rect: key.rect,
+                    secondary: key.secondary,
+                    rotation: key.rotation,
+                }),
+                None => layout.unmatched.push(UnmatchedKey {
+                    legends: key.legends,
+                    row: key.row,
+                    rect: key.rect,
+                    secondary: key.secondary,
+                    rotation: key.rotation,
+                }),
+            }
+        }
+        Ok(layout)
+    }
+
+    /// Get the keys that were bound to ids as a [`PhysicalLayout`]
+    pub fn physical_layout(&self) -> PhysicalLayout<'_> {
+        PhysicalLayout {
+            name: &self.name,
+            keys: &self.keys,
+        }
+    }
+}
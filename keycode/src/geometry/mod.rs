@@ -0,0 +1,227 @@
+//! Physical keyboards: where each key is and how big it is, for on-screen keyboards and heatmaps.
+//!
+//! Positions and sizes are in key units, where one unit (`1u`) is the width of a letter key,
+//! usually 19.05 mm. `x` grows to the right and `y` grows down, starting at the top left corner
+//! of the keyboard, like in [keyboard-layout-editor](http://www.keyboard-layout-editor.com).
+//!
+//! Built-in layouts cover the common standards and form factors, see [`PhysicalLayout::ALL`].
+//! With the `alloc` feature, other keyboards can be imported from keyboard-layout-editor's JSON,
+//! see `KleLayout::parse`.
+//!
+//! ```
+//! use keycode::{geometry::PhysicalLayout, KeyMappingId};
+//!
+//! let layout = PhysicalLayout::ANSI_104;
+//! let space = layout.key(KeyMappingId::Space).unwrap();
+//! assert_eq!((space.rect.x, space.rect.width), (3.75, 6.25));
+//! assert_eq!((layout.width(), layout.height()), (22.5, 6.5));
+//! ```
+
+mod builtin;
+#[cfg(feature = "alloc")]
+mod json;
+#[cfg(feature = "alloc")]
+mod kle;
+
+#[cfg(feature = "alloc")]
+pub use self::kle::*;
+
+use crate::KeyMappingId;
+
+/// A rectangle, in key units
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyRect {
+    /// Distance of the left edge from the left of the keyboard
+    pub x: f32,
+    /// Distance of the top edge from the top of the keyboard
+    pub y: f32,
+    /// Width of the rectangle
+    pub width: f32,
+    /// Height of the rectangle
+    pub height: f32,
+}
+
+impl KeyRect {
+    /// Create a rectangle from its top left corner and size
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> KeyRect {
+        KeyRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Get the distance of the right edge from the left of the keyboard
+    pub fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    /// Get the distance of the bottom edge from the top of the keyboard
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+}
+
+/// Rotation of a key around a point, used by split and ergonomic keyboards
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyRotation {
+    /// Clockwise angle in degrees
+    pub angle: f32,
+    /// Distance of the center of rotation from the left of the keyboard
+    pub x: f32,
+    /// Distance of the center of rotation from the top of the keyboard
+    pub y: f32,
+}
+
+impl KeyRotation {
+    /// No rotation
+    pub const NONE: KeyRotation = KeyRotation {
+        angle: 0.0,
+        x: 0.0,
+        y: 0.0,
+    };
+}
+
+/// A key of a physical keyboard
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicalKey {
+    /// Id of the key
+    pub id: KeyMappingId,
+    /// Row of the key, counting from the top row at 0
+    pub row: u8,
+    /// Outline of the key, before rotation
+    pub rect: KeyRect,
+    /// Second part of keys that aren't rectangular, like the ISO enter key, before rotation
+    ///
+    /// The outline of the key is the union of both rectangles.
+    pub secondary: Option<KeyRect>,
+    /// Rotation of the key, applied to both rectangles
+    pub rotation: KeyRotation,
+}
+
+impl PhysicalKey {
+    const fn with_secondary(mut self, secondary: KeyRect) -> PhysicalKey {
+        self.secondary = Some(secondary);
+        self
+    }
+}
+
+/// Where the keys of a physical keyboard are
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicalLayout<'a> {
+    /// Name of the layout
+    pub name: &'a str,
+    /// Every key, from left to right and top to bottom
+    pub keys: &'a [PhysicalKey],
+}
+
+impl PhysicalLayout<'static> {
+    /// Full size ANSI keyboard, with 104 keys
+    pub const ANSI_104: PhysicalLayout<'static> = PhysicalLayout {
+        name: "ANSI 104",
+        keys: builtin::ANSI_104,
+    };
+    /// Full size ISO keyboard, with 105 keys
+    pub const ISO_105: PhysicalLayout<'static> = PhysicalLayout {
+        name: "ISO 105",
+        keys: builtin::ISO_105,
+    };
+    /// Full size Japanese keyboard, with 109 keys
+    pub const JIS_109: PhysicalLayout<'static> = PhysicalLayout {
+        name: "JIS 109",
+        keys: builtin::JIS_109,
+    };
+    /// Full size Brazilian keyboard, with 107 keys
+    ///
+    /// An ISO keyboard with an extra key left of the right shift key, and a number pad with a
+    /// comma and a period key where the plus key's lower half would be.
+    pub const ABNT2_107: PhysicalLayout<'static> = PhysicalLayout {
+        name: "ABNT2 107",
+        keys: builtin::ABNT2_107,
+    };
+    /// Tenkeyless ANSI keyboard: the full size keyboard without its number pad, with 87 keys
+    pub const ANSI_TKL: PhysicalLayout<'static> = PhysicalLayout {
+        name: "ANSI TKL",
+        keys: builtin::ANSI_TKL,
+    };
+    /// Tenkeyless ISO keyboard: the full size keyboard without its number pad, with 88 keys
+    pub const ISO_TKL: PhysicalLayout<'static> = PhysicalLayout {
+        name: "ISO TKL",
+        keys: builtin::ISO_TKL,
+    };
+    /// 60% ANSI keyboard: only the main block, with 61 keys
+    ///
+    /// Escape takes the place of the backquote key, the other keys that are missing are
+    /// usually reached through a function layer.
+    pub const ANSI_60: PhysicalLayout<'static> = PhysicalLayout {
+        name: "ANSI 60%",
+        keys: builtin::ANSI_60,
+    };
+    /// 60% ISO keyboard: only the main block, with 62 keys
+    ///
+    /// Escape takes the place of the backquote key, the other keys that are missing are
+    /// usually reached through a function layer.
+    pub const ISO_60: PhysicalLayout<'static> = PhysicalLayout {
+        name: "ISO 60%",
+        keys: builtin::ISO_60,
+    };
+
+    /// Every built-in layout
+    pub const ALL: &'static [PhysicalLayout<'static>] = &[
+        PhysicalLayout::ANSI_104,
+        PhysicalLayout::ISO_105,
+        PhysicalLayout::JIS_109,
+        PhysicalLayout::ABNT2_107,
+        PhysicalLayout::ANSI_TKL,
+        PhysicalLayout::ISO_TKL,
+        PhysicalLayout::ANSI_60,
+        PhysicalLayout::ISO_60,
+    ];
+}
+
+impl<'a> PhysicalLayout<'a> {
+    /// Get the position of a key, or `None` if the keyboard doesn't have it
+    pub fn key(&self, id: KeyMappingId) -> Option<&'a PhysicalKey> {
+        self.keys.iter().find(|key| key.id == id)
+    }
+
+    /// Iterate over the keys of a row, from left to right
+    pub fn row(&self, row: u8) -> impl Iterator<Item = &'a PhysicalKey> {
+        self.keys.iter().filter(move |key| key.row == row)
+    }
+
+    /// Get the number of rows
+    pub fn rows(&self) -> u8 {
+        self.keys
+            .iter()
+            .map(|key| key.row.saturating_add(1))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get the width of the keyboard, from the left edge to the rightmost key
+    ///
+    /// Rotation is ignored.
+    pub fn width(&self) -> f32 {
+        self.extent(KeyRect::right)
+    }
+
+    /// Get the height of the keyboard, from the top edge to the lowest key
+    ///
+    /// Rotation is ignored.
+    pub fn height(&self) -> f32 {
+        self.extent(KeyRect::bottom)
+    }
+
+    fn extent(&self, edge: fn(&KeyRect) -> f32) -> f32 {
+        self.keys
+            .iter()
+            .flat_map(|key| core::iter::once(&key.rect).chain(key.secondary.as_ref()))
+            .map(edge)
+            .fold(0.0, f32::max)
+    }
+}
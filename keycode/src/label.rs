@@ -0,0 +1,345 @@
+//! Human readable labels of keys, for configuration screens and shortcut hints
+//!
+//! Every key has a short label for key caps and compact lists, a long label for menus and
+//! descriptions, and maybe a glyph. The modifier and editing keys are named after the convention
+//! of the platform: a PC keyboard has `Ctrl`, `Alt` and `Win` keys, while a Mac keyboard has
+//! `Control`, `Option` and `Command` keys with the `⌃`, `⌥` and `⌘` glyphs.
+//!
+//! ```
+//! use keycode::{label::LabelStyle, KeyMappingId};
+//!
+//! let pc = KeyMappingId::MetaLeft.label(LabelStyle::Pc);
+//! assert_eq!((pc.short, pc.long), ("Win", "Left Windows"));
+//!
+//! let mac = KeyMappingId::MetaLeft.label(LabelStyle::Mac);
+//! assert_eq!((mac.short, mac.long, mac.glyph), ("Command", "Left Command", Some("⌘")));
+//! ```
+
+use crate::{KeyMap, KeyMappingId};
+
+/// Naming convention of the labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LabelStyle {
+    /// Windows and Linux keyboards
+    #[default]
+    Pc,
+    /// Apple keyboards
+    Mac,
+}
+
+/// Display text of a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyLabel {
+    /// Label for key caps and compact lists, like `Shift`, `Num 7` or `Vol+`
+    pub short: &'static str,
+    /// Label for menus and descriptions, like `Left Shift`, `Num Pad 7` or `Volume Up`
+    pub long: &'static str,
+    /// Symbol printed on keys or in shortcut hints, like `⇧` or `⌘`
+    pub glyph: Option<&'static str>,
+}
+
+impl KeyLabel {
+    /// Get the glyph, or the short label of keys without one
+    pub const fn symbol(&self) -> &'static str {
+        match self.glyph {
+            Some(glyph) => glyph,
+            None => self.short,
+        }
+    }
+}
+
+const fn label(short: &'static str, long: &'static str, glyph: Option<&'static str>) -> KeyLabel {
+    KeyLabel { short, long, glyph }
+}
+
+impl KeyMappingId {
+    /// Get the display text of the key in a platform's naming convention
+    pub const fn label(self, style: LabelStyle) -> KeyLabel {
+        match style {
+            LabelStyle::Pc => pc_label(self),
+            LabelStyle::Mac => match mac_label(self) {
+                Some(label) => label,
+                None => pc_label(self),
+            },
+        }
+    }
+}
+
+impl KeyMap {
+    /// Get the display text of the key in a platform's naming convention
+    pub const fn label(&self, style: LabelStyle) -> KeyLabel {
+        self.id.label(style)
+    }
+}
+
+/// Labels of the keys that Apple names differently
+///
+/// Apple keyboards have `Delete` where PC keyboards have `Backspace`, `Forward Delete` where they
+/// have `Delete`, `Return` where they have `Enter`, and `Clear` where they have `Num Lock`.
+const fn mac_label(id: KeyMappingId) -> Option<KeyLabel> {
+    let label = match id {
+        KeyMappingId::Enter => label("Return", "Return", Some("↩")),
+        KeyMappingId::Escape => label("Esc", "Escape", Some("⎋")),
+        KeyMappingId::Backspace => label("Delete", "Delete", Some("⌫")),
+        KeyMappingId::Del => label("Del", "Forward Delete", Some("⌦")),
+        KeyMappingId::Home => label("Home", "Home", Some("↖")),
+        KeyMappingId::End => label("End", "End", Some("↘")),
+        KeyMappingId::PageUp => label("PgUp", "Page Up", Some("⇞")),
+        KeyMappingId::PageDown => label("PgDn", "Page Down", Some("⇟")),
+        KeyMappingId::NumLock => label("Clear", "Clear", Some("⌧")),
+        KeyMappingId::NumpadEnter => label("Enter", "Enter", Some("⌤")),
+        KeyMappingId::ControlLeft => label("Control", "Left Control", Some("⌃")),
+        KeyMappingId::ControlRight => label("Control", "Right Control", Some("⌃")),
+        KeyMappingId::AltLeft => label("Option", "Left Option", Some("⌥")),
+        KeyMappingId::AltRight => label("Option", "Right Option", Some("⌥")),
+        KeyMappingId::MetaLeft => label("Command", "Left Command", Some("⌘")),
+        KeyMappingId::MetaRight => label("Command", "Right Command", Some("⌘")),
+        KeyMappingId::Fn => label("fn", "Function", Some("🌐")),
+        KeyMappingId::Lang1 => label("Kana", "Kana", None),
+        KeyMappingId::Lang2 => label("Eisu", "Eisu", None),
+        _ => return None,
+    };
+    Some(label)
+}
+
+/// Labels of every key, named as on PC keyboards
+const fn pc_label(id: KeyMappingId) -> KeyLabel {
+    match id {
+        KeyMappingId::None => label("None", "No Key", None),
+        KeyMappingId::Hyper => label("Hyper", "Hyper", None),
+        KeyMappingId::Super => label("Super", "Super", None),
+        KeyMappingId::Fn => label("Fn", "Function", None),
+        KeyMappingId::FnLock => label("Fn Lock", "Function Lock", None),
+        KeyMappingId::Suspend => label("Suspend", "Suspend", None),
+        KeyMappingId::Resume => label("Resume", "Resume", None),
+        KeyMappingId::Turbo => label("Turbo", "Turbo", None),
+        KeyMappingId::Sleep => label("Sleep", "Sleep", Some("⏾")),
+        KeyMappingId::WakeUp => label("Wake", "Wake Up", None),
+        KeyMappingId::DisplayToggleIntExt => label("Display", "Toggle Display", None),
+        KeyMappingId::UsbReserved => label("Reserved", "Reserved", None),
+        KeyMappingId::UsbErrorRollOver => label("Rollover", "Rollover Error", None),
+        KeyMappingId::UsbPostFail => label("POST Fail", "POST Failure", None),
+        KeyMappingId::UsbErrorUndefined => label("Error", "Undefined Error", None),
+        KeyMappingId::UsA => label("A", "A", None),
+        KeyMappingId::UsB => label("B", "B", None),
+        KeyMappingId::UsC => label("C", "C", None),
+        KeyMappingId::UsD => label("D", "D", None),
+        KeyMappingId::UsE => label("E", "E", None),
+        KeyMappingId::UsF => label("F", "F", None),
+        KeyMappingId::UsG => label("G", "G", None),
+        KeyMappingId::UsH => label("H", "H", None),
+        KeyMappingId::UsI => label("I", "I", None),
+        KeyMappingId::UsJ => label("J", "J", None),
+        KeyMappingId::UsK => label("K", "K", None),
+        KeyMappingId::UsL => label("L", "L", None),
+        KeyMappingId::UsM => label("M", "M", None),
+        KeyMappingId::UsN => label("N", "N", None),
+        KeyMappingId::UsO => label("O", "O", None),
+        KeyMappingId::UsP => label("P", "P", None),
+        KeyMappingId::UsQ => label("Q", "Q", None),
+        KeyMappingId::UsR => label("R", "R", None),
+        KeyMappingId::UsS => label("S", "S", None),
+        KeyMappingId::UsT => label("T", "T", None),
+        KeyMappingId::UsU => label("U", "U", None),
+        KeyMappingId::UsV => label("V", "V", None),
+        KeyMappingId::UsW => label("W", "W", None),
+        KeyMappingId::UsX => label("X", "X", None),
+        KeyMappingId::UsY => label("Y", "Y", None),
+        KeyMappingId::UsZ => label("Z", "Z", None),
+        KeyMappingId::Digit1 => label("1", "1", None),
+        KeyMappingId::Digit2 => label("2", "2", None),
+        KeyMappingId::Digit3 => label("3", "3", None),
+        KeyMappingId::Digit4 => label("4", "4", None),
+        KeyMappingId::Digit5 => label("5", "5", None),
+        KeyMappingId::Digit6 => label("6", "6", None),
+        KeyMappingId::Digit7 => label("7", "7", None),
+        KeyMappingId::Digit8 => label("8", "8", None),
+        KeyMappingId::Digit9 => label("9", "9", None),
+        KeyMappingId::Digit0 => label("0", "0", None),
+        KeyMappingId::Enter => label("Enter", "Enter", Some("↵")),
+        KeyMappingId::Escape => label("Esc", "Escape", None),
+        KeyMappingId::Backspace => label("Backspace", "Backspace", Some("⌫")),
+        KeyMappingId::Tab => label("Tab", "Tab", Some("⇥")),
+        KeyMappingId::Space => label("Space", "Space", None),
+        KeyMappingId::Minus => label("-", "Minus", None),
+        KeyMappingId::Equal => label("=", "Equal", None),
+        KeyMappingId::BracketLeft => label("[", "Left Bracket", None),
+        KeyMappingId::BracketRight => label("]", "Right Bracket", None),
+        KeyMappingId::Backslash => label("\\", "Backslash", None),
+        KeyMappingId::IntlHash => label("#", "International Hash", None),
+        KeyMappingId::Semicolon => label(";", "Semicolon", None),
+        KeyMappingId::Quote => label("'", "Quote", None),
+        KeyMappingId::Backquote => label("`", "Backquote", None),
+        KeyMappingId::Comma => label(",", "Comma", None),
+        KeyMappingId::Period => label(".", "Period", None),
+        KeyMappingId::Slash => label("/", "Slash", None),
+        KeyMappingId::CapsLock => label("Caps Lock", "Caps Lock", Some("⇪")),
+        KeyMappingId::F1 => label("F1", "F1", None),
+        KeyMappingId::F2 => label("F2", "F2", None),
+        KeyMappingId::F3 => label("F3", "F3", None),
+        KeyMappingId::F4 => label("F4", "F4", None),
+        KeyMappingId::F5 => label("F5", "F5", None),
+        KeyMappingId::F6 => label("F6", "F6", None),
+        KeyMappingId::F7 => label("F7", "F7", None),
+        KeyMappingId::F8 => label("F8", "F8", None),
+        KeyMappingId::F9 => label("F9", "F9", None),
+        KeyMappingId::F10 => label("F10", "F10", None),
+        KeyMappingId::F11 => label("F11", "F11", None),
+        KeyMappingId::F12 => label("F12", "F12", None),
+        KeyMappingId::PrintScreen => label("PrtSc", "Print Screen", None),
+        KeyMappingId::ScrollLock => label("ScrLk", "Scroll Lock", None),
+        KeyMappingId::Pause => label("Pause", "Pause", None),
+        KeyMappingId::Insert => label("Ins", "Insert", None),
+        KeyMappingId::Home => label("Home", "Home", None),
+        KeyMappingId::PageUp => label("PgUp", "Page Up", None),
+        KeyMappingId::Del => label("Del", "Delete", Some("⌦")),
+        KeyMappingId::End => label("End", "End", None),
+        KeyMappingId::PageDown => label("PgDn", "Page Down", None),
+        KeyMappingId::ArrowRight => label("Right", "Right Arrow", Some("→")),
+        KeyMappingId::ArrowLeft => label("Left", "Left Arrow", Some("←")),
+        KeyMappingId::ArrowDown => label("Down", "Down Arrow", Some("↓")),
+        KeyMappingId::ArrowUp => label("Up", "Up Arrow", Some("↑")),
+        KeyMappingId::NumLock => label("Num Lock", "Num Lock", None),
+        KeyMappingId::NumpadDivide => label("Num /", "Num Pad Divide", None),
+        KeyMappingId::NumpadMultiply => label("Num *", "Num Pad Multiply", None),
+        KeyMappingId::NumpadSubtract => label("Num -", "Num Pad Subtract", None),
+        KeyMappingId::NumpadAdd => label("Num +", "Num Pad Add", None),
+        KeyMappingId::NumpadEnter => label("Num Enter", "Num Pad Enter", Some("↵")),
+        KeyMappingId::Numpad1 => label("Num 1", "Num Pad 1", None),
+        KeyMappingId::Numpad2 => label("Num 2", "Num Pad 2", None),
+        KeyMappingId::Numpad3 => label("Num 3", "Num Pad 3", None),
+        KeyMappingId::Numpad4 => label("Num 4", "Num Pad 4", None),
+        KeyMappingId::Numpad5 => label("Num 5", "Num Pad 5", None),
+        KeyMappingId::Numpad6 => label("Num 6", "Num Pad 6", None),
+        KeyMappingId::Numpad7 => label("Num 7", "Num Pad 7", None),
+        KeyMappingId::Numpad8 => label("Num 8", "Num Pad 8", None),
+        KeyMappingId::Numpad9 => label("Num 9", "Num Pad 9", None),
+        KeyMappingId::Numpad0 => label("Num 0", "Num Pad 0", None),
+        KeyMappingId::NumpadDecimal => label("Num .", "Num Pad Decimal", None),
+        KeyMappingId::IntlBackslash => label("\\", "International Backslash", None),
+        KeyMappingId::ContextMenu => label("Menu", "Context Menu", Some("≣")),
+        KeyMappingId::Power => label("Power", "Power", Some("⏻")),
+        KeyMappingId::NumpadEqual => label("Num =", "Num Pad Equal", None),
+        KeyMappingId::F13 => label("F13", "F13", None),
+        KeyMappingId::F14 => label("F14", "F14", None),
+        KeyMappingId::F15 => label("F15", "F15", None),
+        KeyMappingId::F16 => label("F16", "F16", None),
+        KeyMappingId::F17 => label("F17", "F17", None),
+        KeyMappingId::F18 => label("F18", "F18", None),
+        KeyMappingId::F19 => label("F19", "F19", None),
+        KeyMappingId::F20 => label("F20", "F20", None),
+        KeyMappingId::F21 => label("F21", "F21", None),
+        KeyMappingId::F22 => label("F22", "F22", None),
+        KeyMappingId::F23 => label("F23", "F23", None),
+        KeyMappingId::F24 => label("F24", "F24", None),
+        KeyMappingId::Open => label("Open", "Open", None),
+        KeyMappingId::Help => label("Help", "Help", None),
+        KeyMappingId::Select => label("Select", "Select", None),
+        KeyMappingId::Again => label("Again", "Again", None),
+        KeyMappingId::Undo => label("Undo", "Undo", None),
+        KeyMappingId::Cut => label("Cut", "Cut", None),
+        KeyMappingId::Copy => label("Copy", "Copy", None),
+        KeyMappingId::Paste => label("Paste", "Paste", None),
+        KeyMappingId::Find => label("Find", "Find", None),
+        KeyMappingId::VolumeMute => label("Mute", "Volume Mute", Some("🔇")),
+        KeyMappingId::VolumeUp => label("Vol+", "Volume Up", Some("🔊")),
+        KeyMappingId::VolumeDown => label("Vol-", "Volume Down", Some("🔉")),
+        KeyMappingId::NumpadComma => label("Num ,", "Num Pad Comma", None),
+        KeyMappingId::IntlRo => label("Ro", "International Ro", None),
+        KeyMappingId::KanaMode => label("Kana", "Kana Mode", None),
+        KeyMappingId::IntlYen => label("¥", "International Yen", None),
+        KeyMappingId::Convert => label("Henkan", "Convert", None),
+        KeyMappingId::NonConvert => label("Muhenkan", "Non-Convert", None),
+        KeyMappingId::Lang1 => label("Han/Yeong", "Hangul/English", None),
+        KeyMappingId::Lang2 => label("Hanja", "Hanja", None),
+        KeyMappingId::Lang3 => label("Katakana", "Katakana", None),
+        KeyMappingId::Lang4 => label("Hiragana", "Hiragana", None),
+        KeyMappingId::Lang5 => label("Zenkaku", "Zenkaku/Hankaku", None),
+        KeyMappingId::Abort => label("Abort", "Abort", None),
+        KeyMappingId::Props => label("Props", "Properties", None),
+        KeyMappingId::NumpadParenLeft => label("Num (", "Num Pad Left Parenthesis", None),
+        KeyMappingId::NumpadParenRight => label("Num )", "Num Pad Right Parenthesis", None),
+        KeyMappingId::NumpadBackspace => label("Num Bksp", "Num Pad Backspace", None),
+        KeyMappingId::NumpadMemoryStore => label("Num MS", "Num Pad Memory Store", None),
+        KeyMappingId::NumpadMemoryRecall => label("Num MR", "Num Pad Memory Recall", None),
+        KeyMappingId::NumpadMemoryClear => label("Num MC", "Num Pad Memory Clear", None),
+        KeyMappingId::NumpadMemoryAdd => label("Num M+", "Num Pad Memory Add", None),
+        KeyMappingId::NumpadMemorySubtract => label("Num M-", "Num Pad Memory Subtract", None),
+        KeyMappingId::NumpadSignChange => label("Num +/-", "Num Pad Change Sign", None),
+        KeyMappingId::NumpadClear => label("Num Clear", "Num Pad Clear", None),
+        KeyMappingId::NumpadClearEntry => label("Num CE", "Num Pad Clear Entry", None),
+        KeyMappingId::ControlLeft => label("Ctrl", "Left Control", None),
+        KeyMappingId::ShiftLeft => label("Shift", "Left Shift", Some("⇧")),
+        KeyMappingId::AltLeft => label("Alt", "Left Alt", None),
+        KeyMappingId::MetaLeft => label("Win", "Left Windows", Some("⊞")),
+        KeyMappingId::ControlRight => label("Ctrl", "Right Control", None),
+        KeyMappingId::ShiftRight => label("Shift", "Right Shift", Some("⇧")),
+        KeyMappingId::AltRight => label("Alt", "Right Alt", None),
+        KeyMappingId::MetaRight => label("Win", "Right Windows", Some("⊞")),
+        KeyMappingId::Info => label("Info", "Info", None),
+        KeyMappingId::ClosedCaptionToggle => label("CC", "Closed Captions", None),
+        KeyMappingId::BrightnessUp => label("Bright+", "Brightness Up", Some("🔆")),
+        KeyMappingId::BrightnessDown => label("Bright-", "Brightness Down", Some("🔅")),
+        KeyMappingId::BrightnessToggle => label("Bright", "Toggle Brightness", None),
+        KeyMappingId::BrightnessMinimium => label("Bright Min", "Minimum Brightness", None),
+        KeyMappingId::BrightnessMaximum => label("Bright Max", "Maximum Brightness", None),
+        KeyMappingId::BrightnessAuto => label("Bright Auto", "Auto Brightness", None),
+        KeyMappingId::MediaLast => label("Last", "Last Channel", None),
+        KeyMappingId::LaunchPhone => label("Phone", "Phone", None),
+        KeyMappingId::ProgramGuide => label("Guide", "Program Guide", None),
+        KeyMappingId::Exit => label("Exit", "Exit", None),
+        KeyMappingId::ChannelUp => label("Ch+", "Channel Up", None),
+        KeyMappingId::ChannelDown => label("Ch-", "Channel Down", None),
+        KeyMappingId::MediaPlay => label("Play", "Play", Some("⏵")),
+        KeyMappingId::MediaRecord => label("Record", "Record", Some("⏺")),
+        KeyMappingId::MediaFastForward => label("FF", "Fast Forward", Some("⏩")),
+        KeyMappingId::MediaRewind => label("Rewind", "Rewind", Some("⏪")),
+        KeyMappingId::MediaTrackNext => label("Next", "Next Track", Some("⏭")),
+        KeyMappingId::MediaTrackPrevious => label("Prev", "Previous Track", Some("⏮")),
+        KeyMappingId::MediaStop => label("Stop", "Stop", Some("⏹")),
+        KeyMappingId::Eject => label("Eject", "Eject", Some("⏏")),
+        KeyMappingId::MediaPlayPause => label("Play/Pause", "Play/Pause", Some("⏯")),
+        KeyMappingId::SpeechInputToggle => label("Dictate", "Dictation", None),
+        KeyMappingId::BassBoost => label("Bass", "Bass Boost", None),
+        KeyMappingId::MediaSelect => label("Media", "Media Select", None),
+        KeyMappingId::LaunchWordProcessor => label("Word", "Word Processor", None),
+        KeyMappingId::LaunchSpreadsheet => label("Sheet", "Spreadsheet", None),
+        KeyMappingId::LaunchMail => label("Mail", "Mail", None),
+        KeyMappingId::LaunchContacts => label("Contacts", "Contacts", None),
+        KeyMappingId::LaunchCalendar => label("Calendar", "Calendar", None),
+        KeyMappingId::LaunchApp2 => label("Calc", "Calculator", None),
+        KeyMappingId::LaunchApp1 => label("Computer", "My Computer", None),
+        KeyMappingId::LaunchInternetBrowser => label("Browser", "Web Browser", None),
+        KeyMappingId::LogOff => label("Log Off", "Log Off", None),
+        KeyMappingId::LockScreen => label("Lock", "Lock Screen", None),
+        KeyMappingId::LaunchControlPanel => label("Settings", "Control Panel", None),
+        KeyMappingId::SelectTask => label("Tasks", "Select Task", None),
+        KeyMappingId::LaunchDocuments => label("Docs", "Documents", None),
+        KeyMappingId::SpellCheck => label("Spell", "Spell Check", None),
+        KeyMappingId::LaunchKeyboardLayout => label("Layout", "Keyboard Layout", None),
+        KeyMappingId::LaunchScreenSaver => label("Saver", "Screen Saver", None),
+        KeyMappingId::LaunchAssistant => label("Assistant", "Assistant", None),
+        KeyMappingId::LaunchAudioBrowser => label("Music", "Audio Browser", None),
+        KeyMappingId::New => label("New", "New", None),
+        KeyMappingId::Close => label("Close", "Close", None),
+        KeyMappingId::Save => label("Save", "Save", None),
+        KeyMappingId::Print => label("Print", "Print", None),
+        KeyMappingId::BrowserSearch => label("Search", "Browser Search", None),
+        KeyMappingId::BrowserHome => label("Home", "Browser Home", None),
+        KeyMappingId::BrowserBack => label("Back", "Browser Back", None),
+        KeyMappingId::BrowserForward => label("Forward", "Browser Forward", None),
+        KeyMappingId::BrowserStop => label("Stop", "Browser Stop", None),
+        KeyMappingId::BrowserRefresh => label("Refresh", "Browser Refresh", None),
+        KeyMappingId::BrowserFavorites => label("Favorites", "Browser Favorites", None),
+        KeyMappingId::ZoomIn => label("Zoom+", "Zoom In", None),
+        KeyMappingId::ZoomOut => label("Zoom-", "Zoom Out", None),
+        KeyMappingId::ZoomToggle => label("Zoom", "Toggle Zoom", None),
+        KeyMappingId::Redo => label("Redo", "Redo", None),
+        KeyMappingId::MailReply => label("Reply", "Mail Reply", None),
+        KeyMappingId::MailForward => label("Fwd", "Mail Forward", None),
+        KeyMappingId::MailSend => label("Send", "Mail Send", None),
+        KeyMappingId::KeyboardLayoutSelect => label("Layout", "Select Keyboard Layout", None),
+        KeyMappingId::ShowAllWindows => label("Windows", "Show All Windows", None),
+    }
+}
@@ -0,0 +1,725 @@
+//! Import and export of Linux console (`loadkeys`) keymaps
+//!
+//! Keys are bound to [`KeyMappingId`]s through their keycodes, which are matched against the evdev
+//! values of the key mappings. `include` statements, `string` definitions and keysyms that don't
+//! produce text (function keys, `Meta_` keysyms, console actions, ...) are ignored. `compose`
+//! definitions are only kept for diacritics produced by a dead keysym, the rest are compose key
+//! sequences which a [`Layout`] doesn't describe.
+
+use super::{DeadKey, KeyOutput, Layout, ParseLayoutError, ShiftState};
+use crate::{KeyMap, KeyMapping, KeyMappingId};
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Write;
+
+const COMMENT_CHARS: [char; 2] = ['#', '!'];
+const LINE_CONTINUATION: char = '\\';
+const LETTER_PREFIX: char = '+';
+const UNICODE_PREFIX: &str = "U+";
+const HEX_PREFIX: &str = "0x";
+const CONTROL_PREFIX: &str = "Control_";
+const NO_SYMBOL: &str = "VoidSymbol";
+/// Types of kernel keysym codes, in their high byte, that type their low byte as a Latin-1
+/// character: `KT_LATIN`, and `KT_LETTER` for letters that follow caps lock
+const KT_LATIN: u16 = 0x00;
+const KT_LETTER: u16 = 0x0b;
+/// Marks kernel keysym codes in the kernel's own tables
+const KEYSYM_MARK: u16 = 0xf000;
+
+/// Modifiers of the kernel keymap, with their weight in the keymap number
+const MODIFIERS: [(&str, u16, ShiftState); 10] = [
+    ("plain", 0, ShiftState::empty()),
+    ("shift", 1, ShiftState::Shift),
+    ("altgr", 2, ShiftState::AltGr),
+    ("control", 4, ShiftState::Control),
+    ("alt", 8, ShiftState::Alt),
+    ("shiftl", 16, ShiftState::Shift),
+    ("shiftr", 32, ShiftState::Shift),
+    ("ctrll", 64, ShiftState::Control),
+    ("ctrlr", 128, ShiftState::Control),
+    ("capsshift", 256, ShiftState::CapsLock),
+];
+
+/// Dead keysyms, with the diacritic the kernel uses for them and the accent identifying them
+const DEAD_KEYSYMS: [(&str, char, char); 13] = [
+    ("dead_grave", '`', '`'),
+    ("dead_acute", '\'', '\u{b4}'),
+    ("dead_circumflex", '^', '^'),
+    ("dead_tilde", '~', '~'),
+    ("dead_diaeresis", '"', '\u{a8}'),
+    ("dead_cedilla", ',', '\u{b8}'),
+    ("dead_macron", '\u{af}', '\u{af}'),
+    ("dead_breve", '\u{2d8}', '\u{2d8}'),
+    ("dead_abovedot", '\u{2d9}', '\u{2d9}'),
+    ("dead_abovering", '\u{2da}', '\u{2da}'),
+    ("dead_doubleacute", '\u{2dd}', '\u{2dd}'),
+    ("dead_caron", '\u{2c7}', '\u{2c7}'),
+    ("dead_ogonek", '\u{2db}', '\u{2db}'),
+];
+
+/// Keysym names of printable ASCII and Latin-1 characters, other than letters which are named by
+/// themselves
+const KEYSYMS: &[(&str, char)] = &[
+    ("space", ' '),
+    ("exclam", '!'),
+    ("quotedbl", '"'),
+    ("numbersign", '#'),
+    ("dollar", '$'),
+    ("percent", '%'),
+    ("ampersand", '&'),
+    ("apostrophe", '\''),
+    ("parenleft", '('),
+    ("parenright", ')'),
+    ("asterisk", '*'),
+    ("plus", '+'),
+    ("comma", ','),
+    ("minus", '-'),
+    ("period", '.'),
+    ("slash", '/'),
+    ("zero", '0'),
+    ("one", '1'),
+    ("two", '2'),
+    ("three", '3'),
+    ("four", '4'),
+    ("five", '5'),
+    ("six", '6'),
+    ("seven", '7'),
+    ("eight", '8'),
+    ("nine", '9'),
+    ("colon", ':'),
+    ("semicolon", ';'),
+    ("less", '<'),
+    ("equal", '='),
+    ("greater", '>'),
+    ("question", '?'),
+    ("at", '@'),
+    ("bracketleft", '['),
+    ("backslash", '\\'),
+    ("bracketright", ']'),
+    ("asciicircum", '^'),
+    ("underscore", '_'),
+    ("grave", '`'),
+    ("braceleft", '{'),
+    ("bar", '|'),
+    ("braceright", '}'),
+    ("asciitilde", '~'),
+    ("nobreakspace", '\u{a0}'),
+    ("exclamdown", '\u{a1}'),
+    ("cent", '\u{a2}'),
+    ("sterling", '\u{a3}'),
+    ("currency", '\u{a4}'),
+    ("yen", '\u{a5}'),
+    ("brokenbar", '\u{a6}'),
+    ("section", '\u{a7}'),
+    ("diaeresis", '\u{a8}'),
+    ("copyright", '\u{a9}'),
+    ("ordfeminine", '\u{aa}'),
+    ("guillemotleft", '\u{ab}'),
+    ("notsign", '\u{ac}'),
+    ("hyphen", '\u{ad}'),
+    ("registered", '\u{ae}'),
+    ("macron", '\u{af}'),
+    ("degree", '\u{b0}'),
+    ("plusminus", '\u{b1}'),
+    ("twosuperior", '\u{b2}'),
+    ("threesuperior", '\u{b3}'),
+    ("acute", '\u{b4}'),
+    ("mu", '\u{b5}'),
+    ("paragraph", '\u{b6}'),
+    ("periodcentered", '\u{b7}'),
+    ("cedilla", '\u{b8}'),
+    ("onesuperior", '\u{b9}'),
+    ("masculine", '\u{ba}'),
+    ("guillemotright", '\u{bb}'),
+    ("onequarter", '\u{bc}'),
+    ("onehalf", '\u{bd}'),
+    ("threequarters", '\u{be}'),
+    ("questiondown", '\u{bf}'),
+    ("Agrave", '\u{c0}'),
+    ("Aacute", '\u{c1}'),
+    ("Acircumflex", '\u{c2}'),
+    ("Atilde", '\u{c3}'),
+    ("Adiaeresis", '\u{c4}'),
+    ("Aring", '\u{c5}'),
+    ("AE", '\u{c6}'),
+    ("Ccedilla", '\u{c7}'),
+    ("Egrave", '\u{c8}'),
+    ("Eacute", '\u{c9}'),
+    ("Ecircumflex", '\u{ca}'),
+    ("Ediaeresis", '\u{cb}'),
+    ("Igrave", '\u{cc}'),
+    ("Iacute", '\u{cd}'),
+    ("Icircumflex", '\u{ce}'),
+    ("Idiaeresis", '\u{cf}'),
+    ("ETH", '\u{d0}'),
+    ("Ntilde", '\u{d1}'),
+    ("Ograve", '\u{d2}'),
+    ("Oacute", '\u{d3}'),
+    ("Ocircumflex", '\u{d4}'),
+    ("Otilde", '\u{d5}'),
+    ("Odiaeresis", '\u{d6}'),
+    ("multiply", '\u{d7}'),
+    ("Ooblique", '\u{d8}'),
+    ("Ugrave", '\u{d9}'),
+    ("Uacute", '\u{da}'),
+    ("Ucircumflex", '\u{db}'),
+    ("Udiaeresis", '\u{dc}'),
+    ("Yacute", '\u{dd}'),
+    ("THORN", '\u{de}'),
+    ("ssharp", '\u{df}'),
+    ("agrave", '\u{e0}'),
+    ("aacute", '\u{e1}'),
+    ("acircumflex", '\u{e2}'),
+    ("atilde", '\u{e3}'),
+    ("adiaeresis", '\u{e4}'),
+    ("aring", '\u{e5}'),
+    ("ae", '\u{e6}'),
+    ("ccedilla", '\u{e7}'),
+    ("egrave", '\u{e8}'),
+    ("eacute", '\u{e9}'),
+    ("ecircumflex", '\u{ea}'),
+    ("ediaeresis", '\u{eb}'),
+    ("igrave", '\u{ec}'),
+    ("iacute", '\u{ed}'),
+    ("icircumflex", '\u{ee}'),
+    ("idiaeresis", '\u{ef}'),
+    ("eth", '\u{f0}'),
+    ("ntilde", '\u{f1}'),
+    ("ograve", '\u{f2}'),
+    ("oacute", '\u{f3}'),
+    ("ocircumflex", '\u{f4}'),
+    ("otilde", '\u{f5}'),
+    ("odiaeresis", '\u{f6}'),
+    ("division", '\u{f7}'),
+    ("oslash", '\u{f8}'),
+    ("ugrave", '\u{f9}'),
+    ("uacute", '\u{fa}'),
+    ("ucircumflex", '\u{fb}'),
+    ("udiaeresis", '\u{fc}'),
+    ("yacute", '\u{fd}'),
+    ("thorn", '\u{fe}'),
+    ("ydiaeresis", '\u{ff}'),
+];
+
+/// Names that `loadkeys` also accepts for some characters
+const KEYSYM_ALIASES: [(&str, char); 4] = [
+    ("Oslash", '\u{d8}'),
+    ("Eth", '\u{d0}'),
+    ("Thorn", '\u{de}'),
+    ("dead_diaresis", '\u{a8}'),
+];
+
+/// Characters named after the key that is pressed with control to type them
+const CONTROL_KEYSYMS: [(&str, char); 6] = [
+    ("at", '\u{0}'),
+    ("bracketleft", '\u{1b}'),
+    ("backslash", '\u{1c}'),
+    ("bracketright", '\u{1d}'),
+    ("asciicircum", '\u{1e}'),
+    ("underscore", '\u{1f}'),
+];
+
+fn shift_state_from_keymap(keymap: u16) -> ShiftState {
+    MODIFIERS
+        .iter()
+        .filter(|(_, weight, _)| keymap & weight != 0)
+        .fold(ShiftState::empty(), |shift_state, (_, _, modifier)| {
+            shift_state | *modifier
+        })
+}
+
+fn keymap_from_shift_state(shift_state: ShiftState) -> Option<u16> {
+    let mut keymap = 0;
+    let mut remaining = shift_state;
+    for (_, weight, modifier) in &MODIFIERS[1..5] {
+        if remaining.contains(*modifier) {
+            keymap |= weight;
+            remaining.remove(*modifier);
+        }
+    }
+    if remaining.is_empty() {
+        Some(keymap)
+    } else {
+        None
+    }
+}
+
+fn is_letter(c: char) -> bool {
+    c.is_alphabetic() && (c.is_lowercase() || c.is_uppercase())
+}
+
+fn keysym_char(keysym: &str) -> Option<char> {
+    if let Some((_, c)) = KEYSYMS
+        .iter()
+        .chain(KEYSYM_ALIASES.iter())
+        .find(|(name, _)| *name == keysym)
+    {
+        return Some(*c);
+    }
+    if let Some(code) = keysym.strip_prefix(UNICODE_PREFIX) {
+        return u32::from_str_radix(code, 16)
+            .ok()
+            .and_then(core::char::from_u32);
+    }
+    if let Some(code) = keysym.strip_prefix(HEX_PREFIX) {
+        return hex_keysym(code).map(|(c, _)| c);
+    }
+    if let Some(name) = keysym.strip_prefix(CONTROL_PREFIX) {
+        let mut chars = name.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_lowercase() => Some((c as u8 - b'a' + 1) as char),
+            _ => CONTROL_KEYSYMS
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, c)| *c),
+        };
+    }
+
+    let mut chars = keysym.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if is_letter(c) => Some(c),
+        _ => None,
+    }
+}
+
+/// Decode a hexadecimal kernel keysym code into its character, and whether it is a letter
+fn hex_keysym(code: &str) -> Option<(char, bool)> {
+    let mut code = u16::from_str_radix(code, 16).ok()?;
+    if code & KEYSYM_MARK == KEYSYM_MARK {
+        code &= !KEYSYM_MARK;
+    }
+    let c = char::from(code as u8);
+    match code >> 8 {
+        KT_LATIN => Some((c, false)),
+        KT_LETTER => Some((c, true)),
+        _ => None,
+    }
+}
+
+fn keysym_name(c: char) -> String {
+    if is_letter(c) && c.is_ascii() {
+        return String::from(c);
+    }
+    if let Some((name, _)) = KEYSYMS.iter().find(|(_, k)| *k == c) {
+        return String::from(*name);
+    }
+    if ('\u{1}'..='\u{1a}').contains(&c) {
+        return format!("{}{}", CONTROL_PREFIX, (c as u8 - 1 + b'a') as char);
+    }
+    if let Some((name, _)) = CONTROL_KEYSYMS.iter().find(|(_, k)| *k == c) {
+        return format!("{}{}", CONTROL_PREFIX, name);
+    }
+    format!("{}{:04x}", UNICODE_PREFIX, c as u32)
+}
+
+/// Parse a keysym into its output and whether caps lock shifts it
+fn keysym_output(keysym: &str) -> Option<(KeyOutput, bool)> {
+    let (keysym, letter) = match keysym.strip_prefix(LETTER_PREFIX) {
+        Some(keysym) => (keysym, true),
+        None => (keysym, false),
+    };
+
+    if let Some((_, _, accent)) = DEAD_KEYSYMS.iter().find(|(name, _, _)| *name == keysym) {
+        return Some((KeyOutput::Dead(*accent), false));
+    }
+
+    let letter = letter
+        || keysym
+            .strip_prefix(HEX_PREFIX)
+            .and_then(hex_keysym)
+            .is_some_and(|(_, letter)| letter);
+    let c = keysym_char(keysym)?;
+    Some((KeyOutput::Text(String::from(c)), letter || is_letter(c)))
+}
+
+/// Split a line into words, keeping quoted characters and strings together
+fn split_words(line: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if c.is_whitespace() || c == '=' {
+            if let Some(s) = start.take() {
+                words.push(&line[s..index]);
+            }
+            continue;
+        }
+        if start.is_none() {
+            start = Some(index);
+        }
+        if c == '\'' || c == '"' {
+            quote = Some(c);
+        }
+    }
+    if let Some(s) = start {
+        words.push(&line[s..]);
+    }
+    words
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None if COMMENT_CHARS.contains(&c) => return &line[..index],
+            None => {}
+        }
+    }
+    line
+}
+
+/// Parse a character of a `compose` definition
+fn compose_char(word: &str) -> Option<char> {
+    let Some(quoted) = word
+        .strip_prefix('\'')
+        .and_then(|word| word.strip_suffix('\''))
+    else {
+        return keysym_char(word);
+    };
+
+    match quoted.strip_prefix('\\') {
+        Some(escaped) if escaped.len() > 1 => u8::from_str_radix(escaped, 8).ok().map(char::from),
+        Some(escaped) => escaped.chars().next(),
+        None => {
+            let mut chars = quoted.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn quote_compose_char(c: char) -> String {
+    match c {
+        '\'' | '\\' => format!("'\\{}'", c),
+        c if c.is_control() => format!("'\\{:03o}'", c as u32),
+        c => format!("'{}'", c),
+    }
+}
+
+fn parse_keymaps(line: usize, list: &str) -> Result<Vec<u16>, ParseLayoutError> {
+    let mut keymaps = Vec::new();
+    for range in list.split(',') {
+        let parse = |keymap: &str| {
+            keymap
+                .parse::<u16>()
+                .map_err(|_| ParseLayoutError::new(line, format!("invalid keymap `{}`", keymap)))
+        };
+        let (start, end) = match range.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => (parse(range)?, parse(range)?),
+        };
+        keymaps.extend(start..=end);
+    }
+    Ok(keymaps)
+}
+
+fn format_keymaps(keymaps: &[u16]) -> String {
+    let mut ranges: Vec<(u16, u16)> = Vec::new();
+    for keymap in keymaps {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == *keymap => *end = *keymap,
+            _ => ranges.push((*keymap, *keymap)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                format!("{}", start)
+            } else {
+                format!("{}-{}", start, end)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Set the output of a key in a single keymap
+fn set_keysym(layout: &mut Layout, id: KeyMappingId, keymap: u16, keysym: &str) {
+    if let Some((output, _)) = keysym_output(keysym) {
+        layout.set_output(id, shift_state_from_keymap(keymap), output);
+    }
+}
+
+impl Layout {
+    /// Parse a Linux console keymap, as loaded by `loadkeys`
+    ///
+    /// Keycodes without a key mapping are skipped, since keymaps usually define keycodes for keys
+    /// that only exist on some keyboards.
+    pub fn from_kbd_keymap(keymap: &str) -> Result<Layout, ParseLayoutError> {
+        let mut layout = Layout::default();
+        let mut keymaps: Option<Vec<u16>> = None;
+        let mut dead_keys: Vec<DeadKey> = Vec::new();
+        let mut letters: Vec<(KeyMappingId, ShiftState)> = Vec::new();
+
+        let mut lines = keymap.lines().enumerate();
+        while let Some((index, raw_line)) = lines.next() {
+            let line = index + 1;
+            let mut content = String::from(strip_comment(raw_line));
+            while content.trim_end().ends_with(LINE_CONTINUATION) {
+                let trimmed = content.trim_end().len() - 1;
+                content.truncate(trimmed);
+                match lines.next() {
+                    Some((_, next)) => content.push_str(strip_comment(next)),
+                    None => break,
+                }
+            }
+            let words = split_words(&content);
+            let Some(&first) = words.first() else {
+                continue;
+            };
+
+            if first == "keymaps" {
+                let list = words
+                    .get(1)
+                    .ok_or_else(|| ParseLayoutError::new(line, "missing keymaps"))?;
+                keymaps = Some(parse_keymaps(line, list)?);
+                continue;
+            }
+
+            if first == "compose" {
+                let (Some(diacritic), Some(base), Some(&"to"), Some(composed)) = (
+                    words.get(1).and_then(|word| compose_char(word)),
+                    words.get(2).and_then(|word| compose_char(word)),
+                    words.get(3),
+                    words.get(4).and_then(|word| compose_char(word)),
+                ) else {
+                    return Err(ParseLayoutError::new(line, "invalid `compose` definition"));
+                };
+                let Some((_, _, accent)) = DEAD_KEYSYMS
+                    .iter()
+                    .find(|(_, kernel_diacritic, _)| *kernel_diacritic == diacritic)
+                else {
+                    continue;
+                };
+                let dead_key = match dead_keys.iter().position(|d| d.accent == *accent) {
+                    Some(position) => &mut dead_keys[position],
+                    None => {
+                        dead_keys.push(DeadKey::new(*accent));
+                        dead_keys.last_mut().unwrap()
+                    }
+                };
+                dead_key.compositions.push((base, String::from(composed)));
+                continue;
+            }
+
+            let Some(keycode_position) = words.iter().position(|word| *word == "keycode") else {
+                continue;
+            };
+            let keycode = words
+                .get(keycode_position + 1)
+                .and_then(|keycode| keycode.parse::<u16>().ok())
+                .ok_or_else(|| ParseLayoutError::new(line, "invalid keycode"))?;
+            let keysyms = &words[keycode_position + 2..];
+            let Ok(id) = KeyMap::from_key_mapping(KeyMapping::Evdev(keycode)).map(|k| k.id) else {
+                continue;
+            };
+
+            // A single keymap given by its modifiers, like `shift alt keycode 30 = A`
+            if keycode_position > 0 {
+                let mut keymap = 0;
+                for modifier in &words[..keycode_position] {
+                    let (_, weight, _) = MODIFIERS
+                        .iter()
+                        .find(|(name, _, _)| name == modifier)
+                        .ok_or_else(|| {
+                            ParseLayoutError::new(line, format!("unknown modifier `{}`", modifier))
+                        })?;
+                    keymap |= weight;
+                }
+                if let Some(keysym) = keysyms.first() {
+                    set_keysym(&mut layout, id, keymap, keysym);
+                    if let Some((_, true)) = keysym_output(keysym) {
+                        letters.push((id, shift_state_from_keymap(keymap)));
+                    }
+                }
+                continue;
+            }
+
+            // A single letter fills in its shifted and control keymaps, any other single keysym
+            // is used for every keymap
+            if let [keysym] = keysyms {
+                match keysym_output(keysym) {
+                    Some((KeyOutput::Text(text), true)) => {
+                        let c = text.chars().next().unwrap();
+                        let mut upper = c.to_uppercase();
+                        if let (Some(upper), None) = (upper.next(), upper.next()) {
+                            layout.set_output(id, ShiftState::empty(), KeyOutput::Text(text));
+                            layout.set_output(
+                                id,
+                                ShiftState::Shift,
+                                KeyOutput::Text(String::from(upper)),
+                            );
+                            letters.push((id, ShiftState::empty()));
+                            letters.push((id, ShiftState::Shift));
+                            if c.is_ascii_lowercase() {
+                                let control = (c as u8 - b'a' + 1) as char;
+                                for shift_state in
+                                    [ShiftState::Control, ShiftState::Control | ShiftState::Shift]
+                                {
+                                    layout.set_output(
+                                        id,
+                                        shift_state,
+                                        KeyOutput::Text(String::from(control)),
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    _ => {
+                        let columns = keymaps.as_deref().map(|keymaps| keymaps.len()).unwrap_or(1);
+                        for column in 0..columns {
+                            let keymap = keymaps
+                                .as_ref()
+                                .map(|keymaps| keymaps[column])
+                                .unwrap_or(column as u16);
+                            set_keysym(&mut layout, id, keymap, keysym);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            for (column, keysym) in keysyms.iter().enumerate() {
+                if *keysym == NO_SYMBOL {
+                    continue;
+                }
+                let keymap = match &keymaps {
+                    Some(keymaps) => *keymaps
+                        .get(column)
+                        .ok_or_else(|| ParseLayoutError::new(line, "more keysyms than keymaps"))?,
+                    None => column as u16,
+                };
+                set_keysym(&mut layout, id, keymap, keysym);
+                if let Some((_, true)) = keysym_output(keysym) {
+                    letters.push((id, shift_state_from_keymap(keymap)));
+                }
+            }
+        }
+
+        // Caps lock inverts shift for letters
+        for (id, shift_state) in letters {
+            if shift_state.contains(ShiftState::CapsLock) {
+                continue;
+            }
+            if let Some(output) = layout.output(id, shift_state ^ ShiftState::Shift).cloned() {
+                layout.set_output(id, shift_state | ShiftState::CapsLock, output);
+            }
+        }
+
+        for dead_key in dead_keys {
+            layout.add_dead_key(dead_key);
+        }
+
+        Ok(layout)
+    }
+
+    /// Write the layout as a Linux console keymap, which can be loaded by `loadkeys`
+    ///
+    /// Outputs for shift states that the console can't represent, like those using meta, and dead
+    /// keys the console doesn't support are left out.
+    pub fn to_kbd_keymap(&self) -> String {
+        let mut keys: Vec<(u16, &super::LayoutKey)> = self
+            .keys()
+            .map(|key| (KeyMap::from(key.id).evdev, key))
+            .filter(|(keycode, _)| *keycode != 0)
+            .collect();
+        keys.sort_by_key(|(keycode, _)| *keycode);
+
+        let mut keymaps: Vec<u16> = keys
+            .iter()
+            .flat_map(|(_, key)| key.outputs.iter())
+            .filter_map(|(shift_state, _)| keymap_from_shift_state(*shift_state))
+            .collect();
+        keymaps.sort_unstable();
+        keymaps.dedup();
+
+        let mut output = String::new();
+        if !self.name.is_empty() {
+            let _ = writeln!(output, "# {}", self.name);
+        }
+        let _ = writeln!(output, "keymaps {}", format_keymaps(&keymaps));
+
+        for (keycode, key) in &keys {
+            let mut keysyms: Vec<String> = keymaps
+                .iter()
+                .map(|keymap| {
+                    let shift_state = shift_state_from_keymap(*keymap);
+                    let keysym = match self.output(key.id, shift_state) {
+                        Some(KeyOutput::Text(text)) => {
+                            let mut chars = text.chars();
+                            match (chars.next(), chars.next()) {
+                                (Some(c), None) => keysym_name(c),
+                                _ => String::from(NO_SYMBOL),
+                            }
+                        }
+                        Some(KeyOutput::Dead(accent)) => DEAD_KEYSYMS
+                            .iter()
+                            .find(|(_, _, a)| a == accent)
+                            .map(|(name, _, _)| String::from(*name))
+                            .unwrap_or_else(|| String::from(NO_SYMBOL)),
+                        None => String::from(NO_SYMBOL),
+                    };
+
+                    let output = self.output(key.id, shift_state);
+                    let caps_lock_output = self.output(key.id, shift_state | ShiftState::CapsLock);
+                    let shifted_output = self.output(key.id, shift_state ^ ShiftState::Shift);
+                    if output.is_some()
+                        && caps_lock_output != output
+                        && caps_lock_output == shifted_output
+                    {
+                        format!("{}{}", LETTER_PREFIX, keysym)
+                    } else {
+                        keysym
+                    }
+                })
+                .collect();
+            while keysyms.len() > 1 && keysyms.last().map(String::as_str) == Some(NO_SYMBOL) {
+                keysyms.pop();
+            }
+            let _ = writeln!(output, "keycode {} = {}", keycode, keysyms.join(" "));
+        }
+
+        for dead_key in self.dead_keys() {
+            let Some((_, diacritic, _)) = DEAD_KEYSYMS
+                .iter()
+                .find(|(_, _, accent)| *accent == dead_key.accent)
+            else {
+                continue;
+            };
+            for (base, composed) in &dead_key.compositions {
+                let mut chars = composed.chars();
+                let (Some(composed), None) = (chars.next(), chars.next()) else {
+                    continue;
+                };
+                let _ = writeln!(
+                    output,
+                    "compose {} {} to {}",
+                    quote_compose_char(*diacritic),
+                    quote_compose_char(*base),
+                    quote_compose_char(composed)
+                );
+            }
+        }
+
+        output
+    }
+}
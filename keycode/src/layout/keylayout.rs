@@ -0,0 +1,331 @@
+//! Import of macOS `.keylayout` files
+//!
+//! Keys are bound to [`KeyMappingId`](crate::KeyMappingId)s through their virtual key codes, which are matched against
+//! the Mac values of the key mappings. Only the first `<layout>` of a file is used, since it
+//! covers the keyboard types that macOS falls back to.
+
+use super::{single_char, xml, DeadKey, KeyOutput, Layout, ParseLayoutError, ShiftState};
+use crate::{KeyMap, KeyMapping};
+use alloc::{format, string::String, vec::Vec};
+
+const STATE_NONE: &str = "none";
+const OPTIONAL_SUFFIX: char = '?';
+
+// Dead keys without a terminator to take their accent from are identified by private use
+// characters, starting at this code point
+const PRIVATE_USE_START: u32 = 0xe000;
+
+/// Modifiers that can be part of a `.keylayout` shift state
+const MODIFIERS: [ShiftState; 5] = [
+    ShiftState::Shift,
+    ShiftState::CapsLock,
+    ShiftState::Alt,
+    ShiftState::Control,
+    ShiftState::Meta,
+];
+
+fn modifier_from_keylayout(key: &str) -> Option<ShiftState> {
+    match key {
+        "shift" | "rightShift" | "anyShift" => Some(ShiftState::Shift),
+        "caps" => Some(ShiftState::CapsLock),
+        "option" | "rightOption" | "anyOption" => Some(ShiftState::Alt),
+        "control" | "rightControl" | "anyControl" => Some(ShiftState::Control),
+        "command" => Some(ShiftState::Meta),
+        _ => None,
+    }
+}
+
+/// A `<modifier>` element: the modifiers that must be held and the ones that may be held
+struct ModifierMatch {
+    required: ShiftState,
+    optional: ShiftState,
+}
+
+impl ModifierMatch {
+    fn parse(element: &xml::Element) -> Result<ModifierMatch, ParseLayoutError> {
+        let mut modifier_match = ModifierMatch {
+            required: ShiftState::empty(),
+            optional: ShiftState::empty(),
+        };
+        for key in element.required_attribute("keys")?.split_whitespace() {
+            let (key, optional) = match key.strip_suffix(OPTIONAL_SUFFIX) {
+                Some(key) => (key, true),
+                None => (key, false),
+            };
+            let modifier = modifier_from_keylayout(key).ok_or_else(|| {
+                ParseLayoutError::new(element.line, format!("unknown modifier `{}`", key))
+            })?;
+            if optional {
+                modifier_match.optional.insert(modifier);
+            } else {
+                modifier_match.required.insert(modifier);
+            }
+        }
+        Ok(modifier_match)
+    }
+
+    fn matches(&self, shift_state: ShiftState) -> bool {
+        shift_state.contains(self.required) && (self.required | self.optional).contains(shift_state)
+    }
+}
+
+fn parse_index(element: &xml::Element, attribute: &str) -> Result<usize, ParseLayoutError> {
+    let value = element.required_attribute(attribute)?;
+    value.parse::<usize>().map_err(|_| {
+        ParseLayoutError::new(
+            element.line,
+            format!("`{}` is not a number: `{}`", attribute, value),
+        )
+    })
+}
+
+struct KeyLayout<'a> {
+    key_map_sets: Vec<&'a xml::Element>,
+    actions: Vec<&'a xml::Element>,
+    /// Dead key states and the accents identifying them
+    dead_states: Vec<(&'a str, char)>,
+}
+
+impl<'a> KeyLayout<'a> {
+    fn key_map(&self, map_set: &str, index: usize) -> Option<&'a xml::Element> {
+        self.key_map_sets
+            .iter()
+            .find(|set| set.attribute("id") == Some(map_set))?
+            .children("keyMap")
+            .find(|key_map| key_map.attribute("index") == Some(&*format!("{}", index)))
+    }
+
+    /// Find a key in a `<keyMap>`, following `baseMapSet` and `baseIndex` to inherited maps
+    ///
+    /// Maps that inherit from each other in a cycle are followed once.
+    fn key(&self, map_set: &str, index: usize, code: &str) -> Option<&'a xml::Element> {
+        let mut visited: Vec<(&str, usize)> = Vec::new();
+        let (mut map_set, mut index) = (map_set, index);
+        loop {
+            if visited.contains(&(map_set, index)) {
+                return None;
+            }
+            visited.push((map_set, index));
+
+            let key_map = self.key_map(map_set, index)?;
+            if let Some(key) = key_map
+                .children("key")
+                .find(|key| key.attribute("code") == Some(code))
+            {
+                return Some(key);
+            }
+
+            index = key_map.attribute("baseIndex")?.parse::<usize>().ok()?;
+            map_set = key_map.attribute("baseMapSet").unwrap_or(map_set);
+        }
+    }
+
+    fn action(&self, id: &str) -> Option<&'a xml::Element> {
+        self.actions
+            .iter()
+            .find(|action| action.attribute("id") == Some(id))
+            .copied()
+    }
+
+    fn accent(&self, state: &str) -> Option<char> {
+        self.dead_states
+            .iter()
+            .find(|(s, _)| *s == state)
+            .map(|(_, accent)| *accent)
+    }
+
+    fn output(&self, key: &xml::Element) -> Option<KeyOutput> {
+        if let Some(output) = key.attribute("output") {
+            if output.is_empty() {
+                return None;
+            }
+            return Some(KeyOutput::Text(String::from(output)));
+        }
+
+        let when = self
+            .action(key.attribute("action")?)?
+            .children("when")
+            .find(|when| when.attribute("state") == Some(STATE_NONE))?;
+        if let Some(next) = when.attribute("next") {
+            return self.accent(next).map(KeyOutput::Dead);
+        }
+        match when.attribute("output") {
+            Some(output) if !output.is_empty() => Some(KeyOutput::Text(String::from(output))),
+            _ => None,
+        }
+    }
+}
+
+impl Layout {
+    /// Parse a macOS `.keylayout` file
+    ///
+    /// Key codes without a key mapping are skipped, since the layouts that ship with macOS define
+    /// outputs for codes that no keyboard sends.
+    pub fn from_keylayout(keylayout: &str) -> Result<Layout, ParseLayoutError> {
+        let keyboard = xml::parse(keylayout)?;
+        if keyboard.name != "keyboard" {
+            return Err(ParseLayoutError::new(
+                keyboard.line,
+                "root element is not `<keyboard>`",
+            ));
+        }
+
+        let layout_element = keyboard
+            .child("layouts")
+            .and_then(|layouts| layouts.child("layout"))
+            .ok_or_else(|| ParseLayoutError::new(keyboard.line, "missing `<layout>`"))?;
+        let map_set = layout_element.required_attribute("mapSet")?;
+        let modifiers_id = layout_element.required_attribute("modifiers")?;
+
+        let modifier_map = keyboard
+            .children("modifierMap")
+            .find(|modifier_map| modifier_map.attribute("id") == Some(modifiers_id))
+            .ok_or_else(|| {
+                ParseLayoutError::new(
+                    layout_element.line,
+                    format!("missing `<modifierMap>` with id `{}`", modifiers_id),
+                )
+            })?;
+        let default_index = parse_index(modifier_map, "defaultIndex")?;
+        let mut key_map_selects = Vec::new();
+        for key_map_select in modifier_map.children("keyMapSelect") {
+            let map_index = parse_index(key_map_select, "mapIndex")?;
+            for modifier in key_map_select.children("modifier") {
+                key_map_selects.push((ModifierMatch::parse(modifier)?, map_index));
+            }
+        }
+
+        let actions: Vec<&xml::Element> = keyboard
+            .children("actions")
+            .flat_map(|actions| actions.children("action"))
+            .collect();
+        let terminators: Vec<&xml::Element> = keyboard
+            .children("terminators")
+            .flat_map(|terminators| terminators.children("when"))
+            .collect();
+
+        // Every state entered from the `none` state is a dead key
+        let mut dead_states: Vec<(&str, char)> = Vec::new();
+        let mut private_use = PRIVATE_USE_START;
+        for action in &actions {
+            for when in action.children("when") {
+                let (Some(STATE_NONE), Some(next)) =
+                    (when.attribute("state"), when.attribute("next"))
+                else {
+                    continue;
+                };
+                if dead_states.iter().any(|(state, _)| *state == next) {
+                    continue;
+                }
+                let accent = match terminators
+                    .iter()
+                    .find(|terminator| terminator.attribute("state") == Some(next))
+                    .and_then(|terminator| terminator.attribute("output"))
+                    .and_then(|output| output.chars().next())
+                {
+                    Some(accent) => accent,
+                    None => {
+                        let accent = core::char::from_u32(private_use).unwrap();
+                        private_use += 1;
+                        accent
+                    }
+                };
+                dead_states.push((next, accent));
+            }
+        }
+
+        let key_layout = KeyLayout {
+            key_map_sets: keyboard.children("keyMapSet").collect(),
+            actions,
+            dead_states,
+        };
+
+        let mut layout = Layout::new(keyboard.attribute("name").unwrap_or(""));
+
+        // Every combination of the modifiers is resolved to a `<keyMap>` index
+        let mut shift_states = Vec::new();
+        for bits in 0..(1 << MODIFIERS.len()) {
+            let shift_state = MODIFIERS
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| bits & (1 << bit) != 0)
+                .fold(ShiftState::empty(), |shift_state, (_, modifier)| {
+                    shift_state | *modifier
+                });
+            let index = key_map_selects
+                .iter()
+                .find(|(modifier_match, _)| modifier_match.matches(shift_state))
+                .map(|(_, index)| *index)
+                .unwrap_or(default_index);
+            shift_states.push((shift_state, index));
+        }
+
+        let codes: Vec<&str> = key_layout
+            .key_map_sets
+            .iter()
+            .flat_map(|set| set.children("keyMap"))
+            .flat_map(|key_map| key_map.children("key"))
+            .filter_map(|key| key.attribute("code"))
+            .fold(Vec::new(), |mut codes, code| {
+                if !codes.contains(&code) {
+                    codes.push(code);
+                }
+                codes
+            });
+        for code in codes {
+            let Some(id) = code
+                .parse::<u16>()
+                .ok()
+                .and_then(|mac| KeyMap::from_key_mapping(KeyMapping::Mac(mac)).ok())
+                .map(|key_map| key_map.id)
+            else {
+                continue;
+            };
+
+            let outputs: Vec<(ShiftState, KeyOutput)> = shift_states
+                .iter()
+                .filter_map(|(shift_state, index)| {
+                    let key = key_layout.key(map_set, *index, code)?;
+                    Some((*shift_state, key_layout.output(key)?))
+                })
+                .collect();
+            for (shift_state, output) in &outputs {
+                // Only keep caps lock outputs that differ from the output without caps lock
+                if shift_state.contains(ShiftState::CapsLock) {
+                    let without_caps_lock = *shift_state - ShiftState::CapsLock;
+                    if outputs
+                        .iter()
+                        .any(|(s, o)| *s == without_caps_lock && o == output)
+                    {
+                        continue;
+                    }
+                }
+                layout.set_output(id, *shift_state, output.clone());
+            }
+        }
+
+        for (state, accent) in &key_layout.dead_states {
+            let mut dead_key = DeadKey::new(*accent);
+            for action in &key_layout.actions {
+                let Some(base) = action
+                    .children("when")
+                    .find(|when| when.attribute("state") == Some(STATE_NONE))
+                    .and_then(|when| when.attribute("output"))
+                    .and_then(single_char)
+                else {
+                    continue;
+                };
+                if let Some(output) = action
+                    .children("when")
+                    .find(|when| when.attribute("state") == Some(*state))
+                    .and_then(|when| when.attribute("output"))
+                {
+                    dead_key.compositions.push((base, String::from(output)));
+                }
+            }
+            layout.add_dead_key(dead_key);
+        }
+
+        Ok(layout)
+    }
+}
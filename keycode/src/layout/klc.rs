@@ -0,0 +1,299 @@
+//! Import of Microsoft Keyboard Layout Creator `.klc` files
+//!
+//! Keys are bound to [`KeyMappingId`]s through the scan code column, which is matched against the
+//! Windows values of the key mappings.
+
+use super::{DeadKey, KeyOutput, Layout, ParseLayoutError, ShiftState};
+use crate::{KeyMap, KeyMapping, KeyMappingId};
+use alloc::{format, string::String, vec::Vec};
+
+const COMMENT: &str = "//";
+const NO_OUTPUT: &str = "-1";
+const LIGATURE_OUTPUT: &str = "%%";
+const DEAD_KEY_SUFFIX: char = '@';
+const SGCAP: &str = "SGCap";
+
+// Bits of the `SHIFTSTATE` numbers
+const KLC_SHIFT: u8 = 0b001;
+const KLC_CONTROL: u8 = 0b010;
+const KLC_ALT: u8 = 0b100;
+
+// Bits of the `Cap` column
+const CAP_SHIFT: u8 = 0b001;
+const CAP_ALT_GR: u8 = 0b100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Header,
+    ShiftState,
+    Layout,
+    DeadKey(char),
+    Ligature,
+    Ignored,
+}
+
+fn parse_section(keyword: &str) -> Option<Section> {
+    match keyword {
+        "KBD" | "COPYRIGHT" | "COMPANY" | "LOCALENAME" | "LOCALEID" | "VERSION" => {
+            Some(Section::Header)
+        }
+        "SHIFTSTATE" => Some(Section::ShiftState),
+        "LAYOUT" => Some(Section::Layout),
+        "LIGATURE" => Some(Section::Ligature),
+        "ATTRIBUTES" | "KEYNAME" | "KEYNAME_EXT" | "KEYNAME_DEAD" | "DESCRIPTIONS"
+        | "LANGUAGENAMES" | "ENDKBD" => Some(Section::Ignored),
+        _ => None,
+    }
+}
+
+fn shift_state_from_klc(klc_shift_state: u8) -> ShiftState {
+    let mut shift_state = ShiftState::empty();
+    if klc_shift_state & KLC_SHIFT != 0 {
+        shift_state.insert(ShiftState::Shift);
+    }
+    match (
+        klc_shift_state & KLC_CONTROL != 0,
+        klc_shift_state & KLC_ALT != 0,
+    ) {
+        (true, true) => shift_state.insert(ShiftState::AltGr),
+        (true, false) => shift_state.insert(ShiftState::Control),
+        (false, true) => shift_state.insert(ShiftState::Alt),
+        (false, false) => {}
+    }
+    shift_state
+}
+
+fn parse_char(value: &str) -> Option<char> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => u32::from_str_radix(value, 16)
+            .ok()
+            .and_then(core::char::from_u32),
+    }
+}
+
+fn parse_output(line: usize, value: &str) -> Result<Option<KeyOutput>, ParseLayoutError> {
+    if value == NO_OUTPUT || value == LIGATURE_OUTPUT {
+        return Ok(None);
+    }
+
+    let (value, dead) = match value.strip_suffix(DEAD_KEY_SUFFIX) {
+        Some(value) if !value.is_empty() => (value, true),
+        _ => (value, false),
+    };
+    let c = parse_char(value)
+        .ok_or_else(|| ParseLayoutError::new(line, format!("invalid character `{}`", value)))?;
+
+    Ok(Some(if dead {
+        KeyOutput::Dead(c)
+    } else {
+        KeyOutput::Text(String::from(c))
+    }))
+}
+
+fn parse_hex(line: usize, value: &str, what: &str) -> Result<u16, ParseLayoutError> {
+    u16::from_str_radix(value, 16)
+        .map_err(|_| ParseLayoutError::new(line, format!("invalid {} `{}`", what, value)))
+}
+
+impl Layout {
+    /// Parse a Microsoft Keyboard Layout Creator `.klc` file
+    ///
+    /// `.klc` files are usually saved as UTF-16, use [`Layout::from_klc_bytes`] to parse them
+    /// without decoding them first.
+    pub fn from_klc(klc: &str) -> Result<Layout, ParseLayoutError> {
+        let mut layout = Layout::default();
+        let mut section = Section::Header;
+        let mut shift_states: Vec<ShiftState> = Vec::new();
+        let mut virtual_keys: Vec<(String, KeyMappingId)> = Vec::new();
+        let mut previous_key: Option<KeyMappingId> = None;
+        let mut dead_key: Option<DeadKey> = None;
+
+        for (index, raw_line) in klc.lines().enumerate() {
+            let line = index + 1;
+            let content = match raw_line.find(COMMENT) {
+                Some(comment) => &raw_line[..comment],
+                None => raw_line,
+            };
+            let columns: Vec<&str> = content
+                .trim_start_matches('\u{feff}')
+                .split_whitespace()
+                .collect();
+            let Some(&keyword) = columns.first() else {
+                continue;
+            };
+
+            if let Some(next_section) = parse_section(keyword) {
+                if keyword == "KBD" {
+                    // The name is quoted, and can be followed by whitespace and a comment
+                    layout.name = match raw_line.find('"') {
+                        Some(start) => {
+                            let quoted = &raw_line[start + 1..];
+                            String::from(quoted.find('"').map_or(quoted, |end| &quoted[..end]))
+                        }
+                        None => String::from(*columns.get(1).unwrap_or(&"")),
+                    };
+                }
+                section = next_section;
+                continue;
+            }
+            if keyword == "DEADKEY" {
+                if let Some(dead_key) = dead_key.take() {
+                    layout.add_dead_key(dead_key);
+                }
+                let accent = columns
+                    .get(1)
+                    .and_then(|accent| parse_char(accent))
+                    .ok_or_else(|| ParseLayoutError::new(line, "invalid `DEADKEY` accent"))?;
+                dead_key = Some(DeadKey::new(accent));
+                section = Section::DeadKey(accent);
+                continue;
+            }
+
+            match section {
+                Section::Header | Section::Ignored => {}
+                Section::ShiftState => {
+                    let klc_shift_state = keyword.parse::<u8>().map_err(|_| {
+                        ParseLayoutError::new(line, format!("invalid shift state `{}`", keyword))
+                    })?;
+                    shift_states.push(shift_state_from_klc(klc_shift_state));
+                }
+                Section::Layout => {
+                    if columns.len() < 3 {
+                        return Err(ParseLayoutError::new(line, "incomplete `LAYOUT` row"));
+                    }
+
+                    // The second line of an `SGCap` key holds its caps lock outputs
+                    if keyword == NO_OUTPUT {
+                        let id = previous_key.ok_or_else(|| {
+                            ParseLayoutError::new(line, "`SGCap` outputs without a key")
+                        })?;
+                        for (shift_state, value) in shift_states.iter().zip(&columns[3..]) {
+                            if let Some(output) = parse_output(line, value)? {
+                                layout.set_output(id, *shift_state | ShiftState::CapsLock, output);
+                            }
+                        }
+                        continue;
+                    }
+
+                    let scan_code = parse_hex(line, keyword, "scan code")?;
+                    let id = KeyMap::from_key_mapping(KeyMapping::Win(scan_code))
+                        .map_err(|_| {
+                            ParseLayoutError::new(line, format!("unknown scan code `{}`", keyword))
+                        })?
+                        .id;
+                    virtual_keys.push((String::from(columns[1]), id));
+                    previous_key = Some(id);
+
+                    let mut outputs = Vec::new();
+                    for (shift_state, value) in shift_states.iter().zip(&columns[3..]) {
+                        if let Some(output) = parse_output(line, value)? {
+                            outputs.push((*shift_state, output));
+                        }
+                    }
+
+                    let cap = match columns[2] {
+                        SGCAP => 0,
+                        cap => cap.parse::<u8>().map_err(|_| {
+                            ParseLayoutError::new(line, format!("invalid `Cap` value `{}`", cap))
+                        })?,
+                    };
+                    for (pressed, toggled, mask) in [
+                        (ShiftState::empty(), ShiftState::Shift, CAP_SHIFT),
+                        (
+                            ShiftState::AltGr,
+                            ShiftState::Shift | ShiftState::AltGr,
+                            CAP_ALT_GR,
+                        ),
+                    ] {
+                        if cap & mask == 0 {
+                            continue;
+                        }
+                        for (from, to) in [(pressed, toggled), (toggled, pressed)] {
+                            if let Some((_, output)) = outputs.iter().find(|(s, _)| *s == to) {
+                                let output = output.clone();
+                                layout.set_output(id, from | ShiftState::CapsLock, output);
+                            }
+                        }
+                    }
+
+                    for (shift_state, output) in outputs {
+                        layout.set_output(id, shift_state, output);
+                    }
+                }
+                Section::DeadKey(_) => {
+                    let (Some(base), Some(composed)) = (
+                        parse_char(keyword),
+                        columns.get(1).and_then(|c| parse_char(c)),
+                    ) else {
+                        return Err(ParseLayoutError::new(line, "invalid `DEADKEY` row"));
+                    };
+                    if let Some(dead_key) = dead_key.as_mut() {
+                        dead_key.compositions.push((base, String::from(composed)));
+                    }
+                }
+                Section::Ligature => {
+                    let id = virtual_keys
+                        .iter()
+                        .find(|(virtual_key, _)| virtual_key == keyword)
+                        .map(|(_, id)| *id)
+                        .ok_or_else(|| {
+                            ParseLayoutError::new(
+                                line,
+                                format!("unknown virtual key `{}`", keyword),
+                            )
+                        })?;
+                    let shift_state = columns
+                        .get(1)
+                        .and_then(|column| column.parse::<usize>().ok())
+                        .and_then(|column| shift_states.get(column))
+                        .ok_or_else(|| ParseLayoutError::new(line, "invalid ligature column"))?;
+                    let text = columns[2..]
+                        .iter()
+                        .map(|value| {
+                            parse_char(value).ok_or_else(|| {
+                                ParseLayoutError::new(
+                                    line,
+                                    format!("invalid character `{}`", value),
+                                )
+                            })
+                        })
+                        .collect::<Result<String, _>>()?;
+                    layout.set_output(id, *shift_state, KeyOutput::Text(text));
+                }
+            }
+        }
+
+        if let Some(dead_key) = dead_key.take() {
+            layout.add_dead_key(dead_key);
+        }
+
+        Ok(layout)
+    }
+
+    /// Parse a Microsoft Keyboard Layout Creator `.klc` file from its raw bytes
+    ///
+    /// UTF-16 files (with a byte order mark) and UTF-8 files are supported.
+    pub fn from_klc_bytes(klc: &[u8]) -> Result<Layout, ParseLayoutError> {
+        let decoded: String = match klc {
+            [0xff, 0xfe, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+            [0xfe, 0xff, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+            _ => String::from(
+                core::str::from_utf8(klc)
+                    .map_err(|_| ParseLayoutError::new(1, "file is not UTF-8 or UTF-16"))?,
+            ),
+        };
+        Layout::from_klc(&decoded)
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    core::char::decode_utf16(
+        bytes
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]])),
+    )
+    .map(|c| c.unwrap_or(core::char::REPLACEMENT_CHARACTER))
+    .collect()
+}
@@ -0,0 +1,314 @@
+//! Keyboard layouts: the characters each key produces under each shift state.
+//!
+//! A [`Layout`] binds [`KeyMappingId`]s to text, so that characters can be turned into the
+//! keystrokes needed to type them (and the other way around). Layouts are usually imported from a
+//! platform's own layout file format:
+//!
+//! *   Windows `.klc` files, see [`Layout::from_klc`]
+//! *   macOS `.keylayout` files, see [`Layout::from_keylayout`]
+//! *   Linux console keymaps, see [`Layout::from_kbd_keymap`] and [`Layout::to_kbd_keymap`]
+
+mod kbd;
+mod keylayout;
+mod klc;
+mod xml;
+
+use crate::{KeyMap, KeyMappingId, KeyModifiers};
+use alloc::{string::String, vec::Vec};
+use bitflags::bitflags;
+use core::fmt;
+
+bitflags! {
+    /// Modifiers that select which character a key produces in a [`Layout`]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct ShiftState: u8 {
+        /// Either shift key
+        const Shift    = 0b0000_0001;
+        /// Either control key
+        const Control  = 0b0000_0010;
+        /// Either alt (option) key
+        const Alt      = 0b0000_0100;
+        /// AltGr, which Windows also reports as control + alt
+        const AltGr    = 0b0000_1000;
+        /// Caps lock is toggled on
+        const CapsLock = 0b0001_0000;
+        /// Either meta (command) key
+        const Meta     = 0b0010_0000;
+    }
+}
+
+impl ShiftState {
+    /// Get the modifier keys that have to be held down to reach this shift state
+    ///
+    /// Caps lock is a toggle rather than a held modifier, so it is not included.
+    pub fn key_modifiers(self) -> KeyModifiers {
+        let mut key_modifiers = KeyModifiers::empty();
+        if self.contains(ShiftState::Shift) {
+            key_modifiers.insert(KeyModifiers::ShiftLeft);
+        }
+        if self.contains(ShiftState::Control) {
+            key_modifiers.insert(KeyModifiers::ControlLeft);
+        }
+        if self.contains(ShiftState::Alt) {
+            key_modifiers.insert(KeyModifiers::AltLeft);
+        }
+        if self.contains(ShiftState::AltGr) {
+            key_modifiers.insert(KeyModifiers::AltRight);
+        }
+        if self.contains(ShiftState::Meta) {
+            key_modifiers.insert(KeyModifiers::MetaLeft);
+        }
+        key_modifiers
+    }
+}
+
+/// What a key produces when it is pressed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyOutput {
+    /// Text, usually a single character but sometimes a ligature
+    Text(String),
+    /// A dead key, identified by its spacing accent, which combines with the next key
+    Dead(char),
+}
+
+/// A dead key and the characters it composes with
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeadKey {
+    /// Spacing accent that identifies this dead key
+    pub accent: char,
+    /// Pairs of the following character and the text it composes into
+    pub compositions: Vec<(char, String)>,
+}
+
+impl DeadKey {
+    /// Create a dead key without any compositions
+    pub fn new(accent: char) -> DeadKey {
+        DeadKey {
+            accent,
+            compositions: Vec::new(),
+        }
+    }
+
+    /// Get the text produced when this dead key is followed by `base`
+    pub fn compose(&self, base: char) -> Option<&str> {
+        self.compositions
+            .iter()
+            .find(|(c, _)| *c == base)
+            .map(|(_, text)| text.as_str())
+    }
+}
+
+/// Every output of a single key in a [`Layout`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutKey {
+    /// Id of the key
+    pub id: KeyMappingId,
+    /// Output of the key for each shift state that produces something
+    pub outputs: Vec<(ShiftState, KeyOutput)>,
+}
+
+/// A key press, with the shift state it needs, used to type a character
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keystroke {
+    /// Id of the key to press
+    pub id: KeyMappingId,
+    /// Shift state to hold while pressing the key
+    pub shift_state: ShiftState,
+}
+
+impl Keystroke {
+    /// Get the key mapping of the key to press
+    pub fn key_map(&self) -> KeyMap {
+        KeyMap::from(self.id)
+    }
+
+    /// Get the modifier keys to hold while pressing the key
+    pub fn key_modifiers(&self) -> KeyModifiers {
+        self.shift_state.key_modifiers()
+    }
+}
+
+/// A keyboard layout, mapping keys and shift states to text and dead keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layout {
+    /// Name of the layout
+    pub name: String,
+    keys: Vec<LayoutKey>,
+    dead_keys: Vec<DeadKey>,
+}
+
+impl Layout {
+    /// Create an empty layout
+    pub fn new(name: &str) -> Layout {
+        Layout {
+            name: String::from(name),
+            keys: Vec::new(),
+            dead_keys: Vec::new(),
+        }
+    }
+
+    /// Set the output of a key for a shift state, replacing any previous output
+    pub fn set_output(&mut self, id: KeyMappingId, shift_state: ShiftState, output: KeyOutput) {
+        let key = match self.keys.iter().position(|key| key.id == id) {
+            Some(index) => &mut self.keys[index],
+            None => {
+                self.keys.push(LayoutKey {
+                    id,
+                    outputs: Vec::new(),
+                });
+                self.keys.last_mut().unwrap()
+            }
+        };
+
+        match key.outputs.iter_mut().find(|(s, _)| *s == shift_state) {
+            Some((_, existing)) => *existing = output,
+            None => key.outputs.push((shift_state, output)),
+        }
+    }
+
+    /// Get the output of a key for a shift state
+    ///
+    /// Keys that caps lock doesn't affect fall back to their output without caps lock.
+    pub fn output(&self, id: KeyMappingId, shift_state: ShiftState) -> Option<&KeyOutput> {
+        let key = self.key(id)?;
+        let find = |shift_state: ShiftState| {
+            key.outputs
+                .iter()
+                .find(|(s, _)| *s == shift_state)
+                .map(|(_, output)| output)
+        };
+
+        find(shift_state).or_else(|| {
+            if shift_state.contains(ShiftState::CapsLock) {
+                find(shift_state - ShiftState::CapsLock)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Get every output of a key
+    pub fn key(&self, id: KeyMappingId) -> Option<&LayoutKey> {
+        self.keys.iter().find(|key| key.id == id)
+    }
+
+    /// Iterate over the keys of the layout
+    pub fn keys(&self) -> impl Iterator<Item = &LayoutKey> {
+        self.keys.iter()
+    }
+
+    /// Add a dead key, replacing any dead key with the same accent
+    pub fn add_dead_key(&mut self, dead_key: DeadKey) {
+        match self
+            .dead_keys
+            .iter_mut()
+            .find(|d| d.accent == dead_key.accent)
+        {
+            Some(existing) => *existing = dead_key,
+            None => self.dead_keys.push(dead_key),
+        }
+    }
+
+    /// Get a dead key by its accent
+    pub fn dead_key(&self, accent: char) -> Option<&DeadKey> {
+        self.dead_keys.iter().find(|d| d.accent == accent)
+    }
+
+    /// Iterate over the dead keys of the layout
+    pub fn dead_keys(&self) -> impl Iterator<Item = &DeadKey> {
+        self.dead_keys.iter()
+    }
+
+    /// Find the keystrokes that type a character
+    ///
+    /// Characters produced directly by a key are preferred over dead key compositions, and
+    /// shift states with fewer modifiers are preferred over ones with more. Caps lock is assumed
+    /// to be off.
+    pub fn keystrokes(&self, c: char) -> Option<Vec<Keystroke>> {
+        if let Some(keystroke) = self.find_keystroke(|output| match output {
+            KeyOutput::Text(text) => single_char(text) == Some(c),
+            KeyOutput::Dead(_) => false,
+        }) {
+            return Some(alloc::vec![keystroke]);
+        }
+
+        self.dead_keys.iter().find_map(|dead_key| {
+            let (base, _) = dead_key
+                .compositions
+                .iter()
+                .find(|(_, text)| single_char(text) == Some(c))?;
+            let dead = self.find_keystroke(|output| *output == KeyOutput::Dead(dead_key.accent))?;
+            let base = self.find_keystroke(|output| match output {
+                KeyOutput::Text(text) => single_char(text) == Some(*base),
+                KeyOutput::Dead(_) => false,
+            })?;
+            Some(alloc::vec![dead, base])
+        })
+    }
+
+    /// Find the keystrokes that type a string, or `None` if any character can't be typed
+    pub fn keystrokes_for_str(&self, text: &str) -> Option<Vec<Keystroke>> {
+        let mut keystrokes = Vec::new();
+        for c in text.chars() {
+            keystrokes.extend(self.keystrokes(c)?);
+        }
+        Some(keystrokes)
+    }
+
+    fn find_keystroke(&self, matches: impl Fn(&KeyOutput) -> bool) -> Option<Keystroke> {
+        self.keys
+            .iter()
+            .flat_map(|key| {
+                key.outputs
+                    .iter()
+                    .filter(|(shift_state, output)| {
+                        !shift_state.contains(ShiftState::CapsLock) && matches(output)
+                    })
+                    .map(move |(shift_state, _)| Keystroke {
+                        id: key.id,
+                        shift_state: *shift_state,
+                    })
+            })
+            .min_by_key(|keystroke| keystroke.shift_state.bits().count_ones())
+    }
+}
+
+pub(super) fn single_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+/// Error returned when a layout file can't be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseLayoutError {
+    /// Line of the file that could not be parsed, starting at 1
+    pub line: usize,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl ParseLayoutError {
+    pub(crate) fn new(line: usize, message: impl Into<String>) -> ParseLayoutError {
+        ParseLayoutError {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl core::error::Error for ParseLayoutError {}
@@ -0,0 +1,214 @@
+//! Minimal XML reader for layout files
+//!
+//! Only supports what layout files use: elements, attributes, character and entity references,
+//! comments, processing instructions and a `DOCTYPE`. Text content is skipped.
+
+use super::ParseLayoutError;
+use crate::cursor::Cursor;
+use alloc::{format, string::String, vec::Vec};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub line: usize,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn required_attribute(&self, name: &str) -> Result<&str, ParseLayoutError> {
+        self.attribute(name).ok_or_else(|| {
+            ParseLayoutError::new(
+                self.line,
+                format!("`<{}>` is missing the `{}` attribute", self.name, name),
+            )
+        })
+    }
+
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+}
+
+/// Deepest nesting of elements that is read
+const MAX_DEPTH: usize = 64;
+
+struct Reader<'a> {
+    cursor: Cursor<'a>,
+}
+
+impl<'a> Reader<'a> {
+    fn error(&self, message: impl Into<String>) -> ParseLayoutError {
+        ParseLayoutError::new(self.cursor.line(), message)
+    }
+
+    fn skip_past(&mut self, end: &str) -> Result<(), ParseLayoutError> {
+        match self.cursor.rest().find(end) {
+            Some(index) => {
+                self.cursor.advance(index + end.len());
+                Ok(())
+            }
+            None => Err(self.error(format!("missing `{}`", end))),
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, ParseLayoutError> {
+        let rest = self.cursor.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.cursor.advance(len);
+        Ok(&rest[..len])
+    }
+
+    /// Skip markup that isn't an element, returning whether anything was skipped
+    fn skip_misc(&mut self) -> Result<bool, ParseLayoutError> {
+        let rest = self.cursor.rest();
+        if rest.starts_with("<?") {
+            self.skip_past("?>")?;
+        } else if rest.starts_with("<!--") {
+            self.skip_past("-->")?;
+        } else if rest.starts_with("<!") {
+            self.skip_past(">")?;
+        } else if !rest.is_empty() && !rest.starts_with('<') {
+            // Text content
+            self.cursor.advance(rest.find('<').unwrap_or(rest.len()));
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn element(&mut self, depth: usize) -> Result<Element, ParseLayoutError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error(format!("elements are nested more than {} deep", MAX_DEPTH)));
+        }
+        let line = self.cursor.line();
+        self.cursor.advance(1);
+        let name = self.name()?;
+        let mut element = Element {
+            name: String::from(name),
+            attributes: Vec::new(),
+            children: Vec::new(),
+            line,
+        };
+
+        loop {
+            self.cursor.skip_whitespace();
+            let rest = self.cursor.rest();
+            if rest.starts_with("/>") {
+                self.cursor.advance(2);
+                return Ok(element);
+            }
+            if rest.starts_with('>') {
+                self.cursor.advance(1);
+                break;
+            }
+
+            let attribute = self.name()?;
+            self.cursor.skip_whitespace();
+            if !self.cursor.rest().starts_with('=') {
+                return Err(self.error(format!("attribute `{}` has no value", attribute)));
+            }
+            self.cursor.advance(1);
+            self.cursor.skip_whitespace();
+            let quote = match self.cursor.rest().chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err(self.error(format!("attribute `{}` is not quoted", attribute))),
+            };
+            self.cursor.advance(1);
+            let rest = self.cursor.rest();
+            let len = rest
+                .find(quote)
+                .ok_or_else(|| self.error(format!("attribute `{}` is not closed", attribute)))?;
+            let value = unescape(&rest[..len]).ok_or_else(|| {
+                self.error(format!(
+                    "attribute `{}` has an invalid reference",
+                    attribute
+                ))
+            })?;
+            self.cursor.advance(len + 1);
+            element.attributes.push((String::from(attribute), value));
+        }
+
+        loop {
+            if self.skip_misc()? {
+                continue;
+            }
+            let rest = self.cursor.rest();
+            if rest.is_empty() {
+                return Err(self.error(format!("`<{}>` is not closed", element.name)));
+            }
+            if rest.starts_with("</") {
+                self.cursor.advance(2);
+                let end = self.name()?;
+                if end != element.name {
+                    return Err(
+                        self.error(format!("`<{}>` is closed by `</{}>`", element.name, end))
+                    );
+                }
+                self.skip_past(">")?;
+                return Ok(element);
+            }
+            element.children.push(self.element(depth + 1)?);
+        }
+    }
+}
+
+/// Parse an XML document into its root element
+pub(crate) fn parse(xml: &str) -> Result<Element, ParseLayoutError> {
+    let mut reader = Reader {
+        cursor: Cursor::new(xml.trim_start_matches('\u{feff}')),
+    };
+    while reader.skip_misc()? {}
+    if reader.cursor.rest().is_empty() {
+        return Err(reader.error("missing root element"));
+    }
+    reader.element(0)
+}
+
+fn unescape(value: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        let end = rest[start..].find(';')? + start;
+        let reference = &rest[start + 1..end];
+        let c = match reference {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = if let Some(hex) = reference
+                    .strip_prefix("#x")
+                    .or_else(|| reference.strip_prefix("#X"))
+                {
+                    u32::from_str_radix(hex, 16).ok()?
+                } else {
+                    reference.strip_prefix('#')?.parse::<u32>().ok()?
+                };
+                core::char::from_u32(code)?
+            }
+        };
+        unescaped.push(c);
+        rest = &rest[end + 1..];
+    }
+    unescaped.push_str(rest);
+    Some(unescaped)
+}
@@ -56,14 +56,34 @@
 //! // USB HID report for "no keys pressed"
 //! assert_eq!(keyboard_state.usb_input_report(), &[0; 8]);
 //! ```
+//!
+//! # Features
+//!
+//! *   `alloc`: keyboard layouts, see [`layout`], and importing physical layouts from
+//!     keyboard-layout-editor, see [`geometry`]
+//! *   `serde`: `Serialize` and `Deserialize` implementations
 
 #![no_std]
 #![deny(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+mod cursor;
+
+pub mod geometry;
+pub mod label;
+#[cfg(feature = "alloc")]
+pub mod layout;
+pub mod modifiers;
+pub mod shortcut;
+pub mod terminal;
+
 use arraydeque::ArrayDeque;
 use arrayvec::ArrayVec;
+use modifiers::Modifiers;
 
-use core::str::FromStr;
 use keycode_macro::parse_keycode_converter_data;
 
 parse_keycode_converter_data!();
@@ -90,7 +110,7 @@ pub struct KeyboardState {
     input_report: ArrayVec<u8, NUM_KEYS>,
 }
 
-impl<'a> KeyboardState {
+impl KeyboardState {
     /// Create a new keyboard state
     pub fn new(key_rollover: Option<usize>) -> KeyboardState {
         KeyboardState {
@@ -102,7 +122,7 @@ impl<'a> KeyboardState {
     }
 
     /// Update the keyboard state with a key's new state
-    pub fn update_key(self: &mut Self, key: KeyMap, state: KeyState) {
+    pub fn update_key(&mut self, key: KeyMap, state: KeyState) {
         match state {
             KeyState::Pressed => {
                 if let Some(key_modifier) = key.modifier {
@@ -145,8 +165,31 @@ impl<'a> KeyboardState {
         }
     }
 
+    /// Check whether a key is held
+    pub fn is_pressed(&self, id: KeyMappingId) -> bool {
+        match KeyMap::from(id).modifier {
+            Some(key_modifier) => self.modifier_state.contains(key_modifier),
+            None => self.key_state.iter().flatten().any(|key| key.id == id),
+        }
+    }
+
+    /// Get the held modifier keys
+    pub fn modifier_keys(&self) -> KeyModifiers {
+        self.modifier_state
+    }
+
+    /// Get the held modifiers, including hyper, super and function, with the right alt key as alt
+    pub fn modifiers(&self) -> Modifiers {
+        self.key_state
+            .iter()
+            .flatten()
+            .fold(Modifiers::from(self.modifier_state), |modifiers, key| {
+                modifiers | Modifiers::from_key(key.id)
+            })
+    }
+
     /// Generate a USB HID report
-    pub fn usb_input_report(self: &mut Self) -> &[u8] {
+    pub fn usb_input_report(&mut self) -> &[u8] {
         let mut input_report: ArrayVec<u8, NUM_KEYS> = ArrayVec::new();
 
         // Key modifiers
@@ -154,10 +197,8 @@ impl<'a> KeyboardState {
         input_report.push(0);
 
         // Normal keys
-        for possible_key in self.key_state.iter() {
-            if let Some(key) = possible_key {
-                input_report.push(key.usb as u8);
-            }
+        for key in self.key_state.iter().flatten() {
+            input_report.push(key.usb as u8);
         }
 
         // Default (not pressed)
@@ -0,0 +1,232 @@
+//! Modifiers regardless of the side of their key, and matching held modifier keys against them
+//!
+//! [`KeyModifiers`] tells the left and right modifier keys apart, like a USB HID report does.
+//! [`Modifiers`] only names the modifier, so that `Ctrl+C` can be written once for both control
+//! keys, and adds the modifiers that have no [`KeyModifiers`] bit: hyper, super, function and
+//! AltGr.
+//!
+//! ```
+//! use keycode::{modifiers::{ModifierPattern, Modifiers}, KeyModifiers};
+//!
+//! let held = KeyModifiers::ControlRight | KeyModifiers::ShiftLeft;
+//! assert!(Modifiers::from(held).contains(Modifiers::Shift));
+//!
+//! let pattern = ModifierPattern::either(Modifiers::Control | Modifiers::Shift);
+//! assert!(pattern.matches(held, Modifiers::empty()));
+//! assert!(!ModifierPattern::sided(KeyModifiers::ControlLeft).matches(held, Modifiers::empty()));
+//! ```
+
+use crate::{KeyMap, KeyMappingId, KeyModifiers};
+use bitflags::bitflags;
+
+bitflags! {
+    /// Modifiers, without telling the left and right keys apart
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Modifiers: u8 {
+        /// Either control key
+        const Control = 0b0000_0001;
+        /// Either shift key
+        const Shift   = 0b0000_0010;
+        /// Either alt (option) key
+        const Alt     = 0b0000_0100;
+        /// Either meta (command, windows) key
+        const Meta    = 0b0000_1000;
+        /// The hyper key
+        const Hyper   = 0b0001_0000;
+        /// The super key, which some systems have besides meta
+        const Super   = 0b0010_0000;
+        /// The function key
+        const Fn      = 0b0100_0000;
+        /// AltGr, the right alt key on layouts that use it to type more characters
+        const AltGr   = 0b1000_0000;
+    }
+}
+
+/// Modifiers that have a left and a right key, with their keys
+const SIDED: [(Modifiers, KeyModifiers, KeyModifiers); 4] = [
+    (
+        Modifiers::Control,
+        KeyModifiers::ControlLeft,
+        KeyModifiers::ControlRight,
+    ),
+    (
+        Modifiers::Shift,
+        KeyModifiers::ShiftLeft,
+        KeyModifiers::ShiftRight,
+    ),
+    (
+        Modifiers::Alt,
+        KeyModifiers::AltLeft,
+        KeyModifiers::AltRight,
+    ),
+    (
+        Modifiers::Meta,
+        KeyModifiers::MetaLeft,
+        KeyModifiers::MetaRight,
+    ),
+];
+
+/// Modifiers that only have a key in the source table, without a [`KeyModifiers`] bit
+const UNSIDED: [(Modifiers, KeyMappingId); 3] = [
+    (Modifiers::Hyper, KeyMappingId::Hyper),
+    (Modifiers::Super, KeyMappingId::Super),
+    (Modifiers::Fn, KeyMappingId::Fn),
+];
+
+impl Modifiers {
+    /// Get the modifiers of held modifier keys
+    ///
+    /// The right alt key is AltGr if `alt_gr` is set, and alt otherwise.
+    pub fn from_key_modifiers(key_modifiers: KeyModifiers, alt_gr: bool) -> Modifiers {
+        let mut modifiers = Modifiers::empty();
+        for (modifier, left, right) in SIDED {
+            if key_modifiers.intersects(left | right) {
+                modifiers.insert(modifier);
+            }
+        }
+        if alt_gr && key_modifiers.contains(KeyModifiers::AltRight) {
+            modifiers.insert(Modifiers::AltGr);
+            if !key_modifiers.contains(KeyModifiers::AltLeft) {
+                modifiers.remove(Modifiers::Alt);
+            }
+        }
+        modifiers
+    }
+
+    /// Get the modifier of a key, which is empty for keys that aren't modifiers
+    ///
+    /// The right alt key is alt, see [`Modifiers::from_key_modifiers`] for AltGr.
+    pub fn from_key(id: KeyMappingId) -> Modifiers {
+        if let Some(key_modifiers) = KeyMap::from(id).modifier {
+            return Modifiers::from(key_modifiers);
+        }
+        UNSIDED
+            .iter()
+            .find(|(_, key)| *key == id)
+            .map(|(modifier, _)| *modifier)
+            .unwrap_or_else(Modifiers::empty)
+    }
+
+    /// Get the modifier keys that hold these modifiers, using the left keys
+    ///
+    /// AltGr is the right alt key. Hyper, super and function have no [`KeyModifiers`] bit, so
+    /// they are not included.
+    pub fn key_modifiers(self) -> KeyModifiers {
+        let mut key_modifiers = KeyModifiers::empty();
+        for (modifier, left, _) in SIDED {
+            if self.contains(modifier) {
+                key_modifiers.insert(left);
+            }
+        }
+        if self.contains(Modifiers::AltGr) {
+            key_modifiers.insert(KeyModifiers::AltRight);
+        }
+        key_modifiers
+    }
+
+    /// Get the keys of these modifiers, on both sides
+    ///
+    /// AltGr is the right alt key. Hyper, super and function have no [`KeyModifiers`] bit, so
+    /// they are not included.
+    pub fn sides(self) -> KeyModifiers {
+        let mut key_modifiers = KeyModifiers::empty();
+        for (modifier, left, right) in SIDED {
+            if self.contains(modifier) {
+                key_modifiers.insert(left | right);
+            }
+        }
+        if self.contains(Modifiers::AltGr) {
+            key_modifiers.insert(KeyModifiers::AltRight);
+        }
+        key_modifiers
+    }
+}
+
+impl From<KeyModifiers> for Modifiers {
+    /// Get the modifiers of held modifier keys, with the right alt key as alt
+    fn from(key_modifiers: KeyModifiers) -> Modifiers {
+        Modifiers::from_key_modifiers(key_modifiers, false)
+    }
+}
+
+impl From<Modifiers> for KeyModifiers {
+    /// Get the modifier keys that hold the modifiers, see [`Modifiers::key_modifiers`]
+    fn from(modifiers: Modifiers) -> KeyModifiers {
+        modifiers.key_modifiers()
+    }
+}
+
+/// Modifiers to match held modifiers against, each on either side or on a specific side
+///
+/// Matching is exact: modifiers that are held but not in the pattern fail to match, so that
+/// `Ctrl+C` doesn't match while control, shift and C are held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifierPattern {
+    /// Modifiers that can be held with the key of either side
+    pub either: Modifiers,
+    /// Modifier keys that have to be held, the other key of their modifier must not be unless
+    /// the modifier is also in `either`
+    pub sided: KeyModifiers,
+}
+
+impl Default for ModifierPattern {
+    fn default() -> ModifierPattern {
+        ModifierPattern::either(Modifiers::empty())
+    }
+}
+
+impl ModifierPattern {
+    /// Match modifiers held with the key of either side
+    pub const fn either(modifiers: Modifiers) -> ModifierPattern {
+        ModifierPattern {
+            either: modifiers,
+            sided: KeyModifiers::empty(),
+        }
+    }
+
+    /// Match exactly these modifier keys
+    pub const fn sided(key_modifiers: KeyModifiers) -> ModifierPattern {
+        ModifierPattern {
+            either: Modifiers::empty(),
+            sided: key_modifiers,
+        }
+    }
+
+    /// Get the modifiers of the pattern, regardless of side
+    ///
+    /// The right alt key is alt.
+    pub fn modifiers(&self) -> Modifiers {
+        self.either | Modifiers::from(self.sided)
+    }
+
+    /// Check whether held modifiers match the pattern
+    ///
+    /// `key_modifiers` are the held modifier keys. `modifiers` are the held modifiers that have no
+    /// [`KeyModifiers`] bit, hyper, super and function, its other modifiers are ignored. If the
+    /// pattern has AltGr, the right alt key is AltGr rather than alt.
+    pub fn matches(&self, key_modifiers: KeyModifiers, modifiers: Modifiers) -> bool {
+        let mut key_modifiers = key_modifiers;
+        if self.either.contains(Modifiers::AltGr) {
+            if !key_modifiers.contains(KeyModifiers::AltRight) {
+                return false;
+            }
+            key_modifiers.remove(KeyModifiers::AltRight);
+        }
+
+        let sided_match = SIDED.iter().all(|(modifier, left, right)| {
+            let held = key_modifiers & (*left | *right);
+            let sided = self.sided & (*left | *right);
+            if self.either.contains(*modifier) {
+                !held.is_empty() && held.contains(sided)
+            } else {
+                held == sided
+            }
+        });
+        sided_match
+            && UNSIDED.iter().all(|(modifier, _)| {
+                self.either.contains(*modifier) == modifiers.contains(*modifier)
+            })
+    }
+}
@@ -0,0 +1,484 @@
+//! Keyboard shortcuts, like `Ctrl+Shift+KeyK`, and sequences of them, like `Ctrl+K Ctrl+C`
+//!
+//! A shortcut is written as chords separated by whitespace. Each chord is modifiers and a key
+//! separated by `+`, ignoring ASCII case. Keys are named like in [`KeyMappingId`]'s `FromStr`,
+//! so `KeyK`, `k` and `UsK` are the same key. Modifiers match either side unless a specific key
+//! is named, like `ControlLeft`:
+//!
+//! *   `Ctrl`, `Control`
+//! *   `Shift`
+//! *   `Alt`, `Option`, `Opt`
+//! *   `Meta`, `Cmd`, `Command`, `Win`
+//! *   `AltGr`, `Super`, `Hyper`, `Fn`
+//! *   `CmdOrCtrl`, `Primary`: `Cmd` on Mac and `Ctrl` elsewhere, see [`Shortcut::parse`]
+//!
+//! ```
+//! use keycode::{shortcut::Shortcut, KeyboardState, KeyMap, KeyMappingId, KeyState};
+//!
+//! let shortcut: Shortcut = "ctrl+shift+k".parse().unwrap();
+//! assert_eq!(shortcut.to_string(), "Ctrl+Shift+KeyK");
+//!
+//! let mut keyboard_state = KeyboardState::new(None);
+//! keyboard_state.update_key(KeyMap::from(KeyMappingId::ControlRight), KeyState::Pressed);
+//! keyboard_state.update_key(KeyMap::from(KeyMappingId::ShiftLeft), KeyState::Pressed);
+//! keyboard_state.update_key(KeyMap::from(KeyMappingId::UsK), KeyState::Pressed);
+//! assert!(shortcut.matches(&keyboard_state));
+//! ```
+
+use crate::{
+    label::LabelStyle,
+    modifiers::{ModifierPattern, Modifiers},
+    KeyMap, KeyMappingError, KeyMappingId, KeyModifiers, KeyState, KeyboardState,
+};
+use arrayvec::ArrayVec;
+use core::{fmt, str::FromStr};
+
+/// Most chords in a shortcut
+pub const MAX_CHORDS: usize = 4;
+
+/// Chords of a shortcut, in the order they are pressed
+pub type Chords = ArrayVec<Chord, MAX_CHORDS>;
+
+/// Names of modifiers, ignoring ASCII case
+const MODIFIER_NAMES: [(&str, Modifiers); 17] = [
+    ("ctrl", Modifiers::Control),
+    ("control", Modifiers::Control),
+    ("shift", Modifiers::Shift),
+    ("alt", Modifiers::Alt),
+    ("option", Modifiers::Alt),
+    ("opt", Modifiers::Alt),
+    ("meta", Modifiers::Meta),
+    ("cmd", Modifiers::Meta),
+    ("command", Modifiers::Meta),
+    ("win", Modifiers::Meta),
+    ("windows", Modifiers::Meta),
+    ("altgr", Modifiers::AltGr),
+    ("altgraph", Modifiers::AltGr),
+    ("super", Modifiers::Super),
+    ("hyper", Modifiers::Hyper),
+    ("fn", Modifiers::Fn),
+    ("function", Modifiers::Fn),
+];
+
+/// Names of the modifier that is control on PCs and command on Macs
+const PRIMARY_MODIFIER_NAMES: [&str; 4] = ["cmdorctrl", "commandorcontrol", "primary", "mod"];
+
+/// Displayed names of modifiers, in the order they are displayed, for PCs and Macs
+const MODIFIER_LABELS: [(Modifiers, &str, &str); 8] = [
+    (Modifiers::Control, "Ctrl", "Ctrl"),
+    (Modifiers::Alt, "Alt", "Option"),
+    (Modifiers::AltGr, "AltGr", "AltGr"),
+    (Modifiers::Shift, "Shift", "Shift"),
+    (Modifiers::Meta, "Meta", "Cmd"),
+    (Modifiers::Super, "Super", "Super"),
+    (Modifiers::Hyper, "Hyper", "Hyper"),
+    (Modifiers::Fn, "Fn", "Fn"),
+];
+
+/// Modifier keys, in the order they are displayed
+const MODIFIER_KEYS: [(KeyModifiers, KeyMappingId); 8] = [
+    (KeyModifiers::ControlLeft, KeyMappingId::ControlLeft),
+    (KeyModifiers::ControlRight, KeyMappingId::ControlRight),
+    (KeyModifiers::AltLeft, KeyMappingId::AltLeft),
+    (KeyModifiers::AltRight, KeyMappingId::AltRight),
+    (KeyModifiers::ShiftLeft, KeyMappingId::ShiftLeft),
+    (KeyModifiers::ShiftRight, KeyMappingId::ShiftRight),
+    (KeyModifiers::MetaLeft, KeyMappingId::MetaLeft),
+    (KeyModifiers::MetaRight, KeyMappingId::MetaRight),
+];
+
+/// Error returned when a shortcut can't be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+pub enum ParseShortcutError {
+    /// The shortcut has no chords, or a chord has an empty part, like `Ctrl++`
+    Empty,
+    /// A key or modifier has an unknown name
+    UnknownKey(KeyMappingError),
+    /// A key that isn't a modifier is used as one, like `K+Ctrl`
+    NotAModifier(KeyMappingId),
+    /// The shortcut has more than [`MAX_CHORDS`] chords
+    TooManyChords,
+}
+
+impl fmt::Display for ParseShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseShortcutError::Empty => write!(f, "empty shortcut or shortcut part"),
+            ParseShortcutError::UnknownKey(error) => write!(f, "{}", error),
+            ParseShortcutError::NotAModifier(id) => {
+                write!(f, "key {} is not a modifier", id.name())
+            }
+            ParseShortcutError::TooManyChords => {
+                write!(f, "shortcut has more than {} chords", MAX_CHORDS)
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseShortcutError {}
+
+/// A key pressed while holding modifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chord {
+    /// Modifiers that have to be held, and no others
+    pub modifiers: ModifierPattern,
+    /// Key to press
+    pub key: KeyMappingId,
+}
+
+impl Chord {
+    /// Create a chord of a key pressed while holding modifiers on either side
+    pub const fn new(modifiers: Modifiers, key: KeyMappingId) -> Chord {
+        Chord {
+            modifiers: ModifierPattern::either(modifiers),
+            key,
+        }
+    }
+
+    /// Parse a chord, with `CmdOrCtrl` as the primary modifier of `style`
+    pub fn parse(text: &str, style: LabelStyle) -> Result<Chord, ParseShortcutError> {
+        let mut parts = text.split('+');
+        let key = parts.next_back().ok_or(ParseShortcutError::Empty)?;
+        let mut chord = Chord::new(Modifiers::empty(), parse_key(key)?);
+        for part in parts {
+            if let Some(modifier) = parse_modifier(part, style) {
+                chord.modifiers.either.insert(modifier);
+                continue;
+            }
+            let id = parse_key(part)?;
+            match KeyMap::from(id).modifier {
+                Some(key_modifiers) => chord.modifiers.sided.insert(key_modifiers),
+                None => return Err(ParseShortcutError::NotAModifier(id)),
+            }
+        }
+        Ok(chord)
+    }
+
+    /// Check whether pressing a key while holding modifiers is this chord
+    ///
+    /// `key_modifiers` and `modifiers` are the held modifiers, as in [`ModifierPattern::matches`].
+    /// If the key is a modifier itself, it doesn't count as held.
+    pub fn matches_key(
+        &self,
+        key: KeyMappingId,
+        key_modifiers: KeyModifiers,
+        modifiers: Modifiers,
+    ) -> bool {
+        if key != self.key {
+            return false;
+        }
+        let own_key_modifiers = KeyMap::from(key)
+            .modifier
+            .unwrap_or_else(KeyModifiers::empty);
+        self.modifiers.matches(
+            key_modifiers - own_key_modifiers,
+            modifiers - Modifiers::from_key(key),
+        )
+    }
+
+    /// Check whether the key of the chord is held with its modifiers
+    ///
+    /// Other keys that are held are ignored.
+    pub fn matches(&self, keyboard_state: &KeyboardState) -> bool {
+        keyboard_state.is_pressed(self.key)
+            && self.matches_key(
+                self.key,
+                keyboard_state.modifier_keys(),
+                keyboard_state.modifiers(),
+            )
+    }
+
+    /// Get a value that displays the chord in a style, like `Option+Cmd+Escape` on Mac
+    pub fn display(&self, style: LabelStyle) -> impl fmt::Display + '_ {
+        ChordDisplay { chord: self, style }
+    }
+}
+
+impl FromStr for Chord {
+    type Err = ParseShortcutError;
+
+    /// Parse a chord, with `CmdOrCtrl` as control
+    fn from_str(text: &str) -> Result<Chord, Self::Err> {
+        Chord::parse(text, LabelStyle::Pc)
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display(LabelStyle::Pc).fmt(f)
+    }
+}
+
+struct ChordDisplay<'a> {
+    chord: &'a Chord,
+    style: LabelStyle,
+}
+
+impl fmt::Display for ChordDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ModifierPattern { either, sided } = self.chord.modifiers;
+        for (modifier, pc, mac) in MODIFIER_LABELS {
+            if either.contains(modifier) {
+                let label = match self.style {
+                    LabelStyle::Pc => pc,
+                    LabelStyle::Mac => mac,
+                };
+                write!(f, "{}+", label)?;
+            }
+        }
+        for (key_modifier, id) in MODIFIER_KEYS {
+            if sided.contains(key_modifier) {
+                write!(f, "{}+", key_name(id))?;
+            }
+        }
+        f.write_str(key_name(self.chord.key))
+    }
+}
+
+/// A keyboard shortcut: one or more chords pressed one after the other
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Shortcut {
+    /// Chords of the shortcut, in the order they are pressed
+    pub chords: Chords,
+}
+
+impl From<Chord> for Shortcut {
+    fn from(chord: Chord) -> Shortcut {
+        let mut chords = Chords::new();
+        chords.push(chord);
+        Shortcut { chords }
+    }
+}
+
+impl Shortcut {
+    /// Parse a shortcut, with `CmdOrCtrl` as the primary modifier of `style`
+    ///
+    /// ```
+    /// use keycode::{label::LabelStyle, modifiers::Modifiers, shortcut::Shortcut};
+    ///
+    /// let shortcut = Shortcut::parse("CmdOrCtrl+KeyS", LabelStyle::Mac).unwrap();
+    /// assert_eq!(shortcut.chords[0].modifiers.either, Modifiers::Meta);
+    /// ```
+    pub fn parse(text: &str, style: LabelStyle) -> Result<Shortcut, ParseShortcutError> {
+        let mut chords = Chords::new();
+        for chord in text.split_whitespace() {
+            chords
+                .try_push(Chord::parse(chord, style)?)
+                .map_err(|_| ParseShortcutError::TooManyChords)?;
+        }
+        if chords.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+        Ok(Shortcut { chords })
+    }
+
+    /// Check whether a shortcut of a single chord is held, see [`Chord::matches`]
+    ///
+    /// Shortcuts of several chords never match a keyboard state, use a [`ShortcutMatcher`] to
+    /// follow the keys pressed over time.
+    pub fn matches(&self, keyboard_state: &KeyboardState) -> bool {
+        match self.chords.as_slice() {
+            [chord] => chord.matches(keyboard_state),
+            _ => false,
+        }
+    }
+
+    /// Get a value that displays the shortcut in a style, like `Option+Cmd+Escape` on Mac
+    pub fn display(&self, style: LabelStyle) -> impl fmt::Display + '_ {
+        ShortcutDisplay {
+            shortcut: self,
+            style,
+        }
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ParseShortcutError;
+
+    /// Parse a shortcut, with `CmdOrCtrl` as control
+    fn from_str(text: &str) -> Result<Shortcut, Self::Err> {
+        Shortcut::parse(text, LabelStyle::Pc)
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display(LabelStyle::Pc).fmt(f)
+    }
+}
+
+struct ShortcutDisplay<'a> {
+    shortcut: &'a Shortcut,
+    style: LabelStyle,
+}
+
+impl fmt::Display for ShortcutDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, chord) in self.shortcut.chords.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+            chord.display(self.style).fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Progress of a [`ShortcutMatcher`] after a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShortcutMatch {
+    /// The keys pressed so far don't start the shortcut
+    NoMatch,
+    /// The keys pressed so far are the first chords of the shortcut
+    Partial,
+    /// The last chord of the shortcut was pressed, the matcher starts over
+    Complete,
+}
+
+/// Follows key presses to find a shortcut, including shortcuts of several chords
+///
+/// Presses of modifier keys that aren't the next chord's key are ignored, any other key that
+/// doesn't continue the shortcut starts it over.
+///
+/// ```
+/// use keycode::{
+///     modifiers::Modifiers,
+///     shortcut::{ShortcutMatch, ShortcutMatcher},
+///     KeyMappingId, KeyModifiers,
+/// };
+///
+/// let mut matcher = ShortcutMatcher::new("Ctrl+K Ctrl+C".parse().unwrap());
+/// let ctrl = KeyModifiers::ControlLeft;
+/// let none = Modifiers::empty();
+/// assert_eq!(matcher.press(KeyMappingId::UsK, ctrl, none), ShortcutMatch::Partial);
+/// assert_eq!(matcher.press(KeyMappingId::UsC, ctrl, none), ShortcutMatch::Complete);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShortcutMatcher {
+    shortcut: Shortcut,
+    position: usize,
+}
+
+impl ShortcutMatcher {
+    /// Create a matcher for a shortcut
+    pub fn new(shortcut: Shortcut) -> ShortcutMatcher {
+        ShortcutMatcher {
+            shortcut,
+            position: 0,
+        }
+    }
+
+    /// Get the shortcut that is matched
+    pub fn shortcut(&self) -> &Shortcut {
+        &self.shortcut
+    }
+
+    /// Get how many chords of the shortcut were pressed
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Forget the chords pressed so far
+    pub fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    /// Follow a key press, with the modifiers held while pressing it
+    ///
+    /// `key_modifiers` and `modifiers` are the held modifiers, as in [`ModifierPattern::matches`].
+    pub fn press(
+        &mut self,
+        key: KeyMappingId,
+        key_modifiers: KeyModifiers,
+        modifiers: Modifiers,
+    ) -> ShortcutMatch {
+        if self.advance(key, key_modifiers, modifiers) {
+            return self.progress();
+        }
+        if self.position > 0 && Modifiers::from_key(key).is_empty() {
+            self.position = 0;
+            self.advance(key, key_modifiers, modifiers);
+        }
+        self.progress()
+    }
+
+    /// Follow a key event, after the keyboard state was updated with it
+    ///
+    /// Releasing keys doesn't change the progress.
+    pub fn update(
+        &mut self,
+        keyboard_state: &KeyboardState,
+        key: KeyMap,
+        state: KeyState,
+    ) -> ShortcutMatch {
+        match state {
+            KeyState::Pressed => self.press(
+                key.id,
+                keyboard_state.modifier_keys(),
+                keyboard_state.modifiers(),
+            ),
+            KeyState::Released => self.progress(),
+        }
+    }
+
+    /// Move to the next chord if the key press is the current one
+    fn advance(
+        &mut self,
+        key: KeyMappingId,
+        key_modifiers: KeyModifiers,
+        modifiers: Modifiers,
+    ) -> bool {
+        match self.shortcut.chords.get(self.position) {
+            Some(chord) if chord.matches_key(key, key_modifiers, modifiers) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Report the progress, starting over after the last chord
+    fn progress(&mut self) -> ShortcutMatch {
+        if self.position == 0 {
+            ShortcutMatch::NoMatch
+        } else if self.position == self.shortcut.chords.len() {
+            self.position = 0;
+            ShortcutMatch::Complete
+        } else {
+            ShortcutMatch::Partial
+        }
+    }
+}
+
+fn parse_key(name: &str) -> Result<KeyMappingId, ParseShortcutError> {
+    if name.is_empty() {
+        return Err(ParseShortcutError::Empty);
+    }
+    KeyMappingId::from_str(name).map_err(ParseShortcutError::UnknownKey)
+}
+
+fn parse_modifier(name: &str, style: LabelStyle) -> Option<Modifiers> {
+    if PRIMARY_MODIFIER_NAMES
+        .iter()
+        .any(|primary| primary.eq_ignore_ascii_case(name))
+    {
+        return Some(match style {
+            LabelStyle::Pc => Modifiers::Control,
+            LabelStyle::Mac => Modifiers::Meta,
+        });
+    }
+    MODIFIER_NAMES
+        .iter()
+        .find(|(modifier_name, _)| modifier_name.eq_ignore_ascii_case(name))
+        .map(|(_, modifier)| *modifier)
+}
+
+/// Name of a key: its W3 browser event code, or its id for keys without one
+fn key_name(id: KeyMappingId) -> &'static str {
+    KeyMap::from(id)
+        .code
+        .map(|code| code.name())
+        .unwrap_or_else(|| id.name())
+}
@@ -0,0 +1,463 @@
+use super::*;
+use crate::{KeyMap, KeyState};
+use core::convert::TryFrom;
+
+/// Most parameters a sequence can have, including sub-parameters
+const MAX_PARAMETERS: usize = 8;
+
+/// Separates parameters of a sequence
+const PARAMETER_SEPARATOR: u8 = b';';
+/// Separates sub-parameters of a sequence
+const SUB_PARAMETER_SEPARATOR: u8 = b':';
+/// Final byte of kitty keyboard protocol sequences
+const KITTY_FINAL: u8 = b'u';
+/// Length of an X10 mouse report, `CSI M` and three bytes
+const X10_MOUSE_LEN: usize = 6;
+
+/// Keys with their own kitty keyboard protocol code, other than printable keys
+const KITTY_KEYS: [(u32, KeyMappingId); 64] = [
+    (9, KeyMappingId::Tab),
+    (13, KeyMappingId::Enter),
+    (27, KeyMappingId::Escape),
+    (127, KeyMappingId::Backspace),
+    (57358, KeyMappingId::CapsLock),
+    (57359, KeyMappingId::ScrollLock),
+    (57360, KeyMappingId::NumLock),
+    (57361, KeyMappingId::PrintScreen),
+    (57362, KeyMappingId::Pause),
+    (57363, KeyMappingId::ContextMenu),
+    (57376, KeyMappingId::F13),
+    (57377, KeyMappingId::F14),
+    (57378, KeyMappingId::F15),
+    (57379, KeyMappingId::F16),
+    (57380, KeyMappingId::F17),
+    (57381, KeyMappingId::F18),
+    (57382, KeyMappingId::F19),
+    (57383, KeyMappingId::F20),
+    (57384, KeyMappingId::F21),
+    (57385, KeyMappingId::F22),
+    (57386, KeyMappingId::F23),
+    (57387, KeyMappingId::F24),
+    (57399, KeyMappingId::Numpad0),
+    (57400, KeyMappingId::Numpad1),
+    (57401, KeyMappingId::Numpad2),
+    (57402, KeyMappingId::Numpad3),
+    (57403, KeyMappingId::Numpad4),
+    (57404, KeyMappingId::Numpad5),
+    (57405, KeyMappingId::Numpad6),
+    (57406, KeyMappingId::Numpad7),
+    (57407, KeyMappingId::Numpad8),
+    (57408, KeyMappingId::Numpad9),
+    (57409, KeyMappingId::NumpadDecimal),
+    (57410, KeyMappingId::NumpadDivide),
+    (57411, KeyMappingId::NumpadMultiply),
+    (57412, KeyMappingId::NumpadSubtract),
+    (57413, KeyMappingId::NumpadAdd),
+    (57414, KeyMappingId::NumpadEnter),
+    (57415, KeyMappingId::NumpadEqual),
+    (57416, KeyMappingId::NumpadComma),
+    (57428, KeyMappingId::MediaPlay),
+    (57430, KeyMappingId::MediaPlayPause),
+    (57432, KeyMappingId::MediaStop),
+    (57433, KeyMappingId::MediaFastForward),
+    (57434, KeyMappingId::MediaRewind),
+    (57435, KeyMappingId::MediaTrackNext),
+    (57436, KeyMappingId::MediaTrackPrevious),
+    (57437, KeyMappingId::MediaRecord),
+    (57438, KeyMappingId::VolumeDown),
+    (57439, KeyMappingId::VolumeUp),
+    (57440, KeyMappingId::VolumeMute),
+    (57441, KeyMappingId::ShiftLeft),
+    (57442, KeyMappingId::ControlLeft),
+    (57443, KeyMappingId::AltLeft),
+    (57444, KeyMappingId::MetaLeft),
+    (57445, KeyMappingId::Hyper),
+    (57446, KeyMappingId::MetaLeft),
+    (57447, KeyMappingId::ShiftRight),
+    (57448, KeyMappingId::ControlRight),
+    (57449, KeyMappingId::AltRight),
+    (57450, KeyMappingId::MetaRight),
+    (57451, KeyMappingId::Hyper),
+    (57452, KeyMappingId::MetaRight),
+    (57453, KeyMappingId::AltRight),
+];
+
+/// Kitty keyboard protocol event types
+const KITTY_PRESS: u32 = 1;
+const KITTY_REPEAT: u32 = 2;
+const KITTY_RELEASE: u32 = 3;
+
+/// Whether a key was pressed, repeated or released
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TerminalKeyEvent {
+    /// Key was pressed
+    Press,
+    /// Key is held down and repeating
+    Repeat,
+    /// Key was released, only reported by the kitty keyboard protocol
+    Release,
+}
+
+impl TerminalKeyEvent {
+    /// Get the state of the key after the event
+    pub fn key_state(self) -> KeyState {
+        match self {
+            TerminalKeyEvent::Press | TerminalKeyEvent::Repeat => KeyState::Pressed,
+            TerminalKeyEvent::Release => KeyState::Released,
+        }
+    }
+}
+
+/// A key event decoded from terminal input
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TerminalEvent {
+    /// Key of the event, `None` for text that isn't on a US keyboard layout
+    pub key: Option<KeyMap>,
+    /// Modifiers held during the event
+    ///
+    /// Terminals don't tell left and right modifiers apart, so these are always the left ones.
+    pub modifiers: KeyModifiers,
+    /// Whether the key was pressed, repeated or released
+    pub event: TerminalKeyEvent,
+    /// Text typed by the event, if any
+    pub text: Option<char>,
+}
+
+/// Error returned when terminal input can't be decoded into an event
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+pub enum TerminalDecodeError {
+    /// The input ends in the middle of a sequence, more input is needed
+    Incomplete,
+    /// The input starts with a sequence of this many bytes that isn't a key event, like a mouse
+    /// report
+    Unrecognized(usize),
+}
+
+impl core::fmt::Display for TerminalDecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            TerminalDecodeError::Incomplete => write!(f, "incomplete terminal input sequence"),
+            TerminalDecodeError::Unrecognized(len) => {
+                write!(f, "unrecognized terminal input sequence of {} bytes", len)
+            }
+        }
+    }
+}
+
+impl core::error::Error for TerminalDecodeError {}
+
+impl TerminalEvent {
+    fn new(id: KeyMappingId, modifiers: KeyModifiers, text: Option<char>) -> TerminalEvent {
+        TerminalEvent {
+            key: Some(KeyMap::from(id)),
+            modifiers,
+            event: TerminalKeyEvent::Press,
+            text,
+        }
+    }
+
+    /// Decode the first event of terminal input, returning it with the number of bytes it used
+    ///
+    /// A lone escape is reported as [`TerminalDecodeError::Incomplete`], since it may be the start
+    /// of a sequence. Use [`TerminalEvents`] to decode input that is known to be complete.
+    pub fn decode(input: &[u8]) -> Result<(TerminalEvent, usize), TerminalDecodeError> {
+        match input {
+            [] | [ESC] => Err(TerminalDecodeError::Incomplete),
+            [ESC, CSI, ..] => decode_csi(input),
+            [ESC, SS3] => Err(TerminalDecodeError::Incomplete),
+            [ESC, SS3, final_byte, ..] => decode_ss3(*final_byte)
+                .map(|event| (event, 3))
+                .ok_or(TerminalDecodeError::Unrecognized(3)),
+            [ESC, rest @ ..] => {
+                // Alt sends escape before the key
+                let (mut event, len) = decode_character(rest)?;
+                event.modifiers.insert(KeyModifiers::AltLeft);
+                Ok((event, len + 1))
+            }
+            _ => decode_character(input),
+        }
+    }
+}
+
+/// Iterator over the events of complete terminal input
+///
+/// A trailing lone escape is the escape key, unrecognized sequences are skipped and decoding
+/// stops at an incomplete sequence.
+#[derive(Debug, Clone)]
+pub struct TerminalEvents<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> TerminalEvents<'a> {
+    /// Decode events from terminal input
+    pub fn new(input: &'a [u8]) -> TerminalEvents<'a> {
+        TerminalEvents { input }
+    }
+
+    /// Get the input that hasn't been decoded yet
+    pub fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+}
+
+impl Iterator for TerminalEvents<'_> {
+    type Item = TerminalEvent;
+
+    fn next(&mut self) -> Option<TerminalEvent> {
+        loop {
+            match TerminalEvent::decode(self.input) {
+                Ok((event, len)) => {
+                    self.input = &self.input[len..];
+                    return Some(event);
+                }
+                Err(TerminalDecodeError::Unrecognized(len)) => {
+                    self.input = &self.input[len..];
+                }
+                Err(TerminalDecodeError::Incomplete) => {
+                    if self.input == [ESC] {
+                        self.input = &[];
+                        return Some(TerminalEvent::new(
+                            KeyMappingId::Escape,
+                            KeyModifiers::empty(),
+                            None,
+                        ));
+                    }
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+fn printable_key(c: u8) -> Option<(KeyMappingId, KeyModifiers)> {
+    PRINTABLE_KEYS.iter().find_map(|(id, unshifted, shifted)| {
+        if *unshifted == c {
+            Some((*id, KeyModifiers::empty()))
+        } else if *shifted == c {
+            Some((*id, KeyModifiers::ShiftLeft))
+        } else {
+            None
+        }
+    })
+}
+
+/// Decode a single (possibly multi-byte) character
+fn decode_character(input: &[u8]) -> Result<(TerminalEvent, usize), TerminalDecodeError> {
+    let byte = input[0];
+    let event = match byte {
+        TAB => TerminalEvent::new(KeyMappingId::Tab, KeyModifiers::empty(), Some('\t')),
+        CARRIAGE_RETURN => {
+            TerminalEvent::new(KeyMappingId::Enter, KeyModifiers::empty(), Some('\r'))
+        }
+        BACKSPACE | DELETE => {
+            TerminalEvent::new(KeyMappingId::Backspace, KeyModifiers::empty(), None)
+        }
+        ESC => TerminalEvent::new(KeyMappingId::Escape, KeyModifiers::empty(), None),
+        0x00 => TerminalEvent::new(KeyMappingId::Space, KeyModifiers::ControlLeft, None),
+        0x01..=0x1a => {
+            let (id, _) = printable_key(byte - 1 + b'a').unwrap();
+            TerminalEvent::new(id, KeyModifiers::ControlLeft, None)
+        }
+        0x1c..=0x1f => {
+            let (id, modifiers) = printable_key(byte - 0x1c + b'\\').unwrap();
+            TerminalEvent::new(id, modifiers | KeyModifiers::ControlLeft, None)
+        }
+        0x20..=0x7e => {
+            let (id, modifiers) = printable_key(byte).unwrap();
+            TerminalEvent::new(id, modifiers, Some(byte as char))
+        }
+        _ => {
+            let len = match byte {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => return Err(TerminalDecodeError::Unrecognized(1)),
+            };
+            if input.len() < len {
+                return Err(TerminalDecodeError::Incomplete);
+            }
+            let c = core::str::from_utf8(&input[..len])
+                .map_err(|_| TerminalDecodeError::Unrecognized(1))?
+                .chars()
+                .next()
+                .unwrap();
+            return Ok((
+                TerminalEvent {
+                    key: None,
+                    modifiers: KeyModifiers::empty(),
+                    event: TerminalKeyEvent::Press,
+                    text: Some(c),
+                },
+                len,
+            ));
+        }
+    };
+    Ok((event, 1))
+}
+
+fn decode_ss3(final_byte: u8) -> Option<TerminalEvent> {
+    let id = CURSOR_KEYS
+        .iter()
+        .chain(SS3_FUNCTION_KEYS.iter())
+        .find(|(_, f)| *f == final_byte)
+        .map(|(id, _)| *id)
+        .or_else(|| {
+            KEYPAD_KEYS
+                .iter()
+                .find(|(_, _, f)| *f == final_byte)
+                .map(|(id, _, _)| *id)
+        })?;
+    Some(TerminalEvent::new(id, KeyModifiers::empty(), None))
+}
+
+/// Parameters of a `CSI` sequence, each with its sub-parameters
+struct Parameters {
+    values: [[Option<u32>; MAX_PARAMETERS]; MAX_PARAMETERS],
+}
+
+impl Parameters {
+    fn parse(bytes: &[u8]) -> Option<Parameters> {
+        let mut parameters = Parameters {
+            values: [[None; MAX_PARAMETERS]; MAX_PARAMETERS],
+        };
+        let (mut parameter, mut sub_parameter) = (0, 0);
+        for byte in bytes {
+            match *byte {
+                b'0'..=b'9' => {
+                    let value = parameters.values[parameter][sub_parameter].get_or_insert(0);
+                    *value = value.checked_mul(10)?.checked_add(u32::from(byte - b'0'))?;
+                }
+                PARAMETER_SEPARATOR => {
+                    parameter += 1;
+                    sub_parameter = 0;
+                }
+                SUB_PARAMETER_SEPARATOR => sub_parameter += 1,
+                _ => return None,
+            }
+            if parameter >= MAX_PARAMETERS || sub_parameter >= MAX_PARAMETERS {
+                return None;
+            }
+        }
+        Some(parameters)
+    }
+
+    fn get(&self, parameter: usize, sub_parameter: usize) -> Option<u32> {
+        self.values[parameter][sub_parameter]
+    }
+
+    /// Get the modifiers and event type of the second parameter
+    fn modifiers(&self) -> (KeyModifiers, TerminalKeyEvent) {
+        let bits = self.get(1, 0).unwrap_or(1).saturating_sub(1);
+        let event = match self.get(1, 1) {
+            Some(KITTY_REPEAT) => TerminalKeyEvent::Repeat,
+            Some(KITTY_RELEASE) => TerminalKeyEvent::Release,
+            Some(KITTY_PRESS) | Some(_) | None => TerminalKeyEvent::Press,
+        };
+        (modifiers_from_bits(bits as u8), event)
+    }
+}
+
+/// Decode a `CSI` sequence
+fn decode_csi(input: &[u8]) -> Result<(TerminalEvent, usize), TerminalDecodeError> {
+    let body = &input[2..];
+    let final_position = body
+        .iter()
+        .position(|byte| (0x40..=0x7e).contains(byte))
+        .ok_or(TerminalDecodeError::Incomplete)?;
+    let len = final_position + 3;
+    let final_byte = body[final_position];
+    // X10 mouse reports, `CSI M` followed by three bytes for the button and position
+    if final_position == 0 && final_byte == b'M' {
+        return Err(if input.len() < X10_MOUSE_LEN {
+            TerminalDecodeError::Incomplete
+        } else {
+            TerminalDecodeError::Unrecognized(X10_MOUSE_LEN)
+        });
+    }
+    let parameters =
+        Parameters::parse(&body[..final_position]).ok_or(TerminalDecodeError::Unrecognized(len))?;
+    let (modifiers, event) = parameters.modifiers();
+
+    let id = if final_byte == KITTY_FINAL {
+        let code = parameters.get(0, 0).unwrap_or(0);
+        let text = parameters
+            .get(2, 0)
+            .or_else(|| {
+                // The shifted key, reported when shift is held
+                if modifiers.contains(KeyModifiers::ShiftLeft) {
+                    parameters.get(0, 1)
+                } else {
+                    None
+                }
+            })
+            .or(Some(code))
+            .and_then(core::char::from_u32)
+            .filter(|c| !c.is_control() && !(0xe000..=0xf8ff).contains(&(*c as u32)));
+        let key = KITTY_KEYS
+            .iter()
+            .find(|(kitty_code, _)| *kitty_code == code)
+            .map(|(_, id)| *id)
+            .or_else(|| {
+                u8::try_from(code)
+                    .ok()
+                    .and_then(printable_key)
+                    .map(|(id, _)| id)
+            });
+        if key.is_none() && text.is_none() {
+            return Err(TerminalDecodeError::Unrecognized(len));
+        }
+        return Ok((
+            TerminalEvent {
+                key: key.map(KeyMap::from),
+                modifiers,
+                event,
+                text,
+            },
+            len,
+        ));
+    } else if final_byte == TILDE_FINAL {
+        match parameters.get(0, 0) {
+            Some(1) | Some(7) => Some(KeyMappingId::Home),
+            Some(4) | Some(8) => Some(KeyMappingId::End),
+            // Legacy function keys, and F3 in the kitty keyboard protocol
+            Some(11) => Some(KeyMappingId::F1),
+            Some(12) => Some(KeyMappingId::F2),
+            Some(13) => Some(KeyMappingId::F3),
+            Some(14) => Some(KeyMappingId::F4),
+            Some(number) => TILDE_KEYS
+                .iter()
+                .find(|(_, n)| u32::from(*n) == number)
+                .map(|(id, _)| *id),
+            None => None,
+        }
+    } else if final_byte == BACK_TAB_FINAL {
+        return Ok((
+            TerminalEvent {
+                key: Some(KeyMap::from(KeyMappingId::Tab)),
+                modifiers: modifiers | KeyModifiers::ShiftLeft,
+                event,
+                text: None,
+            },
+            len,
+        ));
+    } else {
+        CURSOR_KEYS
+            .iter()
+            .chain(SS3_FUNCTION_KEYS.iter())
+            .find(|(_, f)| *f == final_byte)
+            .map(|(id, _)| *id)
+    };
+
+    let id = id.ok_or(TerminalDecodeError::Unrecognized(len))?;
+    Ok((
+        TerminalEvent {
+            key: Some(KeyMap::from(id)),
+            modifiers,
+            event,
+            text: None,
+        },
+        len,
+    ))
+}
@@ -0,0 +1,159 @@
+use super::*;
+use crate::KeyMap;
+use arrayvec::ArrayVec;
+
+/// Longest sequence a single key press is encoded into
+pub const MAX_SEQUENCE_LEN: usize = 16;
+
+/// Bytes sent by a terminal for a single key press
+pub type TerminalSequence = ArrayVec<u8, MAX_SEQUENCE_LEN>;
+
+/// Encodes key presses into the bytes a terminal sends for them
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TerminalEncoder {
+    /// Application cursor keys mode (`DECCKM`), sending `SS3` instead of `CSI` for cursor keys
+    pub application_cursor_keys: bool,
+    /// Application keypad mode (`DECKPAM`), sending `SS3` sequences for keypad keys
+    pub application_keypad: bool,
+    /// Send escape before keys pressed with alt, otherwise alt is ignored for those keys
+    pub alt_sends_escape: bool,
+    /// Send backspace (`^H`) for the backspace key instead of delete (`^?`)
+    pub backspace_sends_backspace: bool,
+}
+
+impl Default for TerminalEncoder {
+    fn default() -> TerminalEncoder {
+        TerminalEncoder {
+            application_cursor_keys: false,
+            application_keypad: false,
+            alt_sends_escape: true,
+            backspace_sends_backspace: false,
+        }
+    }
+}
+
+fn push_number(sequence: &mut TerminalSequence, number: u8) {
+    if number >= 100 {
+        sequence.push(b'0' + number / 100);
+    }
+    if number >= 10 {
+        sequence.push(b'0' + number / 10 % 10);
+    }
+    sequence.push(b'0' + number % 10);
+}
+
+/// Build `CSI <number> ; <modifiers> <final>`, leaving out the parameters that aren't needed
+fn csi(number: Option<u8>, modifier_bits: u8, final_byte: u8) -> TerminalSequence {
+    let mut sequence = TerminalSequence::new();
+    sequence.push(ESC);
+    sequence.push(CSI);
+    if modifier_bits != 0 {
+        push_number(&mut sequence, number.unwrap_or(1));
+        sequence.push(b';');
+        push_number(&mut sequence, modifier_bits + 1);
+    } else if let Some(number) = number {
+        push_number(&mut sequence, number);
+    }
+    sequence.push(final_byte);
+    sequence
+}
+
+fn ss3(final_byte: u8) -> TerminalSequence {
+    let mut sequence = TerminalSequence::new();
+    sequence.push(ESC);
+    sequence.push(SS3);
+    sequence.push(final_byte);
+    sequence
+}
+
+/// Get the control character typed by control and a printable character, if there is one
+fn control_character(c: u8) -> Option<u8> {
+    match c {
+        b'a'..=b'z' => Some(c - b'a' + 1),
+        b'A'..=b'Z' => Some(c - b'A' + 1),
+        b'@' | b'2' | b' ' => Some(0x00),
+        b'[' | b'3' => Some(ESC),
+        b'\\' | b'4' => Some(0x1c),
+        b']' | b'5' => Some(0x1d),
+        b'^' | b'6' => Some(0x1e),
+        b'_' | b'-' | b'/' | b'7' => Some(0x1f),
+        b'?' | b'8' => Some(DELETE),
+        _ => None,
+    }
+}
+
+impl TerminalEncoder {
+    /// Create an encoder with the modes terminals start in
+    pub fn new() -> TerminalEncoder {
+        TerminalEncoder::default()
+    }
+
+    /// Encode a key press with the modifiers being held
+    ///
+    /// Returns `None` for keys that terminals don't send anything for, like modifier keys.
+    pub fn encode(&self, key: KeyMap, modifiers: KeyModifiers) -> Option<TerminalSequence> {
+        let id = key.id;
+        let bits = modifier_bits(modifiers);
+
+        if let Some((_, final_byte)) = CURSOR_KEYS.iter().find(|(k, _)| *k == id) {
+            if bits == 0 && self.application_cursor_keys {
+                return Some(ss3(*final_byte));
+            }
+            return Some(csi(None, bits, *final_byte));
+        }
+        if let Some((_, final_byte)) = SS3_FUNCTION_KEYS.iter().find(|(k, _)| *k == id) {
+            if bits == 0 {
+                return Some(ss3(*final_byte));
+            }
+            return Some(csi(None, bits, *final_byte));
+        }
+        if let Some((_, number)) = TILDE_KEYS.iter().find(|(k, _)| *k == id) {
+            return Some(csi(Some(*number), bits, TILDE_FINAL));
+        }
+        if id == KeyMappingId::Tab && bits & PARAMETER_SHIFT != 0 {
+            return Some(csi(None, bits & !PARAMETER_SHIFT, BACK_TAB_FINAL));
+        }
+
+        let alt = bits & PARAMETER_ALT != 0;
+        let control = bits & PARAMETER_CONTROL != 0;
+        let shift = bits & PARAMETER_SHIFT != 0;
+        let byte = if let Some((_, c, final_byte)) = KEYPAD_KEYS.iter().find(|(k, _, _)| *k == id) {
+            if self.application_keypad && bits == 0 {
+                return Some(ss3(*final_byte));
+            }
+            *c
+        } else if let Some((_, unshifted, shifted)) =
+            PRINTABLE_KEYS.iter().find(|(k, _, _)| *k == id)
+        {
+            let c = if shift { *shifted } else { *unshifted };
+            if control {
+                control_character(c).unwrap_or(c)
+            } else {
+                c
+            }
+        } else {
+            match id {
+                KeyMappingId::Enter => CARRIAGE_RETURN,
+                KeyMappingId::Tab => TAB,
+                KeyMappingId::Escape => ESC,
+                KeyMappingId::Backspace => {
+                    // Control sends the other one of backspace and delete
+                    if self.backspace_sends_backspace != control {
+                        BACKSPACE
+                    } else {
+                        DELETE
+                    }
+                }
+                _ => return None,
+            }
+        };
+
+        let mut sequence = TerminalSequence::new();
+        if alt && self.alt_sends_escape {
+            sequence.push(ESC);
+        }
+        sequence.push(byte);
+        Some(sequence)
+    }
+}
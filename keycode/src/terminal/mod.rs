@@ -0,0 +1,202 @@
+//! Terminal input: the bytes a terminal sends for key presses
+//!
+//! Follows xterm's encoding, which most terminal emulators share: control characters for
+//! printable keys, `CSI` and `SS3` escape sequences for the rest, with modifiers encoded as a
+//! parameter of the sequence. Printable keys are encoded as on a US keyboard layout.
+//!
+//! F13 to F20 are the exception: xterm has no keys of their own for them and sends them as
+//! shifted F1 to F8, so this module uses the VT220 codes `CSI 25~` to `CSI 34~` for them instead,
+//! as rxvt and the Linux console do.
+//!
+//! Decoding also understands the kitty keyboard protocol's `CSI u` sequences, which report key
+//! releases and repeats as well as presses.
+
+mod decode;
+mod encode;
+
+pub use self::{decode::*, encode::*};
+
+use crate::{KeyMappingId, KeyModifiers};
+
+/// Escape, which starts escape sequences and prefixes keys pressed with alt
+pub const ESC: u8 = 0x1b;
+/// Control sequence introducer, after the escape
+pub const CSI: u8 = b'[';
+/// Single shift three, after the escape
+pub const SS3: u8 = b'O';
+
+// Bits of the modifier parameter of a sequence, which is sent with one added
+const PARAMETER_SHIFT: u8 = 0b0001;
+const PARAMETER_ALT: u8 = 0b0010;
+const PARAMETER_CONTROL: u8 = 0b0100;
+const PARAMETER_META: u8 = 0b1000;
+const PARAMETER_KITTY_HYPER: u8 = 0b0001_0000;
+const PARAMETER_KITTY_META: u8 = 0b0010_0000;
+
+/// Convert modifier keys into the modifier bits of a sequence parameter
+pub(crate) fn modifier_bits(modifiers: KeyModifiers) -> u8 {
+    let mut bits = 0;
+    if modifiers.intersects(KeyModifiers::ShiftLeft | KeyModifiers::ShiftRight) {
+        bits |= PARAMETER_SHIFT;
+    }
+    if modifiers.intersects(KeyModifiers::AltLeft | KeyModifiers::AltRight) {
+        bits |= PARAMETER_ALT;
+    }
+    if modifiers.intersects(KeyModifiers::ControlLeft | KeyModifiers::ControlRight) {
+        bits |= PARAMETER_CONTROL;
+    }
+    if modifiers.intersects(KeyModifiers::MetaLeft | KeyModifiers::MetaRight) {
+        bits |= PARAMETER_META;
+    }
+    bits
+}
+
+/// Convert the modifier bits of a sequence parameter into (left) modifier keys
+///
+/// The kitty keyboard protocol's super, hyper and meta bits are all reported as meta, and its
+/// lock bits are ignored.
+pub(crate) fn modifiers_from_bits(bits: u8) -> KeyModifiers {
+    let mut modifiers = KeyModifiers::empty();
+    if bits & PARAMETER_SHIFT != 0 {
+        modifiers.insert(KeyModifiers::ShiftLeft);
+    }
+    if bits & PARAMETER_ALT != 0 {
+        modifiers.insert(KeyModifiers::AltLeft);
+    }
+    if bits & PARAMETER_CONTROL != 0 {
+        modifiers.insert(KeyModifiers::ControlLeft);
+    }
+    if bits & (PARAMETER_META | PARAMETER_KITTY_HYPER | PARAMETER_KITTY_META) != 0 {
+        modifiers.insert(KeyModifiers::MetaLeft);
+    }
+    modifiers
+}
+
+/// Keys sent as `CSI <final>`, or `SS3 <final>` in application cursor keys mode
+pub(crate) const CURSOR_KEYS: [(KeyMappingId, u8); 6] = [
+    (KeyMappingId::ArrowUp, b'A'),
+    (KeyMappingId::ArrowDown, b'B'),
+    (KeyMappingId::ArrowRight, b'C'),
+    (KeyMappingId::ArrowLeft, b'D'),
+    (KeyMappingId::Home, b'H'),
+    (KeyMappingId::End, b'F'),
+];
+
+/// Keys sent as `SS3 <final>` without modifiers, the first four function keys
+pub(crate) const SS3_FUNCTION_KEYS: [(KeyMappingId, u8); 4] = [
+    (KeyMappingId::F1, b'P'),
+    (KeyMappingId::F2, b'Q'),
+    (KeyMappingId::F3, b'R'),
+    (KeyMappingId::F4, b'S'),
+];
+
+/// Keys sent as `CSI <number> ~`, with the VT220 codes of F13 to F20
+pub(crate) const TILDE_KEYS: [(KeyMappingId, u8); 20] = [
+    (KeyMappingId::Insert, 2),
+    (KeyMappingId::Del, 3),
+    (KeyMappingId::PageUp, 5),
+    (KeyMappingId::PageDown, 6),
+    (KeyMappingId::F5, 15),
+    (KeyMappingId::F6, 17),
+    (KeyMappingId::F7, 18),
+    (KeyMappingId::F8, 19),
+    (KeyMappingId::F9, 20),
+    (KeyMappingId::F10, 21),
+    (KeyMappingId::F11, 23),
+    (KeyMappingId::F12, 24),
+    (KeyMappingId::F13, 25),
+    (KeyMappingId::F14, 26),
+    (KeyMappingId::F15, 28),
+    (KeyMappingId::F16, 29),
+    (KeyMappingId::F17, 31),
+    (KeyMappingId::F18, 32),
+    (KeyMappingId::F19, 33),
+    (KeyMappingId::F20, 34),
+];
+
+/// Keypad keys, with their character and their `SS3 <final>` in application keypad mode
+pub(crate) const KEYPAD_KEYS: [(KeyMappingId, u8, u8); 18] = [
+    (KeyMappingId::Numpad0, b'0', b'p'),
+    (KeyMappingId::Numpad1, b'1', b'q'),
+    (KeyMappingId::Numpad2, b'2', b'r'),
+    (KeyMappingId::Numpad3, b'3', b's'),
+    (KeyMappingId::Numpad4, b'4', b't'),
+    (KeyMappingId::Numpad5, b'5', b'u'),
+    (KeyMappingId::Numpad6, b'6', b'v'),
+    (KeyMappingId::Numpad7, b'7', b'w'),
+    (KeyMappingId::Numpad8, b'8', b'x'),
+    (KeyMappingId::Numpad9, b'9', b'y'),
+    (KeyMappingId::NumpadMultiply, b'*', b'j'),
+    (KeyMappingId::NumpadAdd, b'+', b'k'),
+    (KeyMappingId::NumpadComma, b',', b'l'),
+    (KeyMappingId::NumpadSubtract, b'-', b'm'),
+    (KeyMappingId::NumpadDecimal, b'.', b'n'),
+    (KeyMappingId::NumpadDivide, b'/', b'o'),
+    (KeyMappingId::NumpadEqual, b'=', b'X'),
+    (KeyMappingId::NumpadEnter, b'\r', b'M'),
+];
+
+/// Printable keys of a US keyboard layout, with their unshifted and shifted characters
+pub(crate) const PRINTABLE_KEYS: [(KeyMappingId, u8, u8); 48] = [
+    (KeyMappingId::UsA, b'a', b'A'),
+    (KeyMappingId::UsB, b'b', b'B'),
+    (KeyMappingId::UsC, b'c', b'C'),
+    (KeyMappingId::UsD, b'd', b'D'),
+    (KeyMappingId::UsE, b'e', b'E'),
+    (KeyMappingId::UsF, b'f', b'F'),
+    (KeyMappingId::UsG, b'g', b'G'),
+    (KeyMappingId::UsH, b'h', b'H'),
+    (KeyMappingId::UsI, b'i', b'I'),
+    (KeyMappingId::UsJ, b'j', b'J'),
+    (KeyMappingId::UsK, b'k', b'K'),
+    (KeyMappingId::UsL, b'l', b'L'),
+    (KeyMappingId::UsM, b'm', b'M'),
+    (KeyMappingId::UsN, b'n', b'N'),
+    (KeyMappingId::UsO, b'o', b'O'),
+    (KeyMappingId::UsP, b'p', b'P'),
+    (KeyMappingId::UsQ, b'q', b'Q'),
+    (KeyMappingId::UsR, b'r', b'R'),
+    (KeyMappingId::UsS, b's', b'S'),
+    (KeyMappingId::UsT, b't', b'T'),
+    (KeyMappingId::UsU, b'u', b'U'),
+    (KeyMappingId::UsV, b'v', b'V'),
+    (KeyMappingId::UsW, b'w', b'W'),
+    (KeyMappingId::UsX, b'x', b'X'),
+    (KeyMappingId::UsY, b'y', b'Y'),
+    (KeyMappingId::UsZ, b'z', b'Z'),
+    (KeyMappingId::Digit1, b'1', b'!'),
+    (KeyMappingId::Digit2, b'2', b'@'),
+    (KeyMappingId::Digit3, b'3', b'#'),
+    (KeyMappingId::Digit4, b'4', b'$'),
+    (KeyMappingId::Digit5, b'5', b'%'),
+    (KeyMappingId::Digit6, b'6', b'^'),
+    (KeyMappingId::Digit7, b'7', b'&'),
+    (KeyMappingId::Digit8, b'8', b'*'),
+    (KeyMappingId::Digit9, b'9', b'('),
+    (KeyMappingId::Digit0, b'0', b')'),
+    (KeyMappingId::Space, b' ', b' '),
+    (KeyMappingId::Minus, b'-', b'_'),
+    (KeyMappingId::Equal, b'=', b'+'),
+    (KeyMappingId::BracketLeft, b'[', b'{'),
+    (KeyMappingId::BracketRight, b']', b'}'),
+    (KeyMappingId::Backslash, b'\\', b'|'),
+    (KeyMappingId::Semicolon, b';', b':'),
+    (KeyMappingId::Quote, b'\'', b'"'),
+    (KeyMappingId::Backquote, b'`', b'~'),
+    (KeyMappingId::Comma, b',', b'<'),
+    (KeyMappingId::Period, b'.', b'>'),
+    (KeyMappingId::Slash, b'/', b'?'),
+];
+
+/// Byte sent by the tab key
+pub(crate) const TAB: u8 = b'\t';
+/// Byte sent by the enter key
+pub(crate) const CARRIAGE_RETURN: u8 = b'\r';
+/// Byte sent by the backspace key, unless the terminal sends backspace
+pub(crate) const DELETE: u8 = 0x7f;
+/// Byte sent by the backspace key when the terminal sends backspace
+pub(crate) const BACKSPACE: u8 = 0x08;
+/// Final byte of the sequence sent for shift + tab
+pub(crate) const BACK_TAB_FINAL: u8 = b'Z';
+/// Final byte of sequences ending in a number, like `CSI 3 ~`
+pub(crate) const TILDE_FINAL: u8 = b'~';
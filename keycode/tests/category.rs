@@ -0,0 +1,74 @@
+use keycode::{KeyCategory, KeyMap, KeyMappingId};
+
+#[test]
+fn category_follows_usb_usages_and_codes() {
+    let category = |id: KeyMappingId| KeyMap::from(id).category();
+
+    assert_eq!(category(KeyMappingId::UsA), KeyCategory::Letter);
+    assert_eq!(category(KeyMappingId::Digit0), KeyCategory::Digit);
+    assert_eq!(category(KeyMappingId::Slash), KeyCategory::Symbol);
+    assert_eq!(category(KeyMappingId::F24), KeyCategory::Function);
+    assert_eq!(category(KeyMappingId::PageDown), KeyCategory::Navigation);
+    assert_eq!(category(KeyMappingId::Backspace), KeyCategory::Editing);
+    assert_eq!(category(KeyMappingId::ShiftRight), KeyCategory::Modifier);
+    assert_eq!(category(KeyMappingId::Fn), KeyCategory::Modifier);
+    assert_eq!(category(KeyMappingId::CapsLock), KeyCategory::Lock);
+    assert_eq!(category(KeyMappingId::VolumeUp), KeyCategory::Media);
+    assert_eq!(category(KeyMappingId::MediaPlayPause), KeyCategory::Media);
+    assert_eq!(category(KeyMappingId::LaunchMail), KeyCategory::Application);
+    assert_eq!(category(KeyMappingId::BrightnessUp), KeyCategory::System);
+    assert_eq!(category(KeyMappingId::Lang1), KeyCategory::International);
+    assert_eq!(
+        category(KeyMappingId::IntlYen),
+        KeyCategory::Symbol | KeyCategory::International
+    );
+    assert_eq!(
+        category(KeyMappingId::NumLock),
+        KeyCategory::Numpad | KeyCategory::Lock
+    );
+    assert_eq!(category(KeyMappingId::Escape), KeyCategory::empty());
+
+    // Keys without a W3 browser event code are categorized by their name
+    assert_eq!(
+        category(KeyMappingId::NumpadMemoryStore),
+        KeyCategory::Numpad
+    );
+    assert_eq!(category(KeyMappingId::ChannelUp), KeyCategory::Media);
+    assert_eq!(
+        KeyMappingId::Redo.category(),
+        KeyCategory::Editing | KeyCategory::Application
+    );
+}
+
+#[test]
+fn category_helpers() {
+    let numpad_7 = KeyMap::from(KeyMappingId::Numpad7);
+    assert!(numpad_7.is_numpad());
+    assert!(!numpad_7.is_digit());
+    assert!(KeyMap::from(KeyMappingId::F1).is_function());
+    assert!(KeyMap::from(KeyMappingId::VolumeMute).is_media());
+    assert!(KeyMap::from(KeyMappingId::ScrollLock).is_lock());
+    assert!(!KeyMap::from(KeyMappingId::UsL).is_lock());
+
+    // Ids have the same helpers
+    assert!(KeyMappingId::Numpad7.is_numpad());
+    assert!(!KeyMappingId::Numpad7.is_digit());
+    assert!(KeyMappingId::F1.is_function());
+
+    let letters: Vec<KeyMappingId> = KeyMap::in_category(KeyCategory::Letter)
+        .map(|key_map| key_map.id)
+        .collect();
+    assert_eq!(letters.len(), 26);
+    assert_eq!(letters[0], KeyMappingId::UsA);
+    assert_eq!(
+        KeyMap::in_category(KeyCategory::Function).count(),
+        KeyMap::iter()
+            .filter(|key_map| key_map.is_function())
+            .count()
+    );
+    assert_eq!(
+        KeyMap::in_category(KeyCategory::Lock).count(),
+        4,
+        "CapsLock, ScrollLock, NumLock and FnLock"
+    );
+}
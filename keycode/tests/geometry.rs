@@ -0,0 +1,175 @@
+use keycode::{
+    geometry::{KeyRect, KleLayout, PhysicalLayout},
+    KeyMappingId,
+};
+
+/// keyboard-layout-editor's ANSI 104 preset, as raw data
+const KLE_ANSI_104: &str = r##"["Esc",{x:1},"F1","F2","F3","F4",{x:0.5},"F5","F6","F7","F8",{x:0.5},"F9","F10","F11","F12",{x:0.25},"PrtSc","Scroll Lock","Pause\nBreak"],
+[{y:0.5},"~\n`","!\n1","@\n2","#\n3","$\n4","%\n5","^\n6","&\n7","*\n8","(\n9",")\n0","_\n-","+\n=",{w:2},"Backspace",{x:0.25},"Insert","Home","PgUp",{x:0.25},"Num Lock","/","*","-"],
+[{w:1.5},"Tab","Q","W","E","R","T","Y","U","I","O","P","{\n[","}\n]",{w:1.5},"|\n\\",{x:0.25},"Delete","End","PgDn",{x:0.25},"7\nHome","8\n↑","9\nPgUp",{h:2},"+"],
+[{w:1.75},"Caps Lock","A","S","D","F","G","H","J","K","L",":\n;","\"\n'",{w:2.25},"Enter",{x:3.5},"4\n←","5","6\n→"],
+[{w:2.25},"Shift","Z","X","C","V","B","N","M","<\n,",">\n.","?\n/",{w:2.75},"Shift",{x:1.25},"↑",{x:1.25},"1\nEnd","2\n↓","3\nPgDn",{h:2},"Enter"],
+[{w:1.25},"Ctrl",{w:1.25},"Win",{w:1.25},"Alt",{w:6.25},"",{w:1.25},"Alt",{w:1.25},"Win",{w:1.25},"Menu",{w:1.25},"Ctrl",{x:0.25},"←","↓","→",{x:0.25,w:2},"0\nIns",".\nDel"]"##;
+
+fn overlaps(a: &KeyRect, b: &KeyRect) -> bool {
+    a.x < b.right() && b.x < a.right() && a.y < b.bottom() && b.y < a.bottom()
+}
+
+#[test]
+fn geometry_built_in_layouts_are_consistent() {
+    let expected = [
+        ("ANSI 104", 104, 6),
+        ("ISO 105", 105, 6),
+        ("JIS 109", 109, 6),
+        ("ABNT2 107", 107, 6),
+        ("ANSI TKL", 87, 6),
+        ("ISO TKL", 88, 6),
+        ("ANSI 60%", 61, 5),
+        ("ISO 60%", 62, 5),
+    ];
+    assert_eq!(PhysicalLayout::ALL.len(), expected.len());
+
+    for (layout, (name, keys, rows)) in PhysicalLayout::ALL.iter().zip(expected) {
+        assert_eq!(layout.name, name);
+        assert_eq!(layout.keys.len(), keys, "{}", name);
+        assert_eq!(layout.rows(), rows, "{}", name);
+
+        for (index, key) in layout.keys.iter().enumerate() {
+            for other in &layout.keys[index + 1..] {
+                assert_ne!(key.id, other.id, "{}", name);
+                assert!(
+                    !overlaps(&key.rect, &other.rect),
+                    "{}: {:?} overlaps {:?}",
+                    name,
+                    key.id,
+                    other.id
+                );
+            }
+        }
+
+        // The bottom row of the main block is 15 units wide
+        let main_block_width = layout
+            .row(rows - 1)
+            .take_while(|key| key.id != KeyMappingId::ArrowLeft)
+            .map(|key| key.rect.width)
+            .sum::<f32>();
+        assert_eq!(main_block_width, 15.0, "{}", name);
+    }
+}
+
+#[test]
+fn geometry_positions_keys() {
+    let ansi = PhysicalLayout::ANSI_104;
+    let a = ansi.key(KeyMappingId::UsA).unwrap();
+    assert_eq!(a.rect, KeyRect::new(1.75, 3.5, 1.0, 1.0));
+    assert_eq!(a.row, 3);
+    assert_eq!(ansi.key(KeyMappingId::NumpadAdd).unwrap().rect.height, 2.0);
+    assert_eq!(ansi.key(KeyMappingId::IntlBackslash), None);
+    assert_eq!((ansi.width(), ansi.height()), (22.5, 6.5));
+
+    let iso_enter = PhysicalLayout::ISO_105.key(KeyMappingId::Enter).unwrap();
+    assert_eq!(iso_enter.rect, KeyRect::new(13.75, 2.5, 1.25, 2.0));
+    assert_eq!(iso_enter.secondary, Some(KeyRect::new(13.5, 2.5, 1.5, 1.0)));
+
+    let jis = PhysicalLayout::JIS_109;
+    assert_eq!(jis.key(KeyMappingId::Space).unwrap().rect.width, 2.5);
+    assert!(jis.key(KeyMappingId::IntlYen).is_some());
+
+    let abnt2 = PhysicalLayout::ABNT2_107;
+    assert!(abnt2.key(KeyMappingId::IntlRo).is_some());
+    assert!(abnt2.key(KeyMappingId::NumpadComma).is_some());
+
+    let sixty = PhysicalLayout::ANSI_60;
+    assert_eq!(
+        sixty.key(KeyMappingId::Escape).unwrap().rect,
+        KeyRect::new(0.0, 0.0, 1.0, 1.0)
+    );
+    assert_eq!(sixty.key(KeyMappingId::F1), None);
+    assert_eq!((sixty.width(), sixty.height()), (15.0, 5.0));
+    assert_eq!(PhysicalLayout::ANSI_TKL.width(), 18.25);
+}
+
+#[test]
+fn geometry_kle_import_matches_built_in_layout() {
+    let layout = KleLayout::parse(KLE_ANSI_104).unwrap();
+    assert!(layout.unmatched.is_empty(), "{:?}", layout.unmatched);
+    assert_eq!(layout.keys.as_slice(), PhysicalLayout::ANSI_104.keys);
+
+    // The same layout downloaded as JSON, with its metadata
+    let json = format!(
+        "[{{\"name\": \"Full size\"}}, {}]",
+        KLE_ANSI_104.replace('\n', "")
+    );
+    let json = json
+        .replace("{x:", "{\"x\":")
+        .replace("{y:", "{\"y\":")
+        .replace("{w:", "{\"w\":")
+        .replace("{h:", "{\"h\":")
+        .replace(",w:", ",\"w\":");
+    let downloaded = KleLayout::parse(&json).unwrap();
+    assert_eq!(downloaded.name, "Full size");
+    assert_eq!(downloaded.keys, layout.keys);
+    assert_eq!(downloaded.physical_layout().width(), 22.5);
+}
+
+#[test]
+fn geometry_kle_import_names_rotated_and_unknown_keys() {
+    let layout = KleLayout::parse(
+        r#"[{"name": "Split"},
+            [{"r": 15, "rx": 1, "ry": 2, "w": 1.5}, "KeyG\nG", "Hello"],
+            [{"d": true}, "Decal", {"x": 0.5}, "IntlBackslash", "ShiftLeft"]]"#,
+    )
+    .unwrap();
+
+    let g = layout.keys[0];
+    assert_eq!(g.id, KeyMappingId::UsG);
+    assert_eq!(g.rect, KeyRect::new(1.0, 2.0, 1.5, 1.0));
+    assert_eq!(
+        (g.rotation.angle, g.rotation.x, g.rotation.y),
+        (15.0, 1.0, 2.0)
+    );
+
+    assert_eq!(layout.unmatched.len(), 1);
+    assert_eq!(layout.unmatched[0].legends, ["Hello"]);
+    assert_eq!(layout.unmatched[0].rect, KeyRect::new(2.5, 2.0, 1.0, 1.0));
+
+    // Decals take up room but aren't keys, rows start at the center of rotation
+    let ids: Vec<_> = layout.keys.iter().map(|key| (key.id, key.row)).collect();
+    assert_eq!(
+        ids,
+        [
+            (KeyMappingId::UsG, 0),
+            (KeyMappingId::IntlBackslash, 1),
+            (KeyMappingId::ShiftLeft, 1),
+        ]
+    );
+    assert_eq!(layout.keys[1].rect.x, 2.5);
+    assert_eq!(layout.keys[1].rect.y, 3.0);
+}
+
+#[test]
+fn geometry_kle_strings_decode_unicode_escapes() {
+    let layout = KleLayout::parse(r#"["\u00e9\n\uD83D\uDE00"]"#).unwrap();
+    assert_eq!(layout.unmatched[0].legends, ["\u{e9}", "\u{1f600}"]);
+
+    // Surrogates must come in pairs
+    assert!(KleLayout::parse(r#"["\uD83D"]"#).is_err());
+    assert!(KleLayout::parse(r#"["\uDE00\uD83D"]"#).is_err());
+}
+
+#[test]
+fn geometry_kle_errors_report_the_line() {
+    let error = KleLayout::parse("[\"A\"],\n[{w: \"wide\"}, \"B\"]").unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(error.to_string(), "line 2: property `w` is not a number");
+
+    let error = KleLayout::parse("[[\"A\", 1]]").unwrap_err();
+    assert_eq!(error.line, 1);
+
+    let error = KleLayout::parse("[\"A\"\n").unwrap_err();
+    assert_eq!(error.line, 2);
+
+    // Deeply nested arrays are an error rather than a stack overflow
+    let error = KleLayout::parse(&"[".repeat(200_000)).unwrap_err();
+    assert_eq!(error.line, 1);
+}
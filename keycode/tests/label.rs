@@ -0,0 +1,71 @@
+use keycode::{
+    label::{KeyLabel, LabelStyle},
+    KeyMap, KeyMappingId,
+};
+
+#[test]
+fn label_has_short_long_and_glyph_forms() {
+    assert_eq!(
+        KeyMappingId::ShiftLeft.label(LabelStyle::Pc),
+        KeyLabel {
+            short: "Shift",
+            long: "Left Shift",
+            glyph: Some("⇧"),
+        }
+    );
+    assert_eq!(
+        KeyMappingId::Numpad7.label(LabelStyle::Pc).long,
+        "Num Pad 7"
+    );
+    assert_eq!(
+        KeyMappingId::VolumeUp.label(LabelStyle::Pc).long,
+        "Volume Up"
+    );
+    assert_eq!(KeyMappingId::UsA.label(LabelStyle::Pc).short, "A");
+    assert_eq!(KeyMappingId::F12.label(LabelStyle::Pc).glyph, None);
+}
+
+#[test]
+fn label_follows_platform_naming() {
+    let labels = |id: KeyMappingId| (id.label(LabelStyle::Pc), id.label(LabelStyle::Mac));
+
+    let (pc, mac) = labels(KeyMappingId::AltRight);
+    assert_eq!((pc.short, pc.long, pc.glyph), ("Alt", "Right Alt", None));
+    assert_eq!(
+        (mac.short, mac.long, mac.glyph),
+        ("Option", "Right Option", Some("⌥"))
+    );
+
+    let (pc, mac) = labels(KeyMappingId::Backspace);
+    assert_eq!(pc.long, "Backspace");
+    assert_eq!(mac.long, "Delete");
+
+    let (pc, mac) = labels(KeyMappingId::Del);
+    assert_eq!(pc.long, "Delete");
+    assert_eq!(mac.long, "Forward Delete");
+
+    // Keys without a Mac name use the PC one
+    let (pc, mac) = labels(KeyMappingId::Numpad7);
+    assert_eq!(pc, mac);
+    assert_eq!(LabelStyle::default(), LabelStyle::Pc);
+}
+
+#[test]
+fn label_symbol_falls_back_to_short_label() {
+    let control = KeyMappingId::ControlLeft;
+    assert_eq!(control.label(LabelStyle::Mac).symbol(), "⌃");
+    assert_eq!(control.label(LabelStyle::Pc).symbol(), "Ctrl");
+}
+
+#[test]
+fn label_every_key() {
+    for style in [LabelStyle::Pc, LabelStyle::Mac] {
+        for key_map in KeyMap::iter() {
+            let label = key_map.label(style);
+            assert_eq!(label, key_map.id.label(style));
+            assert!(!label.short.is_empty(), "{:?}", key_map.id);
+            assert!(label.long.len() >= label.short.len(), "{:?}", key_map.id);
+            assert!(label.glyph.map_or(true, |glyph| !glyph.is_empty()));
+        }
+    }
+}
@@ -0,0 +1,709 @@
+use keycode::{
+    layout::{KeyOutput, Keystroke, Layout, ShiftState},
+    KeyMappingId, KeyModifiers,
+};
+
+const KLC: &str = "KBD\tUSCUST\t\"US - Custom\"
+
+COPYRIGHT\t\"(c) 2024 Company\"
+
+LOCALEID\t\"00000409\"
+
+SHIFTSTATE
+
+0\t//Column 4
+1\t//Column 5 : Shft
+2\t//Column 6 :       Ctrl
+6\t//Column 7 :       Ctrl Alt
+7\t//Column 8 : Shft  Ctrl Alt
+
+LAYOUT\t\t;an extra '@' at the end is a dead key
+
+//SC\tVK_\t\tCap\t0\t1\t2\t6\t7
+//--\t----\t\t----\t----\t----\t----\t----\t----
+
+02\t1\t\t0\t1\t0021\t-1\t00a1\t00b9\t// DIGIT ONE, EXCLAMATION MARK
+16\tU\t\t1\tu\tU\t-1\t-1\t-1\t// LATIN SMALL LETTER U
+12\tE\t\t1\te\tE\t-1\t00e9\t00c9\t// LATIN SMALL LETTER E
+1e\tA\t\t1\ta\tA\t-1\t00e1\t00c1\t// LATIN SMALL LETTER A
+27\tOEM_1\t\tSGCap\t;\t:\t-1\t-1\t-1\t// SEMICOLON, COLON
+-1\t-1\t\t0\t00f6\t00d6\t\t\t\t// caps lock outputs
+28\tOEM_7\t\t0\t'\t0022\t-1\t00b4@\t00a8@\t// APOSTROPHE, QUOTATION MARK
+39\tSPACE\t\t0\t0020\t0020\t0020\t-1\t-1\t// SPACE
+56\tOEM_102\t\t0\t\\\t|\t001c\t-1\t-1\t// REVERSE SOLIDUS, VERTICAL LINE
+2e\tC\t\t1\tc\tC\t-1\t%%\t-1\t// LATIN SMALL LETTER C
+
+
+LIGATURE
+
+//VK_\tMod#\tChar0\tChar1
+//----\t\t----\t----\t----
+
+C\t3\t0063\t0301\t// c + combining acute
+
+DEADKEY\t00b4
+
+0061\t00e1\t// a -> \u{e1}
+0065\t00e9\t// e -> \u{e9}
+0075\t00fa\t// u -> \u{fa}
+0020\t00b4\t//   -> \u{b4}
+
+DEADKEY\t00a8
+
+0075\t00fc\t// u -> \u{fc}
+0020\t00a8\t//   -> \u{a8}
+
+KEYNAME
+
+01\tEsc
+39\tSpace
+
+ENDKBD
+";
+
+fn text(text: &str) -> Option<KeyOutput> {
+    Some(KeyOutput::Text(String::from(text)))
+}
+
+#[test]
+fn can_parse_a_klc_layout() {
+    let layout = Layout::from_klc(KLC).unwrap();
+    assert_eq!(layout.name, "US - Custom");
+    let commented = Layout::from_klc("KBD\tX\t\"X // Y\"  // Comment\r\n").unwrap();
+    assert_eq!(commented.name, "X // Y");
+
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsA, ShiftState::empty())
+            .cloned(),
+        text("a")
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsA, ShiftState::Shift).cloned(),
+        text("A")
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsA, ShiftState::AltGr).cloned(),
+        text("\u{e1}")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsA, ShiftState::Control)
+            .cloned(),
+        None
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Digit1, ShiftState::Shift)
+            .cloned(),
+        text("!")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::IntlBackslash, ShiftState::empty())
+            .cloned(),
+        text("\\")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::IntlBackslash, ShiftState::Control)
+            .cloned(),
+        text("\u{1c}")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Quote, ShiftState::AltGr)
+            .cloned(),
+        Some(KeyOutput::Dead('\u{b4}'))
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsC, ShiftState::AltGr).cloned(),
+        text("c\u{301}")
+    );
+}
+
+#[test]
+fn klc_caps_lock_columns_are_applied() {
+    let layout = Layout::from_klc(KLC).unwrap();
+
+    // `Cap` of 1 swaps the base and shift outputs
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsE, ShiftState::CapsLock)
+            .cloned(),
+        text("E")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsE, ShiftState::CapsLock | ShiftState::Shift)
+            .cloned(),
+        text("e")
+    );
+
+    // `SGCap` keys take their caps lock outputs from the following row
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Semicolon, ShiftState::empty())
+            .cloned(),
+        text(";")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Semicolon, ShiftState::CapsLock)
+            .cloned(),
+        text("\u{f6}")
+    );
+
+    // Keys caps lock doesn't affect fall back to their normal outputs
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Digit1, ShiftState::CapsLock)
+            .cloned(),
+        text("1")
+    );
+}
+
+#[test]
+fn klc_dead_keys_are_parsed() {
+    let layout = Layout::from_klc(KLC).unwrap();
+
+    let acute = layout.dead_key('\u{b4}').unwrap();
+    assert_eq!(acute.compose('e'), Some("\u{e9}"));
+    assert_eq!(acute.compose(' '), Some("\u{b4}"));
+    assert_eq!(acute.compose('x'), None);
+    assert_eq!(
+        layout.dead_key('\u{a8}').unwrap().compose('u'),
+        Some("\u{fc}")
+    );
+    assert_eq!(layout.dead_keys().count(), 2);
+}
+
+#[test]
+fn can_parse_utf16_klc_bytes() {
+    let mut bytes = vec![0xff, 0xfe];
+    for unit in KLC.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    assert_eq!(
+        Layout::from_klc_bytes(&bytes).unwrap(),
+        Layout::from_klc(KLC).unwrap()
+    );
+}
+
+#[test]
+fn klc_errors_report_the_line() {
+    let error =
+        Layout::from_klc("KBD\tX\t\"X\"\nSHIFTSTATE\n0\nLAYOUT\nzz\tA\t1\ta\n").unwrap_err();
+    assert_eq!(error.line, 5);
+
+    // A short `SGCap` caps lock row
+    let error = Layout::from_klc("KBD\tX\t\"X\"\nSHIFTSTATE\n0\nLAYOUT\n10\tQ\tSGCap\tq\n-1\t-1\n")
+        .unwrap_err();
+    assert_eq!(error.line, 6);
+}
+
+#[test]
+fn layout_finds_keystrokes_for_text() {
+    let layout = Layout::from_klc(KLC).unwrap();
+
+    assert_eq!(
+        layout.keystrokes('A'),
+        Some(vec![Keystroke {
+            id: KeyMappingId::UsA,
+            shift_state: ShiftState::Shift,
+        }])
+    );
+
+    // Characters on a key are preferred over dead keys
+    assert_eq!(
+        layout.keystrokes('\u{e9}'),
+        Some(vec![Keystroke {
+            id: KeyMappingId::UsE,
+            shift_state: ShiftState::AltGr,
+        }])
+    );
+
+    // Characters only reachable with a dead key take two keystrokes
+    let keystrokes = layout.keystrokes('\u{fa}').unwrap();
+    assert_eq!(
+        keystrokes,
+        vec![
+            Keystroke {
+                id: KeyMappingId::Quote,
+                shift_state: ShiftState::AltGr,
+            },
+            Keystroke {
+                id: KeyMappingId::UsU,
+                shift_state: ShiftState::empty(),
+            },
+        ]
+    );
+    assert_eq!(keystrokes[0].key_modifiers(), KeyModifiers::AltRight);
+
+    assert_eq!(layout.keystrokes('\u{3b1}'), None);
+    assert_eq!(layout.keystrokes_for_str("a!").map(|k| k.len()), Some(2));
+}
+
+const KEYLAYOUT: &str = r#"<?xml version="1.1" encoding="UTF-8"?>
+<!DOCTYPE keyboard SYSTEM "file://localhost/System/Library/DTDs/KeyboardLayout.dtd">
+<!-- Last edited by Ukelele -->
+<keyboard group="0" id="-19336" name="U.S. Custom" maxout="2">
+    <layouts>
+        <layout first="0" last="17" mapSet="16c" modifiers="f4"/>
+        <layout first="18" last="18" mapSet="b4" modifiers="f4"/>
+    </layouts>
+    <modifierMap id="f4" defaultIndex="0">
+        <keyMapSelect mapIndex="0">
+            <modifier keys=""/>
+        </keyMapSelect>
+        <keyMapSelect mapIndex="1">
+            <modifier keys="anyShift caps?"/>
+        </keyMapSelect>
+        <keyMapSelect mapIndex="2">
+            <modifier keys="caps"/>
+        </keyMapSelect>
+        <keyMapSelect mapIndex="3">
+            <modifier keys="anyOption"/>
+        </keyMapSelect>
+        <keyMapSelect mapIndex="4">
+            <modifier keys="anyShift anyOption caps?"/>
+        </keyMapSelect>
+        <keyMapSelect mapIndex="5">
+            <modifier keys="command anyShift? anyOption? caps?"/>
+        </keyMapSelect>
+    </modifierMap>
+    <keyMapSet id="16c">
+        <keyMap index="0">
+            <key code="0" action="a"/>
+            <key code="14" action="e"/>
+            <key code="18" output="1"/>
+            <key code="32" action="u"/>
+            <key code="45" output="n"/>
+            <key code="49" action="space"/>
+            <key code="52" output="&#x0003;"/>
+        </keyMap>
+        <keyMap index="1">
+            <key code="0" output="A"/>
+            <key code="14" output="E"/>
+            <key code="18" output="!"/>
+            <key code="32" output="U"/>
+            <key code="45" output="N"/>
+            <key code="49" output=" "/>
+        </keyMap>
+        <keyMap index="2" baseMapSet="16c" baseIndex="1">
+            <key code="18" output="1"/>
+        </keyMap>
+        <keyMap index="3">
+            <key code="0" output="&#x00E5;"/>
+            <key code="14" action="acute"/>
+            <key code="18" output="&#x00A1;"/>
+            <key code="32" action="umlaut"/>
+            <key code="45" output="&#x02DC;"/>
+            <key code="49" output="&#x00A0;"/>
+        </keyMap>
+        <keyMap index="4">
+            <key code="0" output="&#x00C5;"/>
+            <key code="18" output="&#x2044;"/>
+        </keyMap>
+        <keyMap index="5" baseMapSet="16c" baseIndex="0">
+        </keyMap>
+    </keyMapSet>
+    <actions>
+        <action id="a">
+            <when state="none" output="a"/>
+            <when state="acute" output="&#x00E1;"/>
+            <when state="umlaut" output="&#x00E4;"/>
+        </action>
+        <action id="acute">
+            <when state="none" next="acute"/>
+        </action>
+        <action id="e">
+            <when state="none" output="e"/>
+            <when state="acute" output="&#x00E9;"/>
+        </action>
+        <action id="space">
+            <when state="none" output=" "/>
+            <when state="acute" output="&#x00B4;"/>
+        </action>
+        <action id="u">
+            <when state="none" output="u"/>
+            <when state="acute" output="&#x00FA;"/>
+            <when state="umlaut" output="&#x00FC;"/>
+        </action>
+        <action id="umlaut">
+            <when state="none" next="umlaut"/>
+        </action>
+    </actions>
+    <terminators>
+        <when state="acute" output="&#x00B4;"/>
+        <when state="umlaut" output="&#x00A8;"/>
+    </terminators>
+</keyboard>
+"#;
+
+#[test]
+fn can_parse_a_keylayout() {
+    let layout = Layout::from_keylayout(KEYLAYOUT).unwrap();
+    assert_eq!(layout.name, "U.S. Custom");
+
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsA, ShiftState::empty())
+            .cloned(),
+        text("a")
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsA, ShiftState::Shift).cloned(),
+        text("A")
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsA, ShiftState::Alt).cloned(),
+        text("\u{e5}")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsA, ShiftState::Shift | ShiftState::Alt)
+            .cloned(),
+        text("\u{c5}")
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsA, ShiftState::Meta).cloned(),
+        text("a")
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::Space, ShiftState::Alt).cloned(),
+        text("\u{a0}")
+    );
+
+    // Unmodified keys without an output in a map have no output
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsE, ShiftState::Shift | ShiftState::Alt)
+            .cloned(),
+        None
+    );
+}
+
+#[test]
+fn keylayout_modifier_maps_are_resolved() {
+    let layout = Layout::from_keylayout(KEYLAYOUT).unwrap();
+
+    // Caps lock uses a map inheriting from the shift map
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsN, ShiftState::CapsLock)
+            .cloned(),
+        text("N")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Digit1, ShiftState::CapsLock)
+            .cloned(),
+        text("1")
+    );
+
+    // Caps lock is optional in the shift map
+    assert_eq!(
+        layout
+            .output(
+                KeyMappingId::Digit1,
+                ShiftState::CapsLock | ShiftState::Shift
+            )
+            .cloned(),
+        text("!")
+    );
+
+    // Control isn't in any map so it uses the default map
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsN, ShiftState::Control)
+            .cloned(),
+        text("n")
+    );
+}
+
+#[test]
+fn keylayout_actions_become_dead_keys() {
+    let layout = Layout::from_keylayout(KEYLAYOUT).unwrap();
+
+    assert_eq!(
+        layout.output(KeyMappingId::UsE, ShiftState::Alt).cloned(),
+        Some(KeyOutput::Dead('\u{b4}'))
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsU, ShiftState::Alt).cloned(),
+        Some(KeyOutput::Dead('\u{a8}'))
+    );
+
+    let acute = layout.dead_key('\u{b4}').unwrap();
+    assert_eq!(acute.compose('a'), Some("\u{e1}"));
+    assert_eq!(acute.compose('u'), Some("\u{fa}"));
+    assert_eq!(acute.compose(' '), Some("\u{b4}"));
+    assert_eq!(
+        layout.dead_key('\u{a8}').unwrap().compose('u'),
+        Some("\u{fc}")
+    );
+
+    assert_eq!(
+        layout.keystrokes('\u{fc}'),
+        Some(vec![
+            Keystroke {
+                id: KeyMappingId::UsU,
+                shift_state: ShiftState::Alt,
+            },
+            Keystroke {
+                id: KeyMappingId::UsU,
+                shift_state: ShiftState::empty(),
+            },
+        ])
+    );
+}
+
+#[test]
+fn keylayout_errors_report_the_line() {
+    let error =
+        Layout::from_keylayout("<keyboard name=\"x\">\n<layouts>\n</keyboard>").unwrap_err();
+    assert_eq!(error.line, 3);
+
+    // Deeply nested elements are an error rather than a stack overflow
+    let nested = "<a>\n".repeat(100_000);
+    let error = Layout::from_keylayout(&nested).unwrap_err();
+    assert_eq!(error.line, 66);
+}
+
+#[test]
+fn keylayout_inheritance_cycles_are_followed_once() {
+    let layout = Layout::from_keylayout(
+        r#"<keyboard group="0" id="1" name="Cycle">
+    <layouts>
+        <layout first="0" last="0" modifiers="m" mapSet="s"/>
+    </layouts>
+    <modifierMap id="m" defaultIndex="0">
+        <keyMapSelect mapIndex="0"><modifier keys=""/></keyMapSelect>
+        <keyMapSelect mapIndex="1"><modifier keys="anyShift"/></keyMapSelect>
+        <keyMapSelect mapIndex="2"><modifier keys="anyOption"/></keyMapSelect>
+    </modifierMap>
+    <keyMapSet id="s">
+        <keyMap index="0"><key code="0" output="a"/></keyMap>
+        <keyMap index="1" baseIndex="2"><key code="1" output="S"/></keyMap>
+        <keyMap index="2" baseIndex="1"></keyMap>
+    </keyMapSet>
+</keyboard>
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        layout.output(KeyMappingId::UsS, ShiftState::Alt).cloned(),
+        text("S")
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsA, ShiftState::Alt).cloned(),
+        None
+    );
+}
+
+const KBD_KEYMAP: &str = "# German style test keymap
+keymaps 0-2,4,6,8
+
+include \"linux-with-alt-and-altgr\"
+charset \"iso-8859-1\"
+
+keycode   2 = one              exclam           onesuperior
+keycode  13 = dead_acute       dead_grave       dead_cedilla
+keycode  16 = +q               +Q               at               Control_q
+keycode  18 = e
+keycode  21 = +z               +Z
+keycode  39 = +odiaeresis      +Odiaeresis
+keycode  40 = +adiaeresis      +Adiaeresis      asciicircum
+keycode  57 = space            space            space            nul
+keycode  86 = less             greater          bar
+keycode 248 = a
+keycode  52 = 0x0301
+keycode  53 = 0x002d           0x005f           0x0b2d           0xf01f
+altgr keycode 18 = U+20AC
+shift altgr keycode 2 = \\
+    exclamdown
+string F1 = \"\\033[[A\"
+
+compose '\\'' 'e' to '\u{e9}'
+compose '\\'' 'a' to U+00E1
+compose '`' 'e' to '\u{e8}'
+compose 'o' 'e' to '\u{153}'  # Compose key sequence, not a dead key
+";
+
+#[test]
+fn can_parse_a_kbd_keymap() {
+    let layout = Layout::from_kbd_keymap(KBD_KEYMAP).unwrap();
+
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Digit1, ShiftState::Shift)
+            .cloned(),
+        text("!")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Digit1, ShiftState::AltGr)
+            .cloned(),
+        text("\u{b9}")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Digit1, ShiftState::Shift | ShiftState::AltGr)
+            .cloned(),
+        text("\u{a1}")
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsQ, ShiftState::AltGr).cloned(),
+        text("@")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsQ, ShiftState::Control)
+            .cloned(),
+        text("\u{11}")
+    );
+    assert_eq!(
+        layout.output(KeyMappingId::UsE, ShiftState::AltGr).cloned(),
+        text("\u{20ac}")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Quote, ShiftState::Shift)
+            .cloned(),
+        text("\u{c4}")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::IntlBackslash, ShiftState::AltGr)
+            .cloned(),
+        text("|")
+    );
+
+    // Kernel keysym codes hold their type in the high byte, only Latin-1 types are characters
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Slash, ShiftState::Shift)
+            .cloned(),
+        text("_")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Slash, ShiftState::AltGr)
+            .cloned(),
+        text("-")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Slash, ShiftState::Control)
+            .cloned(),
+        text("\u{1f}")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Period, ShiftState::empty())
+            .cloned(),
+        None
+    );
+}
+
+#[test]
+fn kbd_keymap_letters_follow_caps_lock() {
+    let layout = Layout::from_kbd_keymap(KBD_KEYMAP).unwrap();
+
+    // Single letters fill in their shifted and control keymaps
+    assert_eq!(
+        layout.output(KeyMappingId::UsE, ShiftState::Shift).cloned(),
+        text("E")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsE, ShiftState::Control)
+            .cloned(),
+        text("\u{5}")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsE, ShiftState::CapsLock)
+            .cloned(),
+        text("E")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::UsY, ShiftState::CapsLock)
+            .cloned(),
+        text("Z")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Semicolon, ShiftState::CapsLock)
+            .cloned(),
+        text("\u{d6}")
+    );
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Digit1, ShiftState::CapsLock)
+            .cloned(),
+        text("1")
+    );
+}
+
+#[test]
+fn kbd_keymap_dead_keys_are_parsed() {
+    let layout = Layout::from_kbd_keymap(KBD_KEYMAP).unwrap();
+
+    assert_eq!(
+        layout
+            .output(KeyMappingId::Equal, ShiftState::empty())
+            .cloned(),
+        Some(KeyOutput::Dead('\u{b4}'))
+    );
+    assert_eq!(
+        layout.dead_key('\u{b4}').unwrap().compose('a'),
+        Some("\u{e1}")
+    );
+    assert_eq!(layout.dead_key('`').unwrap().compose('e'), Some("\u{e8}"));
+    assert_eq!(layout.dead_key('o'), None);
+    assert_eq!(
+        layout.keystrokes('\u{e9}'),
+        Some(vec![
+            Keystroke {
+                id: KeyMappingId::Equal,
+                shift_state: ShiftState::empty(),
+            },
+            Keystroke {
+                id: KeyMappingId::UsE,
+                shift_state: ShiftState::empty(),
+            },
+        ])
+    );
+}
+
+#[test]
+fn kbd_keymap_round_trips() {
+    let layout = Layout::from_klc(KLC).unwrap();
+    let keymap = layout.to_kbd_keymap();
+    assert!(keymap.starts_with("# US - Custom\nkeymaps 0-4\n"));
+    assert!(keymap.contains("\nkeycode 30 = +a +A aacute Aacute\n"));
+    assert!(keymap.contains("\ncompose '\\'' 'a' to '\u{e1}'\n"));
+
+    let reimported = Layout::from_kbd_keymap(&keymap).unwrap();
+    for key in layout.keys() {
+        for (shift_state, output) in &key.outputs {
+            // Ligatures and caps lock outputs of keys that aren't letters can't be represented
+            if shift_state.contains(ShiftState::CapsLock)
+                || matches!(output, KeyOutput::Text(text) if text.chars().count() > 1)
+            {
+                continue;
+            }
+            assert_eq!(
+                reimported.output(key.id, *shift_state),
+                Some(output),
+                "{} {:?}",
+                key.id,
+                shift_state
+            );
+        }
+    }
+    assert_eq!(reimported.dead_key('\u{b4}'), layout.dead_key('\u{b4}'));
+}
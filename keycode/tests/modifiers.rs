@@ -0,0 +1,107 @@
+use keycode::{
+    modifiers::{ModifierPattern, Modifiers},
+    KeyMap, KeyMappingId, KeyModifiers, KeyState, KeyboardState,
+};
+
+#[test]
+fn modifiers_from_and_to_key_modifiers() {
+    let held = KeyModifiers::ShiftRight | KeyModifiers::AltRight | KeyModifiers::MetaLeft;
+    assert_eq!(
+        Modifiers::from(held),
+        Modifiers::Shift | Modifiers::Alt | Modifiers::Meta
+    );
+    assert_eq!(
+        Modifiers::from_key_modifiers(held, true),
+        Modifiers::Shift | Modifiers::AltGr | Modifiers::Meta
+    );
+    assert_eq!(
+        Modifiers::from_key_modifiers(KeyModifiers::AltLeft | KeyModifiers::AltRight, true),
+        Modifiers::Alt | Modifiers::AltGr
+    );
+
+    let modifiers = Modifiers::Control | Modifiers::AltGr | Modifiers::Hyper;
+    assert_eq!(
+        KeyModifiers::from(modifiers),
+        KeyModifiers::ControlLeft | KeyModifiers::AltRight
+    );
+    assert_eq!(
+        modifiers.sides(),
+        KeyModifiers::ControlLeft | KeyModifiers::ControlRight | KeyModifiers::AltRight
+    );
+}
+
+#[test]
+fn modifiers_of_keys() {
+    assert_eq!(
+        Modifiers::from_key(KeyMappingId::ControlRight),
+        Modifiers::Control
+    );
+    assert_eq!(Modifiers::from_key(KeyMappingId::Hyper), Modifiers::Hyper);
+    assert_eq!(Modifiers::from_key(KeyMappingId::Super), Modifiers::Super);
+    assert_eq!(Modifiers::from_key(KeyMappingId::Fn), Modifiers::Fn);
+    assert_eq!(Modifiers::from_key(KeyMappingId::UsA), Modifiers::empty());
+
+    let mut keyboard_state = KeyboardState::new(None);
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::ShiftRight), KeyState::Pressed);
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::Fn), KeyState::Pressed);
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::UsA), KeyState::Pressed);
+    assert_eq!(keyboard_state.modifier_keys(), KeyModifiers::ShiftRight);
+    assert_eq!(keyboard_state.modifiers(), Modifiers::Shift | Modifiers::Fn);
+
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::Fn), KeyState::Released);
+    assert_eq!(keyboard_state.modifiers(), Modifiers::Shift);
+}
+
+#[test]
+fn modifiers_match_either_side() {
+    let ctrl_shift = ModifierPattern::either(Modifiers::Control | Modifiers::Shift);
+    let none = Modifiers::empty();
+
+    assert!(ctrl_shift.matches(KeyModifiers::ControlLeft | KeyModifiers::ShiftLeft, none));
+    assert!(ctrl_shift.matches(KeyModifiers::ControlRight | KeyModifiers::ShiftLeft, none));
+    assert!(ctrl_shift.matches(
+        KeyModifiers::ControlLeft | KeyModifiers::ControlRight | KeyModifiers::ShiftRight,
+        none
+    ));
+    // Missing and extra modifiers don't match
+    assert!(!ctrl_shift.matches(KeyModifiers::ControlLeft, none));
+    assert!(!ctrl_shift.matches(
+        KeyModifiers::ControlLeft | KeyModifiers::ShiftLeft | KeyModifiers::AltLeft,
+        none
+    ));
+    assert!(!ctrl_shift.matches(
+        KeyModifiers::ControlLeft | KeyModifiers::ShiftLeft,
+        Modifiers::Fn
+    ));
+
+    let fn_key = ModifierPattern::either(Modifiers::Fn);
+    assert!(fn_key.matches(KeyModifiers::empty(), Modifiers::Fn));
+    // Only the modifiers without a key modifier bit are read from the modifiers
+    assert!(fn_key.matches(KeyModifiers::empty(), Modifiers::Fn | Modifiers::Control));
+    assert!(ModifierPattern::default().matches(KeyModifiers::empty(), none));
+}
+
+#[test]
+fn modifiers_match_specific_side() {
+    let none = Modifiers::empty();
+    let left_ctrl = ModifierPattern::sided(KeyModifiers::ControlLeft);
+    assert!(left_ctrl.matches(KeyModifiers::ControlLeft, none));
+    assert!(!left_ctrl.matches(KeyModifiers::ControlRight, none));
+    assert!(!left_ctrl.matches(KeyModifiers::ControlLeft | KeyModifiers::ControlRight, none));
+
+    // A side that is also in `either` needs that key, and allows the other one
+    let pattern = ModifierPattern {
+        either: Modifiers::Control,
+        sided: KeyModifiers::ControlLeft,
+    };
+    assert!(pattern.matches(KeyModifiers::ControlLeft | KeyModifiers::ControlRight, none));
+    assert!(!pattern.matches(KeyModifiers::ControlRight, none));
+    assert_eq!(pattern.modifiers(), Modifiers::Control);
+
+    let alt_gr = ModifierPattern::either(Modifiers::AltGr);
+    assert!(alt_gr.matches(KeyModifiers::AltRight, none));
+    assert!(!alt_gr.matches(KeyModifiers::AltLeft, none));
+    assert!(!alt_gr.matches(KeyModifiers::AltLeft | KeyModifiers::AltRight, none));
+    assert!(ModifierPattern::either(Modifiers::AltGr | Modifiers::Alt)
+        .matches(KeyModifiers::AltLeft | KeyModifiers::AltRight, none));
+}
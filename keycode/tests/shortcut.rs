@@ -0,0 +1,180 @@
+use keycode::{
+    label::LabelStyle,
+    modifiers::{ModifierPattern, Modifiers},
+    shortcut::{Chord, ParseShortcutError, Shortcut, ShortcutMatch, ShortcutMatcher},
+    KeyMap, KeyMappingError, KeyMappingId, KeyModifiers, KeyState, KeyboardState,
+};
+
+#[test]
+fn shortcut_parses_and_displays() {
+    let shortcut: Shortcut = "Ctrl+Shift+KeyK".parse().unwrap();
+    assert_eq!(
+        shortcut.chords.as_slice(),
+        &[Chord::new(
+            Modifiers::Control | Modifiers::Shift,
+            KeyMappingId::UsK
+        )]
+    );
+    assert_eq!(shortcut.to_string(), "Ctrl+Shift+KeyK");
+    assert_eq!("shift+CONTROL+k".parse::<Shortcut>().unwrap(), shortcut);
+
+    let shortcut: Shortcut = "Cmd+Option+Esc".parse().unwrap();
+    assert_eq!(
+        shortcut.chords[0],
+        Chord::new(Modifiers::Meta | Modifiers::Alt, KeyMappingId::Escape)
+    );
+    assert_eq!(shortcut.to_string(), "Alt+Meta+Escape");
+    assert_eq!(
+        shortcut.display(LabelStyle::Mac).to_string(),
+        "Option+Cmd+Escape"
+    );
+    assert_eq!("Option+Cmd+Escape".parse::<Shortcut>().unwrap(), shortcut);
+
+    let shortcut: Shortcut = "Ctrl+K  Ctrl+C".parse().unwrap();
+    assert_eq!(shortcut.chords.len(), 2);
+    assert_eq!(shortcut.to_string(), "Ctrl+KeyK Ctrl+KeyC");
+}
+
+#[test]
+fn shortcut_parses_platform_and_sided_modifiers() {
+    let save = |style| Shortcut::parse("CmdOrCtrl+S", style).unwrap().chords[0];
+    assert_eq!(save(LabelStyle::Pc).modifiers.either, Modifiers::Control);
+    assert_eq!(save(LabelStyle::Mac).modifiers.either, Modifiers::Meta);
+
+    let chord: Chord = "ControlLeft+Shift+Fn+F1".parse().unwrap();
+    assert_eq!(
+        chord.modifiers,
+        ModifierPattern {
+            either: Modifiers::Shift | Modifiers::Fn,
+            sided: KeyModifiers::ControlLeft,
+        }
+    );
+    assert_eq!(chord.to_string(), "Shift+Fn+ControlLeft+F1");
+    assert_eq!(chord.to_string().parse::<Chord>().unwrap(), chord);
+
+    let chord: Chord = "AltGr+Digit2".parse().unwrap();
+    assert_eq!(chord.modifiers.either, Modifiers::AltGr);
+    assert_eq!(chord.to_string(), "AltGr+Digit2");
+}
+
+#[test]
+fn shortcut_parse_errors() {
+    assert_eq!("".parse::<Shortcut>(), Err(ParseShortcutError::Empty));
+    assert_eq!("Ctrl+".parse::<Shortcut>(), Err(ParseShortcutError::Empty));
+    assert_eq!("Ctrl++".parse::<Shortcut>(), Err(ParseShortcutError::Empty));
+    assert!(matches!(
+        "Ctrl+Nope".parse::<Shortcut>(),
+        Err(ParseShortcutError::UnknownKey(
+            KeyMappingError::UnknownName(_)
+        ))
+    ));
+    assert_eq!(
+        "KeyK+Ctrl".parse::<Shortcut>(),
+        Err(ParseShortcutError::NotAModifier(KeyMappingId::UsK))
+    );
+    assert_eq!(
+        "A B C D E".parse::<Shortcut>(),
+        Err(ParseShortcutError::TooManyChords)
+    );
+    assert_eq!(
+        ParseShortcutError::NotAModifier(KeyMappingId::UsK).to_string(),
+        "key UsK is not a modifier"
+    );
+}
+
+#[test]
+fn shortcut_matches_keyboard_state() {
+    let shortcut: Shortcut = "Ctrl+Shift+K".parse().unwrap();
+    let mut keyboard_state = KeyboardState::new(None);
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::ControlRight), KeyState::Pressed);
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::UsK), KeyState::Pressed);
+    assert!(keyboard_state.is_pressed(KeyMappingId::ControlRight));
+    assert!(keyboard_state.is_pressed(KeyMappingId::UsK));
+    assert!(!keyboard_state.is_pressed(KeyMappingId::ControlLeft));
+    assert!(!shortcut.matches(&keyboard_state));
+
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::ShiftLeft), KeyState::Pressed);
+    assert!(shortcut.matches(&keyboard_state));
+
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::AltLeft), KeyState::Pressed);
+    assert!(!shortcut.matches(&keyboard_state));
+
+    // A modifier key can be the key of a chord
+    let shortcut: Shortcut = "Ctrl+AltLeft".parse().unwrap();
+    let mut keyboard_state = KeyboardState::new(None);
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::ControlLeft), KeyState::Pressed);
+    keyboard_state.update_key(KeyMap::from(KeyMappingId::AltLeft), KeyState::Pressed);
+    assert!(shortcut.matches(&keyboard_state));
+
+    // Sequences need a matcher
+    let shortcut: Shortcut = "Ctrl+K Ctrl+K".parse().unwrap();
+    assert!(!shortcut.matches(&keyboard_state));
+}
+
+#[test]
+fn shortcut_matcher_follows_chords() {
+    let mut matcher = ShortcutMatcher::new("Ctrl+K Ctrl+C".parse().unwrap());
+    let ctrl = KeyModifiers::ControlLeft;
+    let none = Modifiers::empty();
+
+    assert_eq!(
+        matcher.press(KeyMappingId::ControlLeft, ctrl, none),
+        ShortcutMatch::NoMatch
+    );
+    assert_eq!(
+        matcher.press(KeyMappingId::UsK, ctrl, none),
+        ShortcutMatch::Partial
+    );
+    assert_eq!(matcher.position(), 1);
+    // Releasing and pressing control again doesn't start over
+    assert_eq!(
+        matcher.press(KeyMappingId::ControlRight, KeyModifiers::ControlRight, none),
+        ShortcutMatch::Partial
+    );
+    assert_eq!(
+        matcher.press(KeyMappingId::UsC, KeyModifiers::ControlRight, none),
+        ShortcutMatch::Complete
+    );
+    assert_eq!(matcher.position(), 0);
+
+    // Another key starts over, and may start the shortcut again
+    matcher.press(KeyMappingId::UsK, ctrl, none);
+    assert_eq!(
+        matcher.press(KeyMappingId::UsC, KeyModifiers::empty(), none),
+        ShortcutMatch::NoMatch
+    );
+    matcher.press(KeyMappingId::UsK, ctrl, none);
+    assert_eq!(
+        matcher.press(KeyMappingId::UsK, ctrl, none),
+        ShortcutMatch::Partial
+    );
+    assert_eq!(
+        matcher.press(KeyMappingId::UsC, ctrl, none),
+        ShortcutMatch::Complete
+    );
+
+    matcher.press(KeyMappingId::UsK, ctrl, none);
+    matcher.reset();
+    assert_eq!(
+        matcher.press(KeyMappingId::UsC, ctrl, none),
+        ShortcutMatch::NoMatch
+    );
+}
+
+#[test]
+fn shortcut_matcher_follows_keyboard_state() {
+    let mut matcher = ShortcutMatcher::new("Fn+F1".parse().unwrap());
+    let mut keyboard_state = KeyboardState::new(None);
+    let events = [
+        (KeyMappingId::Fn, KeyState::Pressed, ShortcutMatch::NoMatch),
+        (KeyMappingId::F1, KeyState::Pressed, ShortcutMatch::Complete),
+        (KeyMappingId::F1, KeyState::Released, ShortcutMatch::NoMatch),
+        (KeyMappingId::Fn, KeyState::Released, ShortcutMatch::NoMatch),
+        (KeyMappingId::F1, KeyState::Pressed, ShortcutMatch::NoMatch),
+    ];
+    for (id, state, expected) in events {
+        let key = KeyMap::from(id);
+        keyboard_state.update_key(key, state);
+        assert_eq!(matcher.update(&keyboard_state, key, state), expected);
+    }
+}
@@ -0,0 +1,431 @@
+use keycode::{
+    terminal::{
+        TerminalDecodeError, TerminalEncoder, TerminalEvent, TerminalEvents, TerminalKeyEvent,
+    },
+    KeyMap, KeyMappingId, KeyModifiers,
+};
+
+fn encode(encoder: &TerminalEncoder, id: KeyMappingId, modifiers: KeyModifiers) -> Vec<u8> {
+    encoder
+        .encode(KeyMap::from(id), modifiers)
+        .map(|sequence| sequence.to_vec())
+        .unwrap_or_default()
+}
+
+#[test]
+fn terminal_encodes_printable_keys() {
+    let encoder = TerminalEncoder::new();
+
+    assert_eq!(
+        encode(&encoder, KeyMappingId::UsA, KeyModifiers::empty()),
+        b"a"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::UsA, KeyModifiers::ShiftRight),
+        b"A"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Digit2, KeyModifiers::ShiftLeft),
+        b"@"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::UsC, KeyModifiers::ControlLeft),
+        b"\x03"
+    );
+    assert_eq!(
+        encode(
+            &encoder,
+            KeyMappingId::BracketLeft,
+            KeyModifiers::ControlLeft
+        ),
+        b"\x1b"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Space, KeyModifiers::ControlLeft),
+        b"\x00"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::UsX, KeyModifiers::AltLeft),
+        b"\x1bx"
+    );
+    assert_eq!(
+        encode(
+            &encoder,
+            KeyMappingId::UsX,
+            KeyModifiers::AltLeft | KeyModifiers::ControlLeft
+        ),
+        b"\x1b\x18"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Enter, KeyModifiers::empty()),
+        b"\r"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Backspace, KeyModifiers::empty()),
+        b"\x7f"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Backspace, KeyModifiers::ControlLeft),
+        b"\x08"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::ShiftLeft, KeyModifiers::ShiftLeft),
+        b""
+    );
+}
+
+#[test]
+fn terminal_encodes_cursor_and_function_keys() {
+    let encoder = TerminalEncoder::new();
+
+    assert_eq!(
+        encode(&encoder, KeyMappingId::ArrowUp, KeyModifiers::empty()),
+        b"\x1b[A"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::ArrowLeft, KeyModifiers::ControlLeft),
+        b"\x1b[1;5D"
+    );
+    assert_eq!(
+        encode(
+            &encoder,
+            KeyMappingId::End,
+            KeyModifiers::ShiftLeft | KeyModifiers::AltRight
+        ),
+        b"\x1b[1;4F"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::F1, KeyModifiers::empty()),
+        b"\x1bOP"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::F4, KeyModifiers::ShiftLeft),
+        b"\x1b[1;2S"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::F5, KeyModifiers::empty()),
+        b"\x1b[15~"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::F12, KeyModifiers::MetaLeft),
+        b"\x1b[24;9~"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Del, KeyModifiers::empty()),
+        b"\x1b[3~"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Tab, KeyModifiers::ShiftLeft),
+        b"\x1b[Z"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::F24, KeyModifiers::empty()),
+        b""
+    );
+}
+
+#[test]
+fn terminal_encoder_modes_are_applied() {
+    let encoder = TerminalEncoder {
+        application_cursor_keys: true,
+        application_keypad: true,
+        alt_sends_escape: false,
+        backspace_sends_backspace: true,
+    };
+
+    assert_eq!(
+        encode(&encoder, KeyMappingId::ArrowUp, KeyModifiers::empty()),
+        b"\x1bOA"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::ArrowUp, KeyModifiers::ShiftLeft),
+        b"\x1b[1;2A"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Numpad5, KeyModifiers::empty()),
+        b"\x1bOu"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::NumpadEnter, KeyModifiers::empty()),
+        b"\x1bOM"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::UsX, KeyModifiers::AltLeft),
+        b"x"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Backspace, KeyModifiers::empty()),
+        b"\x08"
+    );
+
+    let encoder = TerminalEncoder::new();
+    assert_eq!(
+        encode(&encoder, KeyMappingId::Numpad5, KeyModifiers::empty()),
+        b"5"
+    );
+    assert_eq!(
+        encode(&encoder, KeyMappingId::NumpadEnter, KeyModifiers::empty()),
+        b"\r"
+    );
+}
+
+fn decode(
+    input: &[u8],
+) -> (
+    Option<KeyMappingId>,
+    KeyModifiers,
+    TerminalKeyEvent,
+    Option<char>,
+) {
+    let (event, len) = TerminalEvent::decode(input).unwrap();
+    assert_eq!(len, input.len());
+    (
+        event.key.map(|key| key.id),
+        event.modifiers,
+        event.event,
+        event.text,
+    )
+}
+
+#[test]
+fn terminal_decodes_legacy_input() {
+    use TerminalKeyEvent::Press;
+
+    assert_eq!(
+        decode(b"a"),
+        (
+            Some(KeyMappingId::UsA),
+            KeyModifiers::empty(),
+            Press,
+            Some('a')
+        )
+    );
+    assert_eq!(
+        decode(b"?"),
+        (
+            Some(KeyMappingId::Slash),
+            KeyModifiers::ShiftLeft,
+            Press,
+            Some('?')
+        )
+    );
+    assert_eq!(
+        decode(b"\x03"),
+        (
+            Some(KeyMappingId::UsC),
+            KeyModifiers::ControlLeft,
+            Press,
+            None
+        )
+    );
+    assert_eq!(
+        decode(b"\x1bx"),
+        (
+            Some(KeyMappingId::UsX),
+            KeyModifiers::AltLeft,
+            Press,
+            Some('x')
+        )
+    );
+    assert_eq!(
+        decode("\u{e9}".as_bytes()),
+        (None, KeyModifiers::empty(), Press, Some('\u{e9}'))
+    );
+    assert_eq!(
+        decode(b"\x1b[1;5D"),
+        (
+            Some(KeyMappingId::ArrowLeft),
+            KeyModifiers::ControlLeft,
+            Press,
+            None
+        )
+    );
+    assert_eq!(
+        decode(b"\x1bOA"),
+        (
+            Some(KeyMappingId::ArrowUp),
+            KeyModifiers::empty(),
+            Press,
+            None
+        )
+    );
+    assert_eq!(
+        decode(b"\x1b[24;9~"),
+        (Some(KeyMappingId::F12), KeyModifiers::MetaLeft, Press, None)
+    );
+    assert_eq!(
+        decode(b"\x1b[11~"),
+        (Some(KeyMappingId::F1), KeyModifiers::empty(), Press, None)
+    );
+    assert_eq!(
+        decode(b"\x1b[14;2~"),
+        (Some(KeyMappingId::F4), KeyModifiers::ShiftLeft, Press, None)
+    );
+    assert_eq!(
+        decode(b"\x1b[Z"),
+        (
+            Some(KeyMappingId::Tab),
+            KeyModifiers::ShiftLeft,
+            Press,
+            None
+        )
+    );
+    assert_eq!(
+        decode(b"\x1bOu"),
+        (
+            Some(KeyMappingId::Numpad5),
+            KeyModifiers::empty(),
+            Press,
+            None
+        )
+    );
+}
+
+#[test]
+fn terminal_decodes_kitty_keyboard_protocol() {
+    assert_eq!(
+        decode(b"\x1b[97;5u"),
+        (
+            Some(KeyMappingId::UsA),
+            KeyModifiers::ControlLeft,
+            TerminalKeyEvent::Press,
+            Some('a')
+        )
+    );
+    assert_eq!(
+        decode(b"\x1b[97:65;2u"),
+        (
+            Some(KeyMappingId::UsA),
+            KeyModifiers::ShiftLeft,
+            TerminalKeyEvent::Press,
+            Some('A')
+        )
+    );
+    assert_eq!(
+        decode(b"\x1b[97;1:2;97u"),
+        (
+            Some(KeyMappingId::UsA),
+            KeyModifiers::empty(),
+            TerminalKeyEvent::Repeat,
+            Some('a')
+        )
+    );
+    assert_eq!(
+        decode(b"\x1b[27;1:3u"),
+        (
+            Some(KeyMappingId::Escape),
+            KeyModifiers::empty(),
+            TerminalKeyEvent::Release,
+            None
+        )
+    );
+    assert_eq!(
+        decode(b"\x1b[57441;2u"),
+        (
+            Some(KeyMappingId::ShiftLeft),
+            KeyModifiers::ShiftLeft,
+            TerminalKeyEvent::Press,
+            None
+        )
+    );
+    assert_eq!(
+        decode(b"\x1b[1;3:3A"),
+        (
+            Some(KeyMappingId::ArrowUp),
+            KeyModifiers::AltLeft,
+            TerminalKeyEvent::Release,
+            None
+        )
+    );
+    // F3 can't be sent as `CSI R`, which is a cursor position report
+    assert_eq!(
+        decode(b"\x1b[13;1:3~"),
+        (
+            Some(KeyMappingId::F3),
+            KeyModifiers::empty(),
+            TerminalKeyEvent::Release,
+            None
+        )
+    );
+    assert_eq!(
+        decode(b"\x1b[233u"),
+        (
+            None,
+            KeyModifiers::empty(),
+            TerminalKeyEvent::Press,
+            Some('\u{e9}')
+        )
+    );
+}
+
+#[test]
+fn terminal_decoding_reports_incomplete_and_unrecognized_input() {
+    assert_eq!(
+        TerminalEvent::decode(b"\x1b"),
+        Err(TerminalDecodeError::Incomplete)
+    );
+    assert_eq!(
+        TerminalEvent::decode(b"\x1b[1;5"),
+        Err(TerminalDecodeError::Incomplete)
+    );
+    assert_eq!(
+        TerminalEvent::decode(&"\u{e9}".as_bytes()[..1]),
+        Err(TerminalDecodeError::Incomplete)
+    );
+    // Mouse report
+    assert_eq!(
+        TerminalEvent::decode(b"\x1b[<0;10;20M"),
+        Err(TerminalDecodeError::Unrecognized(11))
+    );
+    // X10 mouse report, whose three bytes aren't decoded as keys
+    assert_eq!(
+        TerminalEvent::decode(b"\x1b[M #!"),
+        Err(TerminalDecodeError::Unrecognized(6))
+    );
+    assert_eq!(
+        TerminalEvent::decode(b"\x1b[M #"),
+        Err(TerminalDecodeError::Incomplete)
+    );
+
+    let mut events = TerminalEvents::new(b"a\x1b[<0;10;20M\x1b[M #!\x1b[A\x1b");
+    assert_eq!(events.next().unwrap().text, Some('a'));
+    assert_eq!(
+        events.next().unwrap().key,
+        Some(KeyMap::from(KeyMappingId::ArrowUp))
+    );
+    assert_eq!(
+        events.next().unwrap().key,
+        Some(KeyMap::from(KeyMappingId::Escape))
+    );
+    assert_eq!(events.next(), None);
+
+    let mut events = TerminalEvents::new(b"b\x1b[1;");
+    assert_eq!(events.next().unwrap().text, Some('b'));
+    assert_eq!(events.next(), None);
+    assert_eq!(events.remaining(), b"\x1b[1;");
+}
+
+#[test]
+fn terminal_encoding_round_trips() {
+    let encoder = TerminalEncoder::new();
+    for id in [
+        KeyMappingId::UsQ,
+        KeyMappingId::Digit7,
+        KeyMappingId::ArrowDown,
+        KeyMappingId::F3,
+        KeyMappingId::F9,
+        KeyMappingId::PageUp,
+    ] {
+        for modifiers in [
+            KeyModifiers::empty(),
+            KeyModifiers::AltLeft,
+            KeyModifiers::ShiftLeft | KeyModifiers::AltLeft,
+        ] {
+            let sequence = encoder.encode(KeyMap::from(id), modifiers).unwrap();
+            let (event, len) = TerminalEvent::decode(&sequence).unwrap();
+            assert_eq!(len, sequence.len());
+            assert_eq!(event.key, Some(KeyMap::from(id)));
+            assert_eq!(event.modifiers, modifiers);
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use keycode::{
-    KeyMap, KeyMapping, KeyMappingCode, KeyMappingId, KeyModifiers, KeyState, KeyboardState,
+    EvdevCode, KeyMap, KeyMapping, KeyMappingCode, KeyMappingError, KeyMappingId, KeyModifiers,
+    KeyState, KeyboardState, MacKeyCode, WinScanCode,
 };
-use std::str::FromStr;
+use std::{convert::TryFrom, str::FromStr};
 
 #[test]
 fn can_get_a_key_map() {
@@ -23,6 +24,43 @@ fn can_get_a_key_map_from_code_str() {
     assert_eq!(key_map.usb, a.usb)
 }
 
+#[test]
+fn unknown_mappings_return_an_error() {
+    assert_eq!(
+        KeyMap::from_usb_code(0x0007, 0x00ff),
+        Err(KeyMappingError::UnknownUsb {
+            page: Some(0x0007),
+            code: 0x00ff
+        })
+    );
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Usb(0x0fff)),
+        Err(KeyMappingError::UnknownUsb {
+            page: None,
+            code: 0x0fff
+        })
+    );
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Evdev(0x0fff)),
+        Err(KeyMappingError::UnknownEvdev(0x0fff))
+    );
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Mac(0x0fff)),
+        Err(KeyMappingError::UnknownMac(0x0fff))
+    );
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Code(None)),
+        Err(KeyMappingError::Ambiguous(KeyMapping::Code(None)))
+    );
+
+    let error = KeyMappingCode::from_str("KeyAbc").unwrap_err();
+    assert_eq!(error.to_string(), "unknown W3 browser event code `KeyAbc`");
+    assert_eq!(
+        KeyMappingError::UnknownWin(0xe0ff).to_string(),
+        "unknown Windows value 0xe0ff"
+    );
+}
+
 macro_rules! check_modifiers {
     [$($modifier:ident,)*] => {
         $(
@@ -125,3 +163,199 @@ fn keyboard_state_works_for_usb_input_report() {
         ]
     );
 }
+
+#[test]
+fn shared_platform_values_prefer_one_key() {
+    // Windows uses 0x76 for both F24 and Lang5
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Win(0x76)).map(|key| key.id),
+        Ok(KeyMappingId::F24)
+    );
+    assert_eq!(
+        KeyMap::candidates(KeyMapping::Win(0x76)),
+        &[KeyMappingId::F24, KeyMappingId::Lang5]
+    );
+    // Mac uses 0x72 for both Insert and Help
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Mac(0x72)).map(|key| key.id),
+        Ok(KeyMappingId::Insert)
+    );
+    assert_eq!(
+        KeyMap::candidates(KeyMapping::Mac(0x72)),
+        &[KeyMappingId::Insert, KeyMappingId::Help]
+    );
+    // USB codes without a page prefer the keyboard page
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Usb(0x10)).map(|key| key.id),
+        Ok(KeyMappingId::UsM)
+    );
+    assert_eq!(
+        KeyMap::candidates(KeyMapping::Usb(0x10))[0],
+        KeyMappingId::UsM
+    );
+    assert!(KeyMap::candidates(KeyMapping::Usb(0x10)).contains(&KeyMappingId::Hyper));
+
+    assert_eq!(
+        KeyMap::candidates(KeyMapping::Id(KeyMappingId::UsA)),
+        &[KeyMappingId::UsA]
+    );
+    assert_eq!(
+        KeyMap::candidates(KeyMapping::Code(None))[0],
+        KeyMappingId::None
+    );
+}
+
+#[test]
+fn missing_platform_values_are_unknown() {
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Evdev(0)),
+        Err(KeyMappingError::UnknownEvdev(0))
+    );
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Xkb(0)),
+        Err(KeyMappingError::UnknownXkb(0))
+    );
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Win(0)),
+        Err(KeyMappingError::UnknownWin(0))
+    );
+    assert_eq!(
+        KeyMap::from_key_mapping(KeyMapping::Mac(0xffff)),
+        Err(KeyMappingError::UnknownMac(0xffff))
+    );
+    assert!(KeyMap::candidates(KeyMapping::Mac(0xffff)).is_empty());
+}
+
+#[test]
+fn all_keys_can_be_iterated() {
+    assert_eq!(KeyMap::ALL.len(), KeyMappingId::ALL.len());
+    assert_eq!(KeyMap::iter().count(), KeyMap::ALL.len());
+    assert_eq!(KeyMap::ALL[0].id, KeyMappingId::None);
+
+    for (key_map, id) in KeyMap::ALL.iter().zip(KeyMappingId::ALL) {
+        assert_eq!(key_map.id, *id);
+        assert_eq!(KeyMap::from(*id), *key_map);
+        assert_eq!(
+            KeyMap::from_usb_code(key_map.usb_page(), key_map.usb),
+            Ok(*key_map)
+        );
+    }
+
+    for code in KeyMappingCode::ALL {
+        let key_map = KeyMap::from(*code);
+        assert_eq!(key_map.code, Some(*code));
+        assert_eq!(code.to_string().parse::<KeyMappingCode>(), Ok(*code));
+    }
+    assert_eq!(KeyMap::with_code().count(), KeyMappingCode::ALL.len());
+
+    for key_map in KeyMap::with_evdev() {
+        assert_eq!(
+            KeyMap::from_key_mapping(KeyMapping::Evdev(key_map.evdev)).map(|key_map| key_map.evdev),
+            Ok(key_map.evdev)
+        );
+    }
+    assert!(KeyMap::with_mac().all(|key_map| key_map.mac != 0xffff));
+
+    assert!(KeyMap::in_usb_page(0x07).any(|key_map| key_map.id == KeyMappingId::UsA));
+    assert!(KeyMap::in_usb_page(0x07).all(|key_map| key_map.usb_page() == 0x07));
+    assert!(!KeyMap::in_usb_page(0x07).any(|key_map| key_map.id == KeyMappingId::Hyper));
+
+    let modifiers: Vec<KeyMappingId> = KeyMap::modifiers().map(|key_map| key_map.id).collect();
+    assert_eq!(modifiers.len(), 8);
+    assert!(modifiers.contains(&KeyMappingId::ShiftLeft));
+}
+
+#[test]
+fn missing_platform_values_are_none() {
+    let a = KeyMap::from(KeyMappingId::UsA);
+    assert_eq!(a.evdev_code().map(EvdevCode::get), Some(30));
+    assert_eq!(a.xkb_code().map(u16::from), Some(38));
+    assert_eq!(a.win_code(), WinScanCode::new(0x1e));
+    assert_eq!(a.mac_code(), MacKeyCode::new(0x00));
+
+    let hyper = KeyMap::from(KeyMappingId::Hyper);
+    assert_eq!(hyper.evdev_code(), None);
+    assert_eq!(hyper.xkb_code(), None);
+    assert_eq!(hyper.win_code(), None);
+    assert_eq!(hyper.mac_code(), None);
+
+    assert_eq!(EvdevCode::new(0), None);
+    assert_eq!(MacKeyCode::new(0xffff), None);
+    assert_eq!(
+        KeyMap::try_from(KeyMapping::from(a.evdev_code().unwrap())),
+        Ok(a)
+    );
+}
+
+#[test]
+fn key_mapping_ids_can_be_parsed() {
+    for id in KeyMappingId::ALL {
+        assert_eq!(id.to_string().parse::<KeyMappingId>(), Ok(*id));
+    }
+    for code in KeyMappingCode::ALL {
+        assert_eq!(
+            code.to_string().parse::<KeyMappingId>().map(KeyMap::from),
+            Ok(KeyMap::from(*code))
+        );
+    }
+
+    assert_eq!("UsA".parse(), Ok(KeyMappingId::UsA));
+    assert_eq!("usa".parse(), Ok(KeyMappingId::UsA));
+    assert_eq!("US_A".parse(), Ok(KeyMappingId::UsA));
+    assert_eq!("KeyA".parse(), Ok(KeyMappingId::UsA));
+    assert_eq!("A".parse(), Ok(KeyMappingId::UsA));
+    assert_eq!("intl_backslash".parse(), Ok(KeyMappingId::IntlBackslash));
+    assert_eq!("Delete".parse(), Ok(KeyMappingId::Del));
+    assert_eq!("ESC".parse(), Ok(KeyMappingId::Escape));
+    assert_eq!("ctrl".parse(), Ok(KeyMappingId::ControlLeft));
+    assert_eq!("AltGr".parse(), Ok(KeyMappingId::AltRight));
+    assert_eq!("1".parse(), Ok(KeyMappingId::Digit1));
+    assert_eq!("super".parse(), Ok(KeyMappingId::Super));
+
+    let err = "nope".parse::<KeyMappingId>().unwrap_err();
+    assert_eq!(err.to_string(), "unknown key name `nope`");
+}
+
+#[test]
+fn lookups_can_be_const() {
+    const A: KeyMap = KeyMap::from_id_const(KeyMappingId::UsA);
+    const ESCAPE: KeyMap = KeyMap::from_usb_const(0x07, 0x29);
+    const ROW: [KeyMap; 3] = [
+        KeyMap::from_usb_const(0x07, 0x14),
+        KeyMap::from_usb_const(0x07, 0x1a),
+        KeyMap::from_usb_const(0x07, 0x08),
+    ];
+    const F24: Option<KeyMappingId> = KeyMap::win_to_id(0x76);
+
+    assert_eq!(A, KeyMap::from(KeyMappingId::UsA));
+    assert_eq!(ESCAPE.id, KeyMappingId::Escape);
+    assert_eq!(
+        ROW.map(|key_map| key_map.id),
+        [KeyMappingId::UsQ, KeyMappingId::UsW, KeyMappingId::UsE]
+    );
+    assert_eq!(F24, Some(KeyMappingId::F24));
+    assert_eq!(KeyMap::usb_to_id(0x07, 0xffff), None);
+    assert_eq!(KeyMap::evdev_to_id(0), None);
+    assert_eq!(KeyMap::mac_to_id(0xffff), None);
+    assert_eq!(KeyMap::code_to_id(KeyMappingCode::KeyA), KeyMappingId::UsA);
+
+    for key_map in KeyMap::ALL {
+        let lookups = [
+            (
+                KeyMapping::Evdev(key_map.evdev),
+                KeyMap::evdev_to_id(key_map.evdev),
+            ),
+            (KeyMapping::Xkb(key_map.xkb), KeyMap::xkb_to_id(key_map.xkb)),
+            (KeyMapping::Win(key_map.win), KeyMap::win_to_id(key_map.win)),
+            (KeyMapping::Mac(key_map.mac), KeyMap::mac_to_id(key_map.mac)),
+        ];
+        for (key_mapping, id) in lookups {
+            assert_eq!(
+                KeyMap::from_key_mapping(key_mapping)
+                    .ok()
+                    .map(|key_map| key_map.id),
+                id
+            );
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use keycode::{KeyMap, KeyMapping, KeyMappingCode, KeyMappingId};
+
+fn lookups(c: &mut Criterion) {
+    c.bench_function("from_usb_code", |b| {
+        b.iter(|| {
+            for key_map in KeyMap::ALL {
+                let _ = black_box(KeyMap::from_usb_code(
+                    black_box(key_map.usb_page()),
+                    black_box(key_map.usb),
+                ));
+            }
+        })
+    });
+
+    c.bench_function("from_key_mapping evdev", |b| {
+        b.iter(|| {
+            for key_map in KeyMap::ALL {
+                let _ = black_box(KeyMap::from_key_mapping(KeyMapping::Evdev(black_box(
+                    key_map.evdev,
+                ))));
+            }
+        })
+    });
+
+    c.bench_function("from_key_mapping mac", |b| {
+        b.iter(|| {
+            for key_map in KeyMap::ALL {
+                let _ = black_box(KeyMap::from_key_mapping(KeyMapping::Mac(black_box(
+                    key_map.mac,
+                ))));
+            }
+        })
+    });
+
+    c.bench_function("from_key_mapping code", |b| {
+        b.iter(|| {
+            for code in KeyMappingCode::ALL {
+                let _ = black_box(KeyMap::from_key_mapping(KeyMapping::Code(Some(black_box(
+                    *code,
+                )))));
+            }
+        })
+    });
+
+    c.bench_function("from id", |b| {
+        b.iter(|| {
+            for id in KeyMappingId::ALL {
+                black_box(KeyMap::from(black_box(*id)));
+            }
+        })
+    });
+
+    c.bench_function("code from_str", |b| {
+        b.iter(|| {
+            for name in ["KeyA", "Escape", "NumpadEnter", "MetaRight", "Unknown"] {
+                let _ = black_box(black_box(name).parse::<KeyMappingCode>());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, lookups);
+criterion_main!(benches);
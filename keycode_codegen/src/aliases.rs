@@ -0,0 +1,82 @@
+/// Common names for keys accepted when parsing a `KeyMappingId`, after the id, the name in the
+/// source table and the W3 browser event code
+///
+/// Names that already belong to another key, like `super` or `menu`, are left out.
+pub const KEY_ALIASES: &[(&str, &str)] = &[
+    ("a", "UsA"),
+    ("b", "UsB"),
+    ("c", "UsC"),
+    ("d", "UsD"),
+    ("e", "UsE"),
+    ("f", "UsF"),
+    ("g", "UsG"),
+    ("h", "UsH"),
+    ("i", "UsI"),
+    ("j", "UsJ"),
+    ("k", "UsK"),
+    ("l", "UsL"),
+    ("m", "UsM"),
+    ("n", "UsN"),
+    ("o", "UsO"),
+    ("p", "UsP"),
+    ("q", "UsQ"),
+    ("r", "UsR"),
+    ("s", "UsS"),
+    ("t", "UsT"),
+    ("u", "UsU"),
+    ("v", "UsV"),
+    ("w", "UsW"),
+    ("x", "UsX"),
+    ("y", "UsY"),
+    ("z", "UsZ"),
+    ("1", "Digit1"),
+    ("2", "Digit2"),
+    ("3", "Digit3"),
+    ("4", "Digit4"),
+    ("5", "Digit5"),
+    ("6", "Digit6"),
+    ("7", "Digit7"),
+    ("8", "Digit8"),
+    ("9", "Digit9"),
+    ("0", "Digit0"),
+    ("-", "Minus"),
+    ("=", "Equal"),
+    ("[", "BracketLeft"),
+    ("]", "BracketRight"),
+    ("\\", "Backslash"),
+    (";", "Semicolon"),
+    ("'", "Quote"),
+    ("`", "Backquote"),
+    (",", "Comma"),
+    (".", "Period"),
+    ("/", "Slash"),
+    ("esc", "Escape"),
+    ("bksp", "Backspace"),
+    ("caps", "CapsLock"),
+    ("ins", "Insert"),
+    ("pgup", "PageUp"),
+    ("pgdn", "PageDown"),
+    ("up", "ArrowUp"),
+    ("down", "ArrowDown"),
+    ("left", "ArrowLeft"),
+    ("right", "ArrowRight"),
+    ("prtsc", "PrintScreen"),
+    ("ctrl", "ControlLeft"),
+    ("control", "ControlLeft"),
+    ("lctrl", "ControlLeft"),
+    ("rctrl", "ControlRight"),
+    ("shift", "ShiftLeft"),
+    ("lshift", "ShiftLeft"),
+    ("rshift", "ShiftRight"),
+    ("alt", "AltLeft"),
+    ("option", "AltLeft"),
+    ("lalt", "AltLeft"),
+    ("ralt", "AltRight"),
+    ("altgr", "AltRight"),
+    ("meta", "MetaLeft"),
+    ("cmd", "MetaLeft"),
+    ("command", "MetaLeft"),
+    ("win", "MetaLeft"),
+    ("lmeta", "MetaLeft"),
+    ("rmeta", "MetaRight"),
+];
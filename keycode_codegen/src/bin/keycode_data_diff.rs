@@ -0,0 +1,38 @@
+//! Compare a new copy of Chromium's `keycode_converter_data.inc` with the bundled one, or with
+//! another table, and print the keys that change and the semver impact on the generated code
+//!
+//! ```bash
+//! cargo run -p keycode_codegen --bin keycode_data_diff -- NEW.inc [OLD.inc]
+//! ```
+
+use std::{env, fs, process};
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|err| {
+        eprintln!("Could not read `{}`: {}", path, err);
+        process::exit(2);
+    })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (new, old) = match args.as_slice() {
+        [new] => (
+            read(new),
+            keycode_codegen::KEYCODE_CONVERTER_DATA.to_string(),
+        ),
+        [new, old] => (read(new), read(old)),
+        _ => {
+            eprintln!("Usage: keycode_data_diff NEW.inc [OLD.inc]");
+            process::exit(2);
+        }
+    };
+
+    match keycode_codegen::diff_tables(&old, &new) {
+        Ok(diff) => print!("{}", diff),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
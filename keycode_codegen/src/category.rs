@@ -0,0 +1,137 @@
+use crate::key_map::KeyMap;
+
+/// USB HID usage pages that keys are categorized by
+const GENERIC_DESKTOP_PAGE: u16 = 0x01;
+const KEYBOARD_PAGE: u16 = 0x07;
+const CONSUMER_PAGE: u16 = 0x0c;
+
+/// A category of keys: the name of its flag, the name of its `is_*` method and its description
+pub struct Category {
+    pub flag: &'static str,
+    pub method: &'static str,
+    pub doc: &'static str,
+    belongs: fn(u16, u16, &str) -> bool,
+}
+
+/// Categories of keys, in the order of their bits
+///
+/// Each takes the USB HID usage page and value of a key and its name: its W3 browser event code,
+/// or its variant for keys without one. Keys can be in several categories, like `NumLock`.
+pub const CATEGORIES: &[Category] = &[
+    Category {
+        flag: "Letter",
+        method: "is_letter",
+        doc: "Letter keys, `KeyA` to `KeyZ`",
+        belongs: |page, usb, _| page == KEYBOARD_PAGE && (0x04..=0x1d).contains(&usb),
+    },
+    Category {
+        flag: "Digit",
+        method: "is_digit",
+        doc: "Digit keys above the letters, `Digit1` to `Digit0`",
+        belongs: |page, usb, _| page == KEYBOARD_PAGE && (0x1e..=0x27).contains(&usb),
+    },
+    Category {
+        flag: "Symbol",
+        method: "is_symbol",
+        doc: "Punctuation and symbol keys of the main block, like `Minus` or `IntlBackslash`",
+        belongs: |page, usb, _| {
+            page == KEYBOARD_PAGE
+                && ((0x2d..=0x38).contains(&usb) || matches!(usb, 0x64 | 0x87 | 0x89))
+        },
+    },
+    Category {
+        flag: "Editing",
+        method: "is_editing",
+        doc: "Whitespace and editing keys, like `Space`, `Backspace`, `Delete` or `Copy`",
+        belongs: |page, usb, name| {
+            (page == KEYBOARD_PAGE
+                && (matches!(usb, 0x28 | 0x2a..=0x2c | 0x49 | 0x4c)
+                    || (0x79..=0x7e).contains(&usb)))
+                || name == "Redo"
+        },
+    },
+    Category {
+        flag: "Function",
+        method: "is_function",
+        doc: "Function keys, `F1` to `F24`",
+        belongs: |page, usb, _| {
+            page == KEYBOARD_PAGE && ((0x3a..=0x45).contains(&usb) || (0x68..=0x73).contains(&usb))
+        },
+    },
+    Category {
+        flag: "Navigation",
+        method: "is_navigation",
+        doc: "Arrow keys and `Home`, `End`, `PageUp` and `PageDown`",
+        belongs: |page, usb, _| page == KEYBOARD_PAGE && matches!(usb, 0x4a | 0x4b | 0x4d..=0x52),
+    },
+    Category {
+        flag: "Numpad",
+        method: "is_numpad",
+        doc: "Keys of the number pad, including `NumLock`",
+        belongs: |_, _, name| name.starts_with("Numpad") || name == "NumLock",
+    },
+    Category {
+        flag: "Modifier",
+        method: "is_modifier",
+        doc: "Modifier keys, like `ShiftLeft`, and `Fn`, `Hyper` and `Super`",
+        belongs: |page, usb, name| {
+            (page == KEYBOARD_PAGE && (0xe0..=0xe7).contains(&usb))
+                || matches!(name, "Fn" | "Hyper" | "Super")
+        },
+    },
+    Category {
+        flag: "Lock",
+        method: "is_lock",
+        doc: "Keys that toggle a lock, like `CapsLock`",
+        belongs: |_, _, name| name.ends_with("Lock"),
+    },
+    Category {
+        flag: "Media",
+        method: "is_media",
+        doc: "Volume, channel and playback keys, like `AudioVolumeUp` or `MediaPlayPause`",
+        belongs: |page, usb, name| {
+            (page == KEYBOARD_PAGE && (0x7f..=0x81).contains(&usb))
+                || (page == CONSUMER_PAGE
+                    && ((0xb0..=0xce).contains(&usb)
+                        || (0xe0..=0xef).contains(&usb)
+                        || (0x150..=0x155).contains(&usb)))
+                || name.starts_with("Media")
+                || name.starts_with("Channel")
+        },
+    },
+    Category {
+        flag: "Application",
+        method: "is_application",
+        doc: "Keys that launch or control an application, like `LaunchMail` or `BrowserBack`",
+        belongs: |page, usb, _| page == CONSUMER_PAGE && (0x180..=0x2ff).contains(&usb),
+    },
+    Category {
+        flag: "System",
+        method: "is_system",
+        doc: "Power, display and system keys, like `Sleep`, `BrightnessUp` or `PrintScreen`",
+        belongs: |page, usb, name| {
+            page == GENERIC_DESKTOP_PAGE
+                || (page == KEYBOARD_PAGE && matches!(usb, 0x46 | 0x48 | 0x66 | 0x9a))
+                || name.starts_with("Brightness")
+                || matches!(name, "Suspend" | "Resume" | "Turbo")
+        },
+    },
+    Category {
+        flag: "International",
+        method: "is_international",
+        doc: "Keys of international keyboards, like `IntlYen`, `Convert` or `Lang1`",
+        belongs: |page, usb, name| {
+            (page == KEYBOARD_PAGE && (0x87..=0x98).contains(&usb)) || name.starts_with("Intl")
+        },
+    },
+];
+
+/// Get the bits of the categories of a key
+pub fn category_bits(key_map: &KeyMap) -> u16 {
+    let name = key_map.dom_code.as_deref().unwrap_or(&key_map.variant);
+    CATEGORIES
+        .iter()
+        .enumerate()
+        .filter(|(_, category)| (category.belongs)(key_map.usb_page_code, key_map.usb_code, name))
+        .fold(0, |bits, (bit, _)| bits | 1 << bit)
+}
@@ -0,0 +1,283 @@
+use crate::{
+    key_map::KeyMap,
+    platform::{Platform, PLATFORMS},
+};
+use core::fmt;
+
+/// How the generated code changes between two tables, in semver terms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SemverImpact {
+    /// The generated code is the same
+    None,
+    /// Only values of keys change, so lookups can return other keys
+    Minor,
+    /// Variants of the generated enums or their discriminants change
+    Major,
+}
+
+impl fmt::Display for SemverImpact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let impact = match self {
+            SemverImpact::None => "none",
+            SemverImpact::Minor => "minor",
+            SemverImpact::Major => "major",
+        };
+        write!(f, "{}", impact)
+    }
+}
+
+/// Changed values of a key that is in both tables
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct KeyChange {
+    chromium_name: String,
+    changes: Vec<String>,
+}
+
+/// Semantic difference between two key tables
+///
+/// Keys are matched by their variant. A removed and an added key with the same USB HID usage are
+/// reported as a renamed key.
+#[derive(Debug, Clone, Default)]
+pub struct TableDiff {
+    added: Vec<KeyMap>,
+    removed: Vec<KeyMap>,
+    renamed: Vec<(KeyMap, KeyMap)>,
+    changed: Vec<KeyChange>,
+    added_codes: Vec<String>,
+    removed_codes: Vec<String>,
+    /// Variants of `KeyMappingId` with a different discriminant
+    moved_ids: Vec<(String, usize, usize)>,
+    /// Variants of `KeyMappingCode` with a different discriminant
+    moved_codes: Vec<(String, usize, usize)>,
+}
+
+fn usb_usage(key_map: &KeyMap) -> (u16, u16) {
+    (key_map.usb_page_code, key_map.usb_code)
+}
+
+fn format_usb(key_map: &KeyMap) -> String {
+    format!("{:#06x}:{:#06x}", key_map.usb_page_code, key_map.usb_code)
+}
+
+fn format_code(code: &Option<String>) -> String {
+    match code {
+        Some(code) => format!("`{}`", code),
+        None => "no code".to_string(),
+    }
+}
+
+/// Get the W3 browser event codes of the keys, in the order of the generated `KeyMappingCode`
+fn codes(key_maps: &[KeyMap]) -> Vec<&str> {
+    key_maps
+        .iter()
+        .filter_map(|key_map| key_map.dom_code.as_deref())
+        .collect()
+}
+
+/// Find the items of `new` that are also in `old` at another position
+fn moved<'a>(old: impl Iterator<Item = &'a str>, new: &[&'a str]) -> Vec<(String, usize, usize)> {
+    old.enumerate()
+        .filter_map(|(old_index, name)| {
+            let new_index = new.iter().position(|new_name| *new_name == name)?;
+            if new_index == old_index {
+                return None;
+            }
+            Some((name.to_string(), old_index, new_index))
+        })
+        .collect()
+}
+
+impl TableDiff {
+    /// Compare the keys of an old and a new table
+    pub(crate) fn new(old: &[KeyMap], new: &[KeyMap]) -> TableDiff {
+        let mut diff = TableDiff::default();
+
+        let mut added: Vec<&KeyMap> = new
+            .iter()
+            .filter(|key_map| !old.iter().any(|old| old.variant == key_map.variant))
+            .collect();
+        for key_map in old {
+            let Some(new_key_map) = new.iter().find(|new| new.variant == key_map.variant) else {
+                match added
+                    .iter()
+                    .position(|added| usb_usage(added) == usb_usage(key_map))
+                {
+                    Some(index) => diff
+                        .renamed
+                        .push((key_map.clone(), added.remove(index).clone())),
+                    None => diff.removed.push(key_map.clone()),
+                }
+                continue;
+            };
+
+            let mut changes = vec![];
+            if usb_usage(key_map) != usb_usage(new_key_map) {
+                changes.push(format!(
+                    "USB HID {} -> {}",
+                    format_usb(key_map),
+                    format_usb(new_key_map)
+                ));
+            }
+            for platform in PLATFORMS.iter().filter(|p| **p != Platform::Usb) {
+                let (old_value, new_value) =
+                    (platform.raw_value(key_map), platform.raw_value(new_key_map));
+                if old_value != new_value {
+                    changes.push(format!(
+                        "{} {:#06x} -> {:#06x}",
+                        platform.short_name(),
+                        old_value,
+                        new_value
+                    ));
+                }
+            }
+            if key_map.dom_code != new_key_map.dom_code {
+                changes.push(format!(
+                    "code {} -> {}",
+                    format_code(&key_map.dom_code),
+                    format_code(&new_key_map.dom_code)
+                ));
+            }
+            if !changes.is_empty() {
+                diff.changed.push(KeyChange {
+                    chromium_name: key_map.chromium_name.clone(),
+                    changes,
+                });
+            }
+        }
+        diff.added = added.into_iter().cloned().collect();
+
+        let (old_codes, new_codes) = (codes(old), codes(new));
+        diff.added_codes = new_codes
+            .iter()
+            .filter(|code| !old_codes.contains(code))
+            .map(|code| code.to_string())
+            .collect();
+        diff.removed_codes = old_codes
+            .iter()
+            .filter(|code| !new_codes.contains(code))
+            .map(|code| code.to_string())
+            .collect();
+        let new_variants: Vec<&str> = new.iter().map(|key_map| key_map.variant.as_str()).collect();
+        diff.moved_ids = moved(
+            old.iter().map(|key_map| key_map.variant.as_str()),
+            &new_variants,
+        );
+        diff.moved_codes = moved(old_codes.into_iter(), &new_codes);
+
+        diff
+    }
+
+    /// Get how the generated code changes, with the reasons
+    pub fn impact(&self) -> (SemverImpact, Vec<String>) {
+        let mut reasons = vec![];
+        let variants = self.added.len() + self.removed.len() + self.renamed.len();
+        if variants > 0 {
+            reasons.push(format!(
+                "`KeyMappingId` gains or loses {} variant{}",
+                variants,
+                if variants == 1 { "" } else { "s" }
+            ));
+        }
+        if !self.added_codes.is_empty() || !self.removed_codes.is_empty() {
+            reasons.push("`KeyMappingCode` gains or loses variants".to_string());
+        }
+        if !self.moved_ids.is_empty() || !self.moved_codes.is_empty() {
+            reasons.push("discriminants of existing variants change".to_string());
+        }
+        if !reasons.is_empty() {
+            return (SemverImpact::Major, reasons);
+        }
+
+        if !self.changed.is_empty() {
+            reasons.push("values of existing keys change".to_string());
+            return (SemverImpact::Minor, reasons);
+        }
+
+        (SemverImpact::None, reasons)
+    }
+}
+
+impl fmt::Display for TableDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.added.is_empty() {
+            writeln!(f, "Added keys:")?;
+            for key_map in &self.added {
+                writeln!(
+                    f,
+                    "  + {} (USB HID {}, {})",
+                    key_map.chromium_name,
+                    format_usb(key_map),
+                    format_code(&key_map.dom_code)
+                )?;
+            }
+        }
+        if !self.removed.is_empty() {
+            writeln!(f, "Removed keys:")?;
+            for key_map in &self.removed {
+                writeln!(
+                    f,
+                    "  - {} (USB HID {}, {})",
+                    key_map.chromium_name,
+                    format_usb(key_map),
+                    format_code(&key_map.dom_code)
+                )?;
+            }
+        }
+        if !self.renamed.is_empty() {
+            writeln!(f, "Renamed keys:")?;
+            for (old, new) in &self.renamed {
+                writeln!(
+                    f,
+                    "  ~ {} -> {} (USB HID {})",
+                    old.chromium_name,
+                    new.chromium_name,
+                    format_usb(new)
+                )?;
+            }
+        }
+        if !self.changed.is_empty() {
+            writeln!(f, "Changed keys:")?;
+            for change in &self.changed {
+                writeln!(
+                    f,
+                    "  {}: {}",
+                    change.chromium_name,
+                    change.changes.join(", ")
+                )?;
+            }
+        }
+        if !self.added_codes.is_empty() || !self.removed_codes.is_empty() {
+            writeln!(f, "KeyMappingCode:")?;
+            for code in &self.added_codes {
+                writeln!(f, "  + {}", code)?;
+            }
+            for code in &self.removed_codes {
+                writeln!(f, "  - {}", code)?;
+            }
+        }
+        for (name, moved) in [
+            ("KeyMappingId", &self.moved_ids),
+            ("KeyMappingCode", &self.moved_codes),
+        ] {
+            if let Some((variant, old, new)) = moved.first() {
+                writeln!(
+                    f,
+                    "{} discriminants: {} variant{} move, like {} {} -> {}",
+                    name,
+                    moved.len(),
+                    if moved.len() == 1 { "" } else { "s" },
+                    variant,
+                    old,
+                    new
+                )?;
+            }
+        }
+
+        let (impact, reasons) = self.impact();
+        if reasons.is_empty() {
+            writeln!(f, "Semver impact: {}", impact)
+        } else {
+            writeln!(f, "Semver impact: {} ({})", impact, reasons.join(", "))
+        }
+    }
+}
@@ -0,0 +1,843 @@
+use crate::{
+    aliases::KEY_ALIASES,
+    category::{category_bits, CATEGORIES},
+    key_map::{extra_columns, ExtraKind, ExtraValue, KeyMap},
+    options::Options,
+    platform::Platform,
+};
+use heck::ToShoutySnakeCase;
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
+
+/// Generate the code for the keys, in the order of the source table so that the output is the
+/// same for every build
+#[allow(clippy::cognitive_complexity)]
+pub fn generate(key_maps: Vec<KeyMap>, options: &Options) -> TokenStream {
+    // Platforms other than USB, which every key has
+    let platforms: Vec<Platform> = options
+        .platforms
+        .iter()
+        .copied()
+        .filter(|platform| *platform != Platform::Usb)
+        .collect();
+
+    let ids: Vec<_> = key_maps
+        .iter()
+        .map(|key_map| format_ident!("{}", key_map.variant))
+        .collect();
+    let codes: Vec<_> = key_maps
+        .iter()
+        .filter_map(|key_map| Some(format_ident!("{}", key_map.dom_code.as_ref()?)))
+        .collect();
+
+    // Each platform value only finds its preferred key, the other keys are only candidates
+    let key_map_refs: Vec<&KeyMap> = key_maps.iter().collect();
+    let candidates: Vec<(Platform, BTreeMap<u16, Vec<&KeyMap>>)> = options
+        .platforms
+        .iter()
+        .map(|platform| (*platform, platform.candidates(&key_map_refs)))
+        .collect();
+    let platform_tables = candidates.iter().map(|(platform, values)| {
+        let table = platform.table();
+        let doc = format!(
+            "Preferred key of every {}, in pages of 256 values",
+            platform.description()
+        );
+        let pages = index_pages(
+            values
+                .iter()
+                .map(|(value, keys)| (u32::from(*value), keys[0].index)),
+        );
+        quote! {
+            #[doc = #doc]
+            const #table: &[(u32, [KeyIndex; 256])] = &[#(#pages),*];
+        }
+    });
+    let (shared_patterns, shared_ids): (Vec<TokenStream>, Vec<Vec<_>>) = candidates
+        .iter()
+        .flat_map(|(platform, values)| {
+            values
+                .iter()
+                .filter(|(_, keys)| keys.len() > 1)
+                .map(move |(value, keys)| {
+                    (
+                        platform.key_mapping(quote! { #value }),
+                        keys.iter()
+                            .map(|key_map| format_ident!("{}", key_map.variant))
+                            .collect(),
+                    )
+                })
+        })
+        .unzip();
+    let usb_index = index_pages(key_maps.iter().map(|key_map| {
+        (
+            u32::from(key_map.usb_page_code) << 16 | u32::from(key_map.usb_code),
+            key_map.index,
+        )
+    }));
+    // Indexes are stored plus one, so that zero can mean no key
+    let key_index_type = if key_maps.len() < usize::from(u8::MAX) {
+        quote! { u8 }
+    } else {
+        quote! { u16 }
+    };
+    let code_to_ids = key_maps
+        .iter()
+        .filter(|key_map| key_map.dom_code.is_some())
+        .map(|key_map| format_ident!("{}", key_map.variant));
+    let null_code_ids = key_maps
+        .iter()
+        .filter(|key_map| key_map.dom_code.is_none())
+        .map(|key_map| format_ident!("{}", key_map.variant));
+    let modifier_variants = [
+        "ControlLeft",
+        "ShiftLeft",
+        "AltLeft",
+        "MetaLeft",
+        "ControlRight",
+        "ShiftRight",
+        "AltRight",
+        "MetaRight",
+    ];
+    let all_key_maps = key_maps.iter().map(|key_map| {
+        let usb_code = key_map.usb_code;
+        let platform_fields = platforms.iter().map(|platform| {
+            let field = format_ident!("{}", platform.field());
+            let value = platform.raw_value(key_map);
+            quote! { #field: #value, }
+        });
+        let id = format_ident!("{}", key_map.variant);
+        let code = match &key_map.dom_code {
+            Some(code) => {
+                let code = format_ident!("{}", code);
+                quote! { Some(KeyMappingCode::#code) }
+            }
+            None => quote! { None },
+        };
+        let modifier = if modifier_variants.contains(&key_map.variant.as_str()) {
+            quote! { Some(KeyModifiers::#id) }
+        } else {
+            quote! { None }
+        };
+        quote! {
+            KeyMap {
+                usb: #usb_code,
+                #(#platform_fields)*
+                code: #code,
+                id: KeyMappingId::#id,
+                modifier: #modifier,
+            }
+        }
+    });
+    let usb_page_matches = key_maps.iter().map(|key_map| key_map.usb_page_code);
+    let platform_codes = platforms.iter().filter_map(|platform| {
+        let description = platform.description();
+        let name = platform.code_type()?;
+        let field = format_ident!("{}", platform.field());
+        let accessor = format_ident!("{}_code", field);
+        // Only USB has no value for keys without one
+        let no_code = platform.no_code().unwrap_or_default();
+        let key_mapping = platform.key_mapping(quote! { code.0 });
+        let type_doc = format!(
+            "{} for a specific key, never {:#06x} which the source table uses for keys without one",
+            description, no_code
+        );
+        let new_doc = format!("Wrap a {}, or `None` for {:#06x}", description, no_code);
+        let accessor_doc = format!("{} of the key, or `None` if it has none", description);
+        let definition = quote! {
+            #[doc = #type_doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+            pub struct #name(u16);
+
+            impl #name {
+                #[doc = #new_doc]
+                pub const fn new(code: u16) -> Option<#name> {
+                    if code == #no_code {
+                        None
+                    } else {
+                        Some(#name(code))
+                    }
+                }
+
+                /// Get the raw value
+                pub const fn get(self) -> u16 {
+                    self.0
+                }
+            }
+
+            impl From<#name> for u16 {
+                fn from(code: #name) -> u16 {
+                    code.0
+                }
+            }
+
+            impl From<#name> for KeyMapping {
+                fn from(code: #name) -> KeyMapping {
+                    #key_mapping
+                }
+            }
+        };
+        let with_doc = format!("Iterate over the keys with a {}", description);
+        let with = format_ident!("with_{}", field);
+        let accessor = quote! {
+            #[doc = #accessor_doc]
+            pub const fn #accessor(&self) -> Option<#name> {
+                #name::new(self.#field)
+            }
+
+            #[doc = #with_doc]
+            pub fn #with() -> impl Iterator<Item = &'static KeyMap> {
+                KeyMap::ALL.iter().filter(|key_map| key_map.#accessor().is_some())
+            }
+        };
+        Some((definition, accessor))
+    });
+    let (platform_code_definitions, platform_code_accessors): (Vec<_>, Vec<_>) =
+        platform_codes.unzip();
+    // Extra columns of extension tables, in a table next to `KeyMap::ALL` so that `KeyMap` stays
+    // the same size
+    let extra_accessors = extra_columns(&key_maps)
+        .expect("extra columns are checked when parsing")
+        .into_iter()
+        .map(|(column, kind)| {
+            let accessor = format_ident!("{}", column);
+            let table = format_ident!("{}", column.to_shouty_snake_case());
+            let description = match column.as_str() {
+                "ps2_set2" => "PS/2 scan code set 2 value".to_string(),
+                "android" => "Android key code".to_string(),
+                "vk" => "Windows virtual-key code".to_string(),
+                "label" => "Label printed on the key".to_string(),
+                _ => format!("Value of the `{}` column of the extension tables", column),
+            };
+            let doc = format!("{} of the key, or `None` if it has none", description);
+            let value_type = match kind {
+                ExtraKind::Code => quote! { u16 },
+                ExtraKind::Text => quote! { &'static str },
+            };
+            let values = key_maps
+                .iter()
+                .map(|key_map| match key_map.extra.get(&column) {
+                    Some(ExtraValue::Code(code)) => quote! { Some(#code) },
+                    Some(ExtraValue::Text(text)) => quote! { Some(#text) },
+                    None => quote! { None },
+                });
+            quote! {
+                #[doc = #doc]
+                pub const fn #accessor(&self) -> Option<#value_type> {
+                    KeyMap::#table[self.id as usize]
+                }
+
+                const #table: &'static [Option<#value_type>] = &[#(#values),*];
+            }
+        });
+    // Earlier names win when the same name, ignoring case, belongs to several keys
+    let (id_names, id_name_ids): (Vec<String>, Vec<_>) = key_maps
+        .iter()
+        .map(|key_map| (key_map.variant.clone(), &key_map.variant))
+        .chain(
+            key_maps
+                .iter()
+                .map(|key_map| (key_map.chromium_name.clone(), &key_map.variant)),
+        )
+        .chain(
+            key_maps
+                .iter()
+                .filter_map(|key_map| Some((key_map.dom_code.clone()?, &key_map.variant))),
+        )
+        .chain(KEY_ALIASES.iter().filter_map(|(alias, variant)| {
+            let key_map = key_maps
+                .iter()
+                .find(|key_map| key_map.variant == *variant)?;
+            Some((alias.to_string(), &key_map.variant))
+        }))
+        .map(|(name, variant)| (name, format_ident!("{}", variant)))
+        .unzip();
+    let platform_to_ids = platforms.iter().map(|platform| {
+        let name = format_ident!("{}_to_id", platform.field());
+        let table = platform.table();
+        let doc = format!(
+            "Get the id of the key with a {}, usable in `const` contexts",
+            platform.description()
+        );
+        quote! {
+            #[doc = #doc]
+            pub const fn #name(code: u16) -> Option<KeyMappingId> {
+                find_id(#table, code as u32)
+            }
+        }
+    });
+    let platform_variants = platforms.iter().map(|platform| {
+        let variant = platform.variant();
+        let doc = format!("{} for a specific key", platform.description());
+        quote! {
+            #[doc = #doc]
+            #variant(u16),
+        }
+    });
+    let platform_fields = platforms.iter().map(|platform| {
+        let field = format_ident!("{}", platform.field());
+        let doc = format!("{} for a specific key", platform.description());
+        quote! {
+            #[doc = #doc]
+            pub #field: u16,
+        }
+    });
+    let platform_errors = platforms.iter().map(|platform| {
+        let variant = platform.error_variant();
+        let doc = format!("No key has this {}", platform.description());
+        quote! {
+            #[doc = #doc]
+            #variant(u16),
+        }
+    });
+    let platform_error_messages = platforms.iter().map(|platform| {
+        let variant = platform.error_variant();
+        let message = format!("unknown {} value {{:#06x}}", platform.short_name());
+        quote! {
+            KeyMappingError::#variant(code) => write!(f, #message, code),
+        }
+    });
+    let platform_lookups = platforms.iter().map(|platform| {
+        let variant = platform.variant();
+        let error = platform.error_variant();
+        let table = platform.table();
+        quote! {
+            KeyMapping::#variant(code) => find_index(#table, u32::from(code))
+                .ok_or(KeyMappingError::#error(code))?,
+        }
+    });
+    let no_code_doc = platforms
+        .iter()
+        .filter_map(|platform| {
+            Some(format!(
+                "{} `{:#06x}`",
+                platform.short_name(),
+                platform.no_code()?
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let from_key_mapping_doc = format!(
+        "Values that the source table uses for keys without a value on a platform ({}) never \
+         match a key. Values shared by several keys return the first key of \
+         [`KeyMap::candidates`].",
+        no_code_doc
+    );
+    let category_flags = CATEGORIES.iter().enumerate().map(|(bit, category)| {
+        let flag = format_ident!("{}", category.flag);
+        let doc = category.doc;
+        let bits = Literal::u16_unsuffixed(1 << bit);
+        quote! {
+            #[doc = #doc]
+            const #flag = #bits;
+        }
+    });
+    let category_methods = CATEGORIES.iter().map(|category| {
+        let method = format_ident!("{}", category.method);
+        let flag = format_ident!("{}", category.flag);
+        let doc = format!(
+            "Check whether the key is in [`KeyCategory::{}`]",
+            category.flag
+        );
+        quote! {
+            #[doc = #doc]
+            pub const fn #method(&self) -> bool {
+                self.category().contains(KeyCategory::#flag)
+            }
+        }
+    });
+    let id_category_methods = CATEGORIES.iter().map(|category| {
+        let method = format_ident!("{}", category.method);
+        let flag = format_ident!("{}", category.flag);
+        let doc = format!(
+            "Check whether the key is in [`KeyCategory::{}`]",
+            category.flag
+        );
+        quote! {
+            #[doc = #doc]
+            pub const fn #method(self) -> bool {
+                self.category().contains(KeyCategory::#flag)
+            }
+        }
+    });
+    let key_categories = key_maps.iter().map(|key_map| {
+        let bits = Literal::u16_unsuffixed(category_bits(key_map));
+        quote! { KeyCategory::from_bits_retain(#bits) }
+    });
+    let id_discriminants = key_maps.iter().map(|key_map| key_map.index as u16);
+    let code_discriminants = 0..codes.len() as u16;
+
+    quote! {
+        bitflags::bitflags! {
+            /// Bitmask for key modifiers based on the USB HID standard
+            ///
+            /// See the stardard here:
+            ///
+            /// <https://www.usb.org/sites/default/files/documents/hid1_11.pdf>
+            ///
+            /// Go to page 56, "8.3 Report Format for Array Items"
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+            pub struct KeyModifiers: u8 {
+                /// Control left key bitmask
+                const ControlLeft  = 0b0000_0001;
+                /// Shift left key bitmask
+                const ShiftLeft    = 0b0000_0010;
+                /// Alt left key bitmask
+                const AltLeft      = 0b0000_0100;
+                /// Meta left key bitmask
+                const MetaLeft     = 0b0000_1000;
+                /// Control right key bitmask
+                const ControlRight = 0b0001_0000;
+                /// Shift right key bitmask
+                const ShiftRight   = 0b0010_0000;
+                /// Alt right key bitmask
+                const AltRight     = 0b0100_0000; // 👎
+                /// Meta right key bitmask
+                const MetaRight    = 0b1000_0000;
+            }
+        }
+
+        bitflags::bitflags! {
+            /// Categories of keys, derived from their USB HID usages and W3 browser event codes
+            ///
+            /// A key can be in several categories, like `NumLock` which is a number pad key and a
+            /// lock key, or in none, like `Escape`.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+            pub struct KeyCategory: u16 {
+                #(#category_flags)*
+            }
+        }
+
+        /// The mapping of values between platforms for a specific key
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum KeyMapping {
+            /// USB HID value for a specific key
+            Usb(u16),
+            #(#platform_variants)*
+            /// W3 browser event code for a specific key
+            Code(Option<KeyMappingCode>),
+            /// Id for a specific key
+            Id(KeyMappingId),
+        }
+
+        /// Ergonomic access to a specific key's mapping of values
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct KeyMap {
+            /// USB HID value for a specific key
+            pub usb: u16,
+            #(#platform_fields)*
+            /// W3 browser event code for a specific key
+            pub code: Option<KeyMappingCode>,
+            /// Id for a specific key
+            pub id: KeyMappingId,
+            /// USB HID bitmask
+            pub modifier: Option<KeyModifiers>,
+        }
+
+        #(#platform_code_definitions)*
+
+        /// Longest W3 browser event code kept by [`KeyMappingError::UnknownCode`]
+        pub const MAX_CODE_ERROR_LEN: usize = 32;
+
+        /// Error returned when a value can't be converted into a specific key
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+        pub enum KeyMappingError {
+            /// No key has this USB HID value, in this USB HID usage page if one was given
+            UnknownUsb {
+                /// USB HID usage page that was looked up, if any
+                page: Option<u16>,
+                /// USB HID value that was looked up
+                code: u16,
+            },
+            #(#platform_errors)*
+            /// No key has this W3 browser event code, truncated to [`MAX_CODE_ERROR_LEN`] bytes
+            UnknownCode(arrayvec::ArrayString<MAX_CODE_ERROR_LEN>),
+            /// No key has this name, truncated to [`MAX_CODE_ERROR_LEN`] bytes
+            UnknownName(arrayvec::ArrayString<MAX_CODE_ERROR_LEN>),
+            /// More than one key has this value
+            Ambiguous(KeyMapping),
+        }
+
+        impl KeyMappingError {
+            fn truncate(value: &str) -> arrayvec::ArrayString<MAX_CODE_ERROR_LEN> {
+                let mut truncated = arrayvec::ArrayString::new();
+                for c in value.chars() {
+                    if truncated.try_push(c).is_err() {
+                        break;
+                    }
+                }
+                truncated
+            }
+
+            fn unknown_code(code: &str) -> KeyMappingError {
+                KeyMappingError::UnknownCode(KeyMappingError::truncate(code))
+            }
+
+            fn unknown_name(name: &str) -> KeyMappingError {
+                KeyMappingError::UnknownName(KeyMappingError::truncate(name))
+            }
+        }
+
+        impl core::fmt::Display for KeyMappingError {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match self {
+                    KeyMappingError::UnknownUsb { page: Some(page), code } => {
+                        write!(f, "unknown USB HID value {:#06x} in usage page {:#06x}", code, page)
+                    }
+                    KeyMappingError::UnknownUsb { page: None, code } => {
+                        write!(f, "unknown USB HID value {:#06x}", code)
+                    }
+                    #(#platform_error_messages)*
+                    KeyMappingError::UnknownCode(code) => write!(f, "unknown W3 browser event code `{}`", code),
+                    KeyMappingError::UnknownName(name) => write!(f, "unknown key name `{}`", name),
+                    KeyMappingError::Ambiguous(key_mapping) => {
+                        write!(f, "more than one key has the mapping {:?}", key_mapping)
+                    }
+                }
+            }
+        }
+
+        impl core::error::Error for KeyMappingError {}
+
+        impl KeyMap {
+            /// If you don't want to use TryFrom, until it is stabilized
+            ///
+            #[doc = #from_key_mapping_doc]
+            pub fn from_key_mapping(key_mapping: KeyMapping) -> Result<KeyMap, KeyMappingError> {
+                get_key_map(key_mapping)
+            }
+
+            /// Get every key with a mapping, starting with the one that lookups return
+            ///
+            /// USB values without a usage page are shared by keys on different pages, keys on the
+            /// keyboard page (`0x07`) come first and then keys in the order of the source table.
+            /// Platform values that the notes of the source table document as shared by several
+            /// keys, like Windows `0x76` for both F24 and Lang5, list the keys the table doesn't
+            /// give that value last. `KeyMapping::Code(None)` returns every key without a W3
+            /// browser event code.
+            pub fn candidates(key_mapping: KeyMapping) -> &'static [KeyMappingId] {
+                if let Some((_, ids)) = SHARED_CANDIDATES
+                    .iter()
+                    .find(|(shared, _)| *shared == key_mapping)
+                {
+                    return ids;
+                }
+                match key_mapping {
+                    KeyMapping::Code(None) => NULL_CODE_IDS,
+                    _ => match get_key_map(key_mapping) {
+                        Ok(key_map) => core::slice::from_ref(&KeyMappingId::ALL[key_map.id as usize]),
+                        Err(_) => &[],
+                    },
+                }
+            }
+
+            #(#platform_code_accessors)*
+
+            #(#extra_accessors)*
+
+            /// Every key, in the order of the source table
+            pub const ALL: &'static [KeyMap] = &[#(#all_key_maps),*];
+
+            /// USB HID usage page of every key in [`KeyMap::ALL`]
+            const USB_PAGES: &'static [u16] = &[#(#usb_page_matches),*];
+
+            /// Iterate over every key
+            pub fn iter() -> core::slice::Iter<'static, KeyMap> {
+                KeyMap::ALL.iter()
+            }
+
+            /// Iterate over the keys in a USB HID usage page
+            pub fn in_usb_page(page: u16) -> impl Iterator<Item = &'static KeyMap> {
+                KeyMap::ALL
+                    .iter()
+                    .zip(KeyMap::USB_PAGES)
+                    .filter(move |(_, key_page)| **key_page == page)
+                    .map(|(key_map, _)| key_map)
+            }
+
+            /// Get the USB HID usage page of the key
+            pub fn usb_page(&self) -> u16 {
+                KeyMap::USB_PAGES[self.id as usize]
+            }
+
+            /// Iterate over the keys with a W3 browser event code
+            pub fn with_code() -> impl Iterator<Item = &'static KeyMap> {
+                KeyMap::ALL.iter().filter(|key_map| key_map.code.is_some())
+            }
+
+            /// Iterate over the modifier keys
+            pub fn modifiers() -> impl Iterator<Item = &'static KeyMap> {
+                KeyMap::ALL.iter().filter(|key_map| key_map.modifier.is_some())
+            }
+
+            /// Categories of every key in [`KeyMap::ALL`]
+            const CATEGORIES: &'static [KeyCategory] = &[#(#key_categories),*];
+
+            /// Get the categories of the key
+            pub const fn category(&self) -> KeyCategory {
+                self.id.category()
+            }
+
+            #(#category_methods)*
+
+            /// Iterate over the keys in any of the categories
+            pub fn in_category(category: KeyCategory) -> impl Iterator<Item = &'static KeyMap> {
+                KeyMap::ALL
+                    .iter()
+                    .filter(move |key_map| key_map.category().intersects(category))
+            }
+
+            /// Get the key with an id, usable in `const` contexts
+            pub const fn from_id_const(id: KeyMappingId) -> KeyMap {
+                KeyMap::ALL[id as usize]
+            }
+
+            /// Get the key with a USB HID value in a usage page, usable in `const` contexts
+            ///
+            /// # Panics
+            ///
+            /// If no key has the value, which fails to compile when evaluated in a `const`
+            pub const fn from_usb_const(page: u16, code: u16) -> KeyMap {
+                match KeyMap::usb_to_id(page, code) {
+                    Some(id) => KeyMap::from_id_const(id),
+                    None => panic!("no key has this USB HID value"),
+                }
+            }
+
+            /// Get the id of the key with a USB HID value in a usage page, usable in `const`
+            /// contexts
+            pub const fn usb_to_id(page: u16, code: u16) -> Option<KeyMappingId> {
+                find_id(USB_INDEX, (page as u32) << 16 | code as u32)
+            }
+
+            #(#platform_to_ids)*
+
+            /// Get the id of the key with a W3 browser event code, usable in `const` contexts
+            pub const fn code_to_id(code: KeyMappingCode) -> KeyMappingId {
+                CODE_TO_ID[code as usize]
+            }
+
+            /// Get KeyMap from a USB keycode
+            pub fn from_usb_code(page: u16, code: u16) -> Result<KeyMap, KeyMappingError> {
+                get_usb_code(page, code)
+            }
+        }
+
+        impl core::convert::TryFrom<KeyMapping> for KeyMap {
+            type Error = KeyMappingError;
+            fn try_from(key_mapping: KeyMapping) -> Result<KeyMap, Self::Error> {
+                get_key_map(key_mapping)
+            }
+        }
+
+        fn get_usb_code(page: u16, code: u16) -> Result<KeyMap, KeyMappingError> {
+            match KeyMap::usb_to_id(page, code) {
+                Some(id) => Ok(KeyMap::from_id_const(id)),
+                None => Err(KeyMappingError::UnknownUsb { page: Some(page), code }),
+            }
+        }
+
+        fn get_key_map(key_mapping: KeyMapping) -> Result<KeyMap, KeyMappingError> {
+            let index = match key_mapping {
+                KeyMapping::Usb(code) => find_index(USB_TO_ID, u32::from(code))
+                    .ok_or(KeyMappingError::UnknownUsb { page: None, code })?,
+                #(#platform_lookups)*
+                KeyMapping::Code(Some(code)) => KeyMap::code_to_id(code) as usize,
+                KeyMapping::Code(None) => return Err(KeyMappingError::Ambiguous(key_mapping)),
+                KeyMapping::Id(id) => id as usize,
+            };
+            Ok(KeyMap::ALL[index])
+        }
+
+        /// Index into [`KeyMap::ALL`] plus one, zero for no key
+        type KeyIndex = #key_index_type;
+
+        /// Find the index into [`KeyMap::ALL`] of a value in pages of 256 values, each starting
+        /// with the value shifted right by 8, sorted by their start
+        const fn find_index(pages: &[(u32, [KeyIndex; 256])], value: u32) -> Option<usize> {
+            let (mut low, mut high) = (0, pages.len());
+            while low < high {
+                let page = low + (high - low) / 2;
+                let (start, indexes) = &pages[page];
+                if *start == value >> 8 {
+                    return match indexes[(value & 0xff) as usize] {
+                        0 => None,
+                        index => Some(index as usize - 1),
+                    };
+                } else if *start < value >> 8 {
+                    low = page + 1;
+                } else {
+                    high = page;
+                }
+            }
+            None
+        }
+
+        const fn find_id(pages: &[(u32, [KeyIndex; 256])], value: u32) -> Option<KeyMappingId> {
+            match find_index(pages, value) {
+                Some(index) => Some(KeyMappingId::ALL[index]),
+                None => None,
+            }
+        }
+
+        #(#platform_tables)*
+
+        /// Every key by USB HID usage page and value, in pages of 256 values
+        const USB_INDEX: &[(u32, [KeyIndex; 256])] = &[#(#usb_index),*];
+
+        /// Id of the key of every W3 browser event code, in the order of [`KeyMappingCode::ALL`]
+        const CODE_TO_ID: &[KeyMappingId] = &[#(KeyMappingId::#code_to_ids),*];
+
+        /// Keys without a W3 browser event code
+        const NULL_CODE_IDS: &[KeyMappingId] = &[#(KeyMappingId::#null_code_ids),*];
+
+        /// Platform values shared by several keys, with the preferred key first
+        const SHARED_CANDIDATES: &[(KeyMapping, &[KeyMappingId])] = &[
+            #((#shared_patterns, &[#(KeyMappingId::#shared_ids),*])),*
+        ];
+
+        /// W3 browser event code for a specific key
+        ///
+        /// <https://www.w3.org/TR/uievents-code/>
+        ///
+        /// The discriminant of each code is its position among the codes in the source table.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[repr(u16)]
+        pub enum KeyMappingCode {
+            #(
+                #[doc = "W3 browser event code for a specific key"]
+                #codes = #code_discriminants,
+            )*
+        }
+
+        impl KeyMappingCode {
+            /// Every code, in the order of the source table
+            pub const ALL: &'static [KeyMappingCode] = &[#(KeyMappingCode::#codes),*];
+
+            const NAMES: &'static [&'static str] = &[#(stringify!(#codes)),*];
+
+            /// Get the name of the code, like `KeyA`
+            pub const fn name(self) -> &'static str {
+                KeyMappingCode::NAMES[self as usize]
+            }
+        }
+
+        impl core::fmt::Display for KeyMappingCode {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl core::str::FromStr for KeyMappingCode {
+            type Err = KeyMappingError;
+
+            fn from_str(code: &str) -> Result<KeyMappingCode, Self::Err> {
+                match code {
+                    #(
+                        stringify!(#codes) => Ok(KeyMappingCode::#codes),
+                    )*
+                    _ => Err(KeyMappingError::unknown_code(code)),
+                }
+            }
+        }
+
+        impl From<KeyMappingCode> for KeyMap {
+            fn from(code: KeyMappingCode) -> KeyMap {
+                KeyMap::from_id_const(KeyMap::code_to_id(code))
+            }
+        }
+
+        /// Id for a specific key
+        ///
+        /// The discriminant of each key is its position in the source table.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[repr(u16)]
+        pub enum KeyMappingId {
+            #(
+                #[doc = "Id for a specific key"]
+                #[allow(non_camel_case_types)]
+                #ids = #id_discriminants,
+            )*
+        }
+
+        impl KeyMappingId {
+            /// Every id, in the order of the source table
+            pub const ALL: &'static [KeyMappingId] = &[#(KeyMappingId::#ids),*];
+
+            const NAMES: &'static [&'static str] = &[#(stringify!(#ids)),*];
+
+            /// Get the name of the id, like `UsA`
+            pub const fn name(self) -> &'static str {
+                KeyMappingId::NAMES[self as usize]
+            }
+
+            /// Get the categories of the key
+            pub const fn category(self) -> KeyCategory {
+                KeyMap::CATEGORIES[self as usize]
+            }
+
+            #(#id_category_methods)*
+        }
+
+        impl core::fmt::Display for KeyMappingId {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        /// Names accepted by `KeyMappingId::from_str`, earlier names win
+        const KEY_MAPPING_ID_NAMES: &[(&str, KeyMappingId)] = &[
+            #((#id_names, KeyMappingId::#id_name_ids)),*
+        ];
+
+        impl core::str::FromStr for KeyMappingId {
+            type Err = KeyMappingError;
+
+            /// Parse a key, ignoring ASCII case, from its id (`UsA`), its name in the source table
+            /// (`US_A`), its W3 browser event code (`KeyA`) or a common name (`a`, `esc`, `ctrl`)
+            ///
+            /// Names that belong to several keys pick the first in that order, so `super` is the
+            /// `Super` key rather than `MetaLeft`.
+            fn from_str(name: &str) -> Result<KeyMappingId, Self::Err> {
+                KEY_MAPPING_ID_NAMES
+                    .iter()
+                    .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+                    .map(|(_, id)| *id)
+                    .ok_or_else(|| KeyMappingError::unknown_name(name))
+            }
+        }
+
+        impl From<KeyMappingId> for KeyMap {
+            fn from(id: KeyMappingId) -> KeyMap {
+                KeyMap::from_id_const(id)
+            }
+        }
+    }
+}
+
+/// Group values into pages of 256 values, sorted by their start, each holding the index of the
+/// key plus one
+fn index_pages(values: impl Iterator<Item = (u32, usize)>) -> Vec<TokenStream> {
+    let mut pages: BTreeMap<u32, [usize; 256]> = BTreeMap::new();
+    for (value, index) in values {
+        let page = pages.entry(value >> 8).or_insert([0; 256]);
+        page[(value & 0xff) as usize] = index + 1;
+    }
+    pages
+        .into_iter()
+        .map(|(start, indexes)| {
+            let indexes = indexes
+                .iter()
+                .map(|index| proc_macro2::Literal::usize_unsuffixed(*index));
+            quote! { (#start, [#(#indexes),*]) }
+        })
+        .collect()
+}
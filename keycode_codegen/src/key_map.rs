@@ -0,0 +1,68 @@
+use crate::preprocess::Location;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+
+/// Value of an extra column from a `USB_KEYMAP_EXT` row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtraValue {
+    Code(u16),
+    Text(String),
+}
+
+/// Type of an extra column, every value of a column has the same type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtraKind {
+    Code,
+    Text,
+}
+
+impl ExtraValue {
+    pub fn kind(&self) -> ExtraKind {
+        match self {
+            ExtraValue::Code(_) => ExtraKind::Code,
+            ExtraValue::Text(_) => ExtraKind::Text,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    /// Position of the key in the source table
+    pub index: usize,
+    /// Where the row is in its table
+    pub location: Location,
+    pub usb_page_code: u16,
+    pub usb_code: u16,
+    pub evdev_code: u16,
+    pub xkb_code: u16,
+    pub win_code: u16,
+    pub mac_code: u16,
+    pub dom_code: Option<String>,
+    /// Name of the key in the source table, like `US_A`
+    pub chromium_name: String,
+    pub variant: String,
+    /// Extra columns by name, like `ps2_set2`
+    pub extra: BTreeMap<String, ExtraValue>,
+    /// Whether the row was commented out in the source table
+    pub commented: bool,
+}
+
+/// Get the extra columns of the keys and their types, checking that every value of a column has
+/// the same type
+pub fn extra_columns(key_maps: &[KeyMap]) -> Result<BTreeMap<String, ExtraKind>> {
+    let mut columns = BTreeMap::new();
+    for key_map in key_maps {
+        for (name, value) in &key_map.extra {
+            let kind = *columns.entry(name.clone()).or_insert_with(|| value.kind());
+            if kind != value.kind() {
+                return Err(anyhow!(
+                    "Column `{}` of `{}` mixes hex values and strings",
+                    name,
+                    key_map.chromium_name
+                ));
+            }
+        }
+    }
+
+    Ok(columns)
+}
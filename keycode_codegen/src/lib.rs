@@ -0,0 +1,544 @@
+//! Generate the key mapping types of the [`keycode`][] crate from Chromium's
+//! `keycode_converter_data.inc`
+//!
+//! This is the code behind `keycode_macro::parse_keycode_converter_data!`, usable from a build
+//! script to write plain Rust source that rust-analyzer and rustdoc can see:
+//!
+//! ```no_run
+//! // build.rs
+//! use keycode_codegen::Options;
+//! use std::{env, path::Path};
+//!
+//! fn main() {
+//!     let out_dir = env::var("OUT_DIR").unwrap();
+//!     let options = Options {
+//!         prefix: "Vendor".to_string(),
+//!         ..Options::default()
+//!     };
+//!     keycode_codegen::build(&options, Path::new(&out_dir).join("keys.rs")).unwrap();
+//! }
+//! ```
+//!
+//! The generated file can then be included with
+//! `include!(concat!(env!("OUT_DIR"), "/keys.rs"));`, in a crate that depends on `arrayvec` and
+//! `bitflags`.
+//!
+//! [`keycode`]: https://crates.io/crates/keycode
+
+mod aliases;
+mod category;
+mod diff;
+mod generate;
+mod key_map;
+mod merge;
+mod options;
+mod parse;
+mod platform;
+mod prefix;
+mod preprocess;
+mod validate;
+
+pub use self::{
+    diff::{SemverImpact, TableDiff},
+    options::Options,
+    platform::Platform,
+};
+
+use self::{
+    generate::*, merge::merge, parse::*, prefix::prefix_items, preprocess::preprocess,
+    validate::validate,
+};
+use anyhow::{anyhow, Result};
+use core::str::FromStr;
+use proc_macro2::TokenStream;
+use std::{env, fs, path::Path};
+
+/// Bundled copy of Chromium's `keycode_converter_data.inc`
+pub const KEYCODE_CONVERTER_DATA: &str = include_str!("../keycode_converter_data.inc");
+const BUNDLED_TABLE_NAME: &str = "keycode_converter_data.inc";
+
+/// Code generated from the key tables
+#[derive(Debug, Clone)]
+pub struct Generated {
+    /// Items for the keys, the same for every build with the same tables
+    pub tokens: TokenStream,
+    /// Full paths of the tables that were read, to rebuild when they change
+    pub tracked_paths: Vec<String>,
+    /// Problems in the tables that don't stop the code from being generated
+    pub warnings: Vec<String>,
+}
+
+/// Parse, merge and validate the tables of the options, then generate the code for the keys
+///
+/// Paths are relative to `CARGO_MANIFEST_DIR`, which Cargo sets for both macros and build
+/// scripts.
+pub fn generate_tokens(options: &Options) -> Result<Generated> {
+    let mut tracked_paths = vec![];
+    let mut warnings = vec![];
+    let (name, data) = match &options.path {
+        Some(path) => (path.as_str(), read_table(path, &mut tracked_paths)?),
+        None => (BUNDLED_TABLE_NAME, KEYCODE_CONVERTER_DATA.to_string()),
+    };
+    let mut key_maps = parse_table(name, &data, options, &mut tracked_paths, &mut warnings)?;
+    for extension in &options.extensions {
+        let data = read_table(extension, &mut tracked_paths)?;
+        let extension_key_maps =
+            parse_table(extension, &data, options, &mut tracked_paths, &mut warnings)?;
+        key_maps = merge(key_maps, extension_key_maps, extension)?;
+    }
+
+    Ok(Generated {
+        tokens: prefix_items(generate(key_maps, options), &options.prefix),
+        tracked_paths,
+        warnings,
+    })
+}
+
+/// Generate the code for the keys as Rust source
+pub fn generate_source(options: &Options) -> Result<String> {
+    Ok(to_source(options, &generate_tokens(options)?))
+}
+
+fn to_source(options: &Options, generated: &Generated) -> String {
+    format!(
+        "// Generated by keycode_codegen from {}, do not edit\n{}\n",
+        options.path.as_deref().unwrap_or(BUNDLED_TABLE_NAME),
+        generated.tokens
+    )
+}
+
+/// Write the code for the keys as Rust source, from a build script
+///
+/// Tells Cargo to run the build script again when a table changes and passes on the warnings for
+/// the tables.
+pub fn build(options: &Options, out_file: impl AsRef<Path>) -> Result<()> {
+    let generated = generate_tokens(options)?;
+    for path in &generated.tracked_paths {
+        println!("cargo:rerun-if-changed={}", path);
+    }
+    for warning in &generated.warnings {
+        println!("cargo:warning={}", warning);
+    }
+
+    let out_file = out_file.as_ref();
+    fs::write(out_file, to_source(options, &generated))
+        .map_err(|err| anyhow!("Could not write `{}`: {}", out_file.display(), err))
+}
+
+/// Compare the keys of two tables, like the bundled table and a newer copy of Chromium's
+pub fn diff_tables(old: &str, new: &str) -> Result<TableDiff> {
+    let options = Options::default();
+    let old = parse_table("old table", old, &options, &mut vec![], &mut vec![])?;
+    let new = parse_table("new table", new, &options, &mut vec![], &mut vec![])?;
+    Ok(TableDiff::new(&old, &new))
+}
+
+/// Read a table relative to `CARGO_MANIFEST_DIR`, remembering its full path
+fn read_table(path: &str, tracked_paths: &mut Vec<String>) -> Result<String> {
+    let manifest_dir =
+        env::var("CARGO_MANIFEST_DIR").map_err(|_| anyhow!("`CARGO_MANIFEST_DIR` is not set"))?;
+    let full_path = Path::new(&manifest_dir).join(path);
+    let data = fs::read_to_string(&full_path)
+        .map_err(|err| anyhow!("Could not read `{}`: {}", full_path.display(), err))?;
+    tracked_paths.push(full_path.display().to_string());
+    Ok(data)
+}
+
+/// Preprocess, parse and validate a table, with `#include` paths relative to `CARGO_MANIFEST_DIR`
+fn parse_table(
+    name: &str,
+    data: &str,
+    options: &Options,
+    tracked_paths: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<key_map::KeyMap>> {
+    let mut defines = options
+        .defines
+        .iter()
+        .map(|define| (define.clone(), String::new()))
+        .collect();
+    let parsed = preprocess(data, &mut defines, options.commented_rows, &mut |path| {
+        read_table(path, tracked_paths)
+    })
+    .and_then(|table| {
+        proc_macro2::TokenStream::from_str(&table.text)
+            .map_err(|err| {
+                anyhow!(
+                    "Could not parse the key table into a `TokenStream`: {}",
+                    err
+                )
+            })
+            .and_then(|tokens| parse(tokens, &table))
+    });
+
+    let mut table_warnings = vec![];
+    let key_maps = parsed
+        .and_then(|key_maps| validate(key_maps, &mut table_warnings))
+        .map_err(|err| anyhow!("Could not parse `{}`: {}", name, err))?;
+    warnings.extend(
+        table_warnings
+            .into_iter()
+            .map(|warning| format!("`{}`, {}", name, warning)),
+    );
+
+    Ok(key_maps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_test_table(name: &str, data: &str) -> Result<Vec<key_map::KeyMap>> {
+        parse_table(name, data, &Options::default(), &mut vec![], &mut vec![])
+    }
+
+    fn parse_bundled_table() -> Vec<key_map::KeyMap> {
+        parse_test_table(BUNDLED_TABLE_NAME, KEYCODE_CONVERTER_DATA).unwrap()
+    }
+
+    #[test]
+    fn generated_code_is_reproducible() {
+        let generate_from_source = || {
+            let key_maps = parse_bundled_table();
+            generate(key_maps, &Options::default()).to_string()
+        };
+
+        assert_eq!(generate_from_source(), generate_from_source());
+    }
+
+    #[test]
+    fn options_unescape_strings() {
+        let options = Options::parse(
+            TokenStream::from_str(r#"path = "dir\\keys.inc", prefix = r"Vendor""#).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(options.path.as_deref(), Some(r"dir\keys.inc"));
+        assert_eq!(options.prefix, "Vendor");
+    }
+
+    #[test]
+    fn prefixes_rename_items_in_docs() {
+        let options = Options {
+            prefix: "Vendor".to_string(),
+            ..Options::default()
+        };
+        let source = generate_source(&options).unwrap();
+        // Docs written as doc comments and built from strings
+        assert!(source.contains("[`VendorKeyMap::candidates`]"));
+        assert!(source.contains("[`VendorKeyCategory::Numpad`]"));
+        assert!(!source.contains("[`KeyCategory::Numpad`]"));
+    }
+
+    fn merge_extension(extension: &str) -> Result<Vec<key_map::KeyMap>> {
+        let extension = format!("USB_KEYMAP_DECLARATION {{ {} }};", extension);
+        merge(
+            parse_bundled_table(),
+            parse_test_table("test.inc", &extension)?,
+            "test.inc",
+        )
+    }
+
+    #[test]
+    fn extensions_fill_in_and_add_keys() {
+        let key_maps = merge_extension(
+            r#"
+            USB_KEYMAP_EXT(0x070004, 0x001e, 0x0026, 0x001e, 0x0000, "KeyA", US_A, ps2_set2 = 0x1c),
+            USB_KEYMAP(0xff000001, 0x0000, 0x0000, 0x0000, 0xffff, NULL, VENDOR_KEY),
+            "#,
+        )
+        .unwrap();
+        let base_len = parse_bundled_table().len();
+
+        assert_eq!(key_maps.len(), base_len + 1);
+        assert_eq!(key_maps[base_len].variant, "VendorKey");
+        assert_eq!(key_maps[base_len].index, base_len);
+        let us_a = key_maps
+            .iter()
+            .find(|key_map| key_map.variant == "UsA")
+            .unwrap();
+        assert_eq!(
+            us_a.extra.get("ps2_set2"),
+            Some(&key_map::ExtraValue::Code(0x1c))
+        );
+    }
+
+    #[test]
+    fn extensions_report_conflicts() {
+        // A different evdev value for an existing key
+        assert!(merge_extension(
+            r#"USB_KEYMAP(0x070004, 0x001f, 0x0026, 0x001e, 0x0000, "KeyA", US_A),"#
+        )
+        .is_err());
+        // A new key with the USB HID value of another key
+        assert!(merge_extension(
+            r#"USB_KEYMAP(0x070004, 0x0000, 0x0000, 0x0000, 0xffff, NULL, VENDOR_KEY),"#
+        )
+        .is_err());
+        // A new key with the W3 browser event code of another key
+        assert!(merge_extension(
+            r#"USB_KEYMAP(0xff000001, 0x0000, 0x0000, 0x0000, 0xffff, "KeyA", VENDOR_KEY),"#
+        )
+        .is_err());
+        // A column with hex values and strings
+        assert!(merge_extension(
+            r#"
+            USB_KEYMAP_EXT(0xff000001, 0x0000, 0x0000, 0x0000, 0xffff, NULL, VENDOR_A, label = 0x1),
+            USB_KEYMAP_EXT(0xff000002, 0x0000, 0x0000, 0x0000, 0xffff, NULL, VENDOR_B, label = "B"),
+            "#
+        )
+        .is_err());
+        // A column named like a category method, while other `is_*` names are free
+        assert!(merge_extension(
+            r#"USB_KEYMAP_EXT(0xff000001, 0x0000, 0x0000, 0x0000, 0xffff, NULL, VENDOR_A, is_numpad = 0x1),"#
+        )
+        .is_err());
+        assert!(merge_extension(
+            r#"USB_KEYMAP_EXT(0xff000001, 0x0000, 0x0000, 0x0000, 0xffff, NULL, VENDOR_A, is_wide = 0x1),"#
+        )
+        .is_ok());
+    }
+
+    fn preprocess_lines(data: &str, defines: &[&str]) -> Result<Vec<String>> {
+        let mut defines = defines
+            .iter()
+            .map(|define| (define.to_string(), String::new()))
+            .collect();
+        let output = preprocess(data, &mut defines, false, &mut |path| {
+            Err(anyhow!("no file `{}`", path))
+        })?;
+        Ok(output.text.lines().map(str::to_string).collect())
+    }
+
+    #[test]
+    fn preprocessor_evaluates_conditionals() {
+        let data = "#if defined(A) && !defined B\na\n#elif B || 0\nb\n#else\nc\n#endif\nd";
+        assert_eq!(
+            preprocess_lines(data, &["A"]).unwrap(),
+            ["", "a", "", "", "", "", "", "d"]
+        );
+        assert_eq!(preprocess_lines(data, &["A", "B"]).unwrap()[3], "b");
+        assert_eq!(preprocess_lines(data, &[]).unwrap()[5], "c");
+        assert!(preprocess_lines("#if A\na", &[]).is_err());
+        assert!(preprocess_lines("#endif", &[]).is_err());
+
+        // Defined names count as their value
+        let data = "#define A 0\n#define B A || 2\n#if A\na\n#elif B\nb\n#endif";
+        assert_eq!(preprocess_lines(data, &[]).unwrap()[5], "b");
+        assert_eq!(preprocess_lines(data, &[]).unwrap()[3], "");
+        assert!(preprocess_lines("#define A A\n#if A\n#endif", &[]).is_err());
+    }
+
+    #[test]
+    fn preprocessor_includes_files_for_defines() {
+        let parse_with = |defines: &[&str]| {
+            let mut defines = defines
+                .iter()
+                .map(|define| (define.to_string(), String::new()))
+                .collect();
+            preprocess(KEYCODE_CONVERTER_DATA, &mut defines, false, &mut |path| {
+                Err(anyhow!("no file `{}`", path))
+            })
+        };
+
+        assert!(parse_with(&[]).is_ok());
+        assert!(parse_with(&["OS_ANDROID"])
+            .unwrap_err()
+            .to_string()
+            .contains("keycode_conversion_data_android_generated.inc"));
+    }
+
+    #[test]
+    fn preprocessor_keeps_locations_of_included_lines() {
+        let mut defines = vec![];
+        let table = preprocess(
+            "a\n#include \"b.inc\"\nc",
+            &mut defines,
+            false,
+            &mut |path| {
+                assert_eq!(path, "b.inc");
+                Ok("\nb".to_string())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(table.text, "a\n\nb\nc\n");
+        let (location, column) = table.location(table.text.find('b').unwrap());
+        assert_eq!(
+            (location.to_string(), column),
+            ("line 2 of `b.inc`".to_string(), 1)
+        );
+        let (location, column) = table.location(table.text.find('c').unwrap());
+        assert_eq!((location.to_string(), column), ("line 3".to_string(), 1));
+    }
+
+    #[test]
+    fn preprocessor_reports_include_cycles() {
+        let mut defines = vec![];
+        let err = preprocess("#include \"a.inc\"", &mut defines, false, &mut |path| {
+            Ok(match path {
+                "a.inc" => "\n#include \"b.inc\"",
+                _ => "#include \"a.inc\"",
+            }
+            .to_string())
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "line 1 of `b.inc`: `a.inc` includes itself"
+        );
+    }
+
+    #[test]
+    fn malformed_rows_are_all_reported_with_their_position() {
+        let data = r#"USB_KEYMAP_DECLARATION {
+  USB_KEYMAP(0x000000, 0x0000, 0x0000, 0x0000, 0xffff, NULL, NONE),
+  USB_KEYMAP(0x070004, 0x1ffff, 0x0026, zz, 0x0000, "KeyA", US_A),
+      USB_KEYMAP(0x070005, 0x0030, 0x0038, 0x0030, 0x000b, KeyB, US_B),
+};"#;
+        let err = parse_test_table("bad.inc", data).unwrap_err().to_string();
+
+        assert!(err.starts_with("Could not parse `bad.inc`: 3 errors in the key table"));
+        assert!(err.contains(
+            "line 3, column 24, row `US_A` (`KeyA`): `EVDEV_CODE` could not be parsed into a `u16`"
+        ));
+        assert!(err.contains("line 3, column 41, row `US_A` (`KeyA`)"));
+        assert!(err.contains("line 4, column 60, row `US_B`: `DOM_CODE`"));
+    }
+
+    #[test]
+    fn extra_column_errors_are_reported_with_their_position() {
+        let data = r#"USB_KEYMAP_DECLARATION {
+  /* USB_KEYMAP( */ USB_KEYMAP_EXT(0x070004, 0x001e, 0x0026, 0x001e, 0x0000, "KeyA", US_A,
+    label = "(a, \"b\")", ps2_set2 = 0x1ffff),
+};"#;
+        let err = parse_test_table("bad.inc", data).unwrap_err().to_string();
+
+        assert!(err.contains(
+            "line 3, column 38, row `US_A` (`KeyA`): `USB_KEYMAP_EXT` column `ps2_set2` must be"
+        ));
+    }
+
+    fn validate_rows(rows: &str) -> (Result<Vec<key_map::KeyMap>>, Vec<String>) {
+        let data = format!(
+            "USB_KEYMAP_DECLARATION {{\n  {}\n}};",
+            rows.trim().replace('\n', "\n  ")
+        );
+        let mut warnings = vec![];
+        let key_maps = parse_table(
+            "test.inc",
+            &data,
+            &Options::default(),
+            &mut vec![],
+            &mut warnings,
+        );
+        (key_maps, warnings)
+    }
+
+    #[test]
+    fn validation_reports_clashing_keys() {
+        let (key_maps, warnings) = validate_rows(
+            r#"
+USB_KEYMAP(0x070004, 0x001e, 0x0026, 0x001e, 0x0000, "KeyA", US_A),
+USB_KEYMAP(0x070005, 0x0030, 0x0038, 0x0030, 0x000b, "KeyA", US_B),
+USB_KEYMAP(0x070004, 0x0000, 0x0000, 0x0000, 0xffff, NULL, OTHER_A),
+USB_KEYMAP(0x070006, 0x002e, 0x0036, 0x001e, 0x0008, "KeyC", US_C),
+USB_KEYMAP(0x070004, 0x001f, 0x0026, 0x001e, 0x0000, "KeyA", US_A),
+"#,
+        );
+        let err = key_maps.unwrap_err().to_string();
+
+        assert!(err.starts_with("Could not parse `test.inc`: 4 errors in the key table"));
+        assert!(err.contains(
+            "line 3, row `US_B`: has the W3 browser event code `KeyA` of `US_A` on line 2"
+        ));
+        assert!(err.contains(
+            "line 4, row `OTHER_A`: has the USB HID usage 0x0007:0x0004 of `US_A` on line 2"
+        ));
+        assert!(
+            err.contains("line 5, row `US_C`: has the Windows value 0x001e of `US_A` on line 2")
+        );
+        assert!(err.contains(
+            "line 6, row `US_A`: has the variant of the row on line 2 with different values"
+        ));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn validation_allows_documented_aliases_and_warns() {
+        let (key_maps, warnings) = validate_rows(
+            r#"
+USB_KEYMAP(0x070073, 0x00c2, 0x00ca, 0x0076, 0x006a, "F24", F24),
+USB_KEYMAP(0x070094, 0x0000, 0x0000, 0x0076, 0xffff, "Lang5", LANG5),
+USB_KEYMAP(0x070004, 0x001e, 0x0027, 0x001e, 0x0000, "KeyA", US_A),
+USB_KEYMAP(0x070004, 0x001e, 0x0027, 0x001e, 0x0000, "KeyA", US_A),
+"#,
+        );
+
+        assert_eq!(key_maps.unwrap().len(), 3);
+        assert_eq!(
+            warnings,
+            [
+                "`test.inc`, line 4, row `US_A`: has X11 value 0x0027, not its evdev value 0x001e plus 8",
+                "`test.inc`, line 5, row `US_A`: repeats the row on line 4 and is skipped",
+            ]
+        );
+    }
+
+    #[test]
+    fn bundled_table_is_valid() {
+        let mut warnings = vec![];
+        let options = Options {
+            commented_rows: true,
+            ..Options::default()
+        };
+        parse_table(
+            BUNDLED_TABLE_NAME,
+            KEYCODE_CONVERTER_DATA,
+            &options,
+            &mut vec![],
+            &mut warnings,
+        )
+        .unwrap();
+
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn table_diffs_report_keys_and_semver_impact() {
+        let table = |rows: &str| format!("USB_KEYMAP_DECLARATION {{ {} }};", rows);
+        let old = table(
+            r#"
+            USB_KEYMAP(0x070004, 0x001e, 0x0026, 0x001e, 0x0000, "KeyA", US_A),
+            USB_KEYMAP(0x070005, 0x0030, 0x0038, 0x0030, 0x000b, "KeyB", US_B),
+            "#,
+        );
+        let changed = table(
+            r#"
+            USB_KEYMAP(0x070004, 0x001e, 0x0026, 0x001e, 0x0001, "KeyA", US_A),
+            USB_KEYMAP(0x070005, 0x0030, 0x0038, 0x0030, 0x000b, "KeyB", US_B),
+            "#,
+        );
+        let renamed = table(
+            r#"
+            USB_KEYMAP(0x070004, 0x001e, 0x0026, 0x001e, 0x0000, "KeyA", US_A),
+            USB_KEYMAP(0x070005, 0x0030, 0x0038, 0x0030, 0x000b, "KeyB", LETTER_B),
+            "#,
+        );
+
+        let diff = diff_tables(&old, &old).unwrap();
+        assert_eq!(diff.impact().0, SemverImpact::None);
+
+        let diff = diff_tables(&old, &changed).unwrap();
+        assert_eq!(diff.impact().0, SemverImpact::Minor);
+        assert!(diff
+            .to_string()
+            .contains("Changed keys:\n  US_A: Mac 0x0000 -> 0x0001\n"));
+
+        let diff = diff_tables(&old, &renamed).unwrap();
+        assert_eq!(diff.impact().0, SemverImpact::Major);
+        assert!(diff
+            .to_string()
+            .contains("Renamed keys:\n  ~ US_B -> LETTER_B (USB HID 0x0007:0x0005)\n"));
+    }
+}
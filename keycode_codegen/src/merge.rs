@@ -0,0 +1,149 @@
+use crate::{
+    key_map::{extra_columns, KeyMap},
+    platform::{Platform, PLATFORMS},
+};
+use anyhow::{anyhow, Result};
+
+/// Merge the keys of an extension table on top of `key_maps`
+///
+/// Keys with a variant that is already in `key_maps` fill in its missing platform values, W3
+/// browser event code and extra columns, the other keys are added after the existing ones. Every
+/// value that disagrees with the existing keys is reported at once.
+pub fn merge(mut key_maps: Vec<KeyMap>, extension: Vec<KeyMap>, name: &str) -> Result<Vec<KeyMap>> {
+    let mut conflicts = vec![];
+    for key_map in extension {
+        match key_maps
+            .iter()
+            .position(|existing| existing.variant == key_map.variant)
+        {
+            Some(index) => {
+                let filled = fill_key(&mut key_maps[index], &key_map, &mut conflicts);
+                for platform in filled {
+                    check_platform_value(&key_maps, &key_maps[index], platform, &mut conflicts);
+                }
+                check_dom_code(&key_maps, &key_maps[index], &mut conflicts);
+            }
+            None => {
+                let key_map = KeyMap {
+                    index: key_maps.len(),
+                    ..key_map
+                };
+                for platform in PLATFORMS {
+                    check_platform_value(&key_maps, &key_map, platform, &mut conflicts);
+                }
+                check_dom_code(&key_maps, &key_map, &mut conflicts);
+                key_maps.push(key_map);
+            }
+        }
+    }
+
+    if !conflicts.is_empty() {
+        return Err(anyhow!(
+            "Extension `{}` conflicts with the key table: {}",
+            name,
+            conflicts.join("; ")
+        ));
+    }
+    extra_columns(&key_maps)?;
+
+    Ok(key_maps)
+}
+
+/// Fill in the values of an existing key, returning the platforms with a new value
+fn fill_key(existing: &mut KeyMap, key_map: &KeyMap, conflicts: &mut Vec<String>) -> Vec<Platform> {
+    let mut filled = vec![];
+    if (existing.usb_page_code, existing.usb_code) != (key_map.usb_page_code, key_map.usb_code) {
+        conflicts.push(format!(
+            "`{}` has USB HID value {:#06x}:{:#06x}, not {:#06x}:{:#06x}",
+            existing.chromium_name,
+            existing.usb_page_code,
+            existing.usb_code,
+            key_map.usb_page_code,
+            key_map.usb_code
+        ));
+    }
+    for platform in PLATFORMS.iter().copied().filter(|p| *p != Platform::Usb) {
+        match (platform.value(existing), platform.value(key_map)) {
+            (None, Some(value)) => {
+                *platform.raw_value_mut(existing) = value;
+                filled.push(platform);
+            }
+            (Some(old), Some(new)) if old != new => conflicts.push(format!(
+                "`{}` has {} {:#06x}, not {:#06x}",
+                existing.chromium_name,
+                platform.description(),
+                old,
+                new
+            )),
+            _ => {}
+        }
+    }
+    match (&existing.dom_code, &key_map.dom_code) {
+        (None, Some(_)) => existing.dom_code = key_map.dom_code.clone(),
+        (Some(old), Some(new)) if old != new => conflicts.push(format!(
+            "`{}` has W3 browser event code `{}`, not `{}`",
+            existing.chromium_name, old, new
+        )),
+        _ => {}
+    }
+    for (column, value) in &key_map.extra {
+        match existing.extra.get(column) {
+            Some(old) if old != value => conflicts.push(format!(
+                "`{}` has different values for column `{}`",
+                existing.chromium_name, column
+            )),
+            Some(_) => {}
+            None => {
+                existing.extra.insert(column.clone(), value.clone());
+            }
+        }
+    }
+
+    filled
+}
+
+/// Report another key with the same value as `key_map` on a platform, unless the notes of the
+/// source table document the value as shared
+fn check_platform_value(
+    key_maps: &[KeyMap],
+    key_map: &KeyMap,
+    platform: Platform,
+    conflicts: &mut Vec<String>,
+) {
+    let value = match platform.value(key_map) {
+        Some(value) if !platform.is_documented_alias(value) => value,
+        _ => return,
+    };
+    let other = key_maps.iter().find(|other| {
+        other.index != key_map.index
+            && !other.commented
+            && platform.value(other) == Some(value)
+            && (platform != Platform::Usb || other.usb_page_code == key_map.usb_page_code)
+    });
+    if let Some(other) = other {
+        conflicts.push(format!(
+            "`{}` and `{}` both have {} {:#06x}",
+            other.chromium_name,
+            key_map.chromium_name,
+            platform.description(),
+            value
+        ));
+    }
+}
+
+/// Report another key with the same W3 browser event code as `key_map`
+fn check_dom_code(key_maps: &[KeyMap], key_map: &KeyMap, conflicts: &mut Vec<String>) {
+    let code = match &key_map.dom_code {
+        Some(code) => code,
+        None => return,
+    };
+    let other = key_maps
+        .iter()
+        .find(|other| other.index != key_map.index && other.dom_code.as_ref() == Some(code));
+    if let Some(other) = other {
+        conflicts.push(format!(
+            "`{}` and `{}` both have W3 browser event code `{}`",
+            other.chromium_name, key_map.chromium_name, code
+        ));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::platform::{Platform, PLATFORMS};
+use anyhow::{anyhow, Result};
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+
+const OPTION_PATH: &str = "path";
+const OPTION_PREFIX: &str = "prefix";
+const OPTION_PLATFORMS: &str = "platforms";
+const OPTION_EXTENSIONS: &str = "extensions";
+const OPTION_DEFINES: &str = "defines";
+const OPTION_COMMENTED_ROWS: &str = "commented_rows";
+
+/// Options passed to the macro, like
+/// `parse_keycode_converter_data!(path = "keys.inc", prefix = "Vendor", platforms = [usb, evdev])`
+/// or `parse_keycode_converter_data!(extensions = ["vendor.inc"])`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Path of the source table relative to `CARGO_MANIFEST_DIR`, the bundled table if `None`
+    pub path: Option<String>,
+    /// Prefix for the names of every generated item
+    pub prefix: String,
+    /// Platforms with a column in the generated code, always including USB
+    pub platforms: Vec<Platform>,
+    /// Paths of tables merged on top of the source table, in order, relative to
+    /// `CARGO_MANIFEST_DIR`
+    pub extensions: Vec<String>,
+    /// Names that count as defined for `#if` directives in the tables
+    pub defines: Vec<String>,
+    /// Whether to add the rows that the source table comments out as potentially interesting
+    pub commented_rows: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            path: None,
+            prefix: String::new(),
+            platforms: PLATFORMS.to_vec(),
+            extensions: vec![],
+            defines: vec![],
+            commented_rows: false,
+        }
+    }
+}
+
+impl Options {
+    /// Parse comma separated `name = value` options, every option is optional
+    pub fn parse(input: TokenStream) -> Result<Options> {
+        let mut options = Options::default();
+        let mut iter = input.into_iter();
+        while let Some(name) = iter.next() {
+            let name = match name {
+                TokenTree::Ident(ident) => ident.to_string(),
+                other => return Err(anyhow!("Expected an option name, found `{}`", other)),
+            };
+            match iter.next() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {}
+                _ => return Err(anyhow!("Option `{}` is missing an `=`", name)),
+            }
+            let value = iter
+                .next()
+                .ok_or_else(|| anyhow!("Option `{}` is missing a value", name))?;
+
+            match name.as_str() {
+                OPTION_PATH => options.path = Some(parse_string(&name, value)?),
+                OPTION_PREFIX => {
+                    let prefix = parse_string(&name, value)?;
+                    if !prefix.chars().all(|c| c.is_ascii_alphanumeric())
+                        || !prefix.starts_with(|c: char| c.is_ascii_uppercase())
+                    {
+                        return Err(anyhow!(
+                            "Option `{}` must be an upper camel case name like `Vendor`",
+                            OPTION_PREFIX
+                        ));
+                    }
+                    options.prefix = prefix;
+                }
+                OPTION_PLATFORMS => options.platforms = parse_platforms(value)?,
+                OPTION_EXTENSIONS => options.extensions = parse_extensions(value)?,
+                OPTION_DEFINES => options.defines = parse_defines(value)?,
+                OPTION_COMMENTED_ROWS => options.commented_rows = parse_bool(&name, value)?,
+                _ => {
+                    return Err(anyhow!(
+                        "Unknown option `{}`, expected `{}`, `{}`, `{}`, `{}`, `{}` or `{}`",
+                        name,
+                        OPTION_PATH,
+                        OPTION_PREFIX,
+                        OPTION_PLATFORMS,
+                        OPTION_EXTENSIONS,
+                        OPTION_DEFINES,
+                        OPTION_COMMENTED_ROWS
+                    ))
+                }
+            }
+
+            match iter.next() {
+                None => break,
+                Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => {}
+                Some(other) => return Err(anyhow!("Expected a `,`, found `{}`", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_string(name: &str, value: TokenTree) -> Result<String> {
+    syn::parse2::<syn::LitStr>(value.into())
+        .map(|string| string.value())
+        .map_err(|_| anyhow!("Option `{}` must be a string", name))
+}
+
+fn parse_bool(name: &str, value: TokenTree) -> Result<bool> {
+    match value {
+        TokenTree::Ident(ident) if ident == "true" => Ok(true),
+        TokenTree::Ident(ident) if ident == "false" => Ok(false),
+        _ => Err(anyhow!("Option `{}` must be `true` or `false`", name)),
+    }
+}
+
+fn parse_platforms(value: TokenTree) -> Result<Vec<Platform>> {
+    let group = match value {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket => group,
+        _ => {
+            return Err(anyhow!(
+                "Option `{}` must be a list like `[usb, evdev]`",
+                OPTION_PLATFORMS
+            ))
+        }
+    };
+
+    let mut platforms = vec![];
+    for token in group.stream() {
+        match token {
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                let platform = Platform::from_name(&name).ok_or_else(|| {
+                    anyhow!(
+                        "Unknown platform `{}`, expected `usb`, `evdev`, `xkb`, `win` or `mac`",
+                        name
+                    )
+                })?;
+                if !platforms.contains(&platform) {
+                    platforms.push(platform);
+                }
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ',' => {}
+            other => return Err(anyhow!("Expected a platform, found `{}`", other)),
+        }
+    }
+
+    if !platforms.contains(&Platform::Usb) {
+        return Err(anyhow!(
+            "Option `{}` must include `usb`, which every key has",
+            OPTION_PLATFORMS
+        ));
+    }
+    platforms.sort();
+
+    Ok(platforms)
+}
+
+fn parse_extensions(value: TokenTree) -> Result<Vec<String>> {
+    let group = match value {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket => group,
+        _ => {
+            return Err(anyhow!(
+                "Option `{}` must be a list like `[\"vendor.inc\"]`",
+                OPTION_EXTENSIONS
+            ))
+        }
+    };
+
+    let mut extensions = vec![];
+    for token in group.stream() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => {}
+            token => extensions.push(parse_string(OPTION_EXTENSIONS, token)?),
+        }
+    }
+
+    Ok(extensions)
+}
+
+fn parse_defines(value: TokenTree) -> Result<Vec<String>> {
+    let group = match value {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket => group,
+        _ => {
+            return Err(anyhow!(
+                "Option `{}` must be a list like `[OS_ANDROID]`",
+                OPTION_DEFINES
+            ))
+        }
+    };
+
+    let mut defines = vec![];
+    for token in group.stream() {
+        match token {
+            TokenTree::Ident(ident) => defines.push(ident.to_string()),
+            TokenTree::Punct(punct) if punct.as_char() == ',' => {}
+            other => return Err(anyhow!("Expected a name to define, found `{}`", other)),
+        }
+    }
+
+    Ok(defines)
+}
@@ -1,15 +1,19 @@
 mod parse_keycode_list;
+mod scan_rows;
 
 use self::parse_keycode_list::*;
-use crate::key_map::KeyMap;
+use crate::{
+    key_map::{extra_columns, KeyMap},
+    preprocess::Preprocessed,
+};
 use anyhow::{anyhow, Result};
 use proc_macro2::{TokenStream, TokenTree};
-use std::collections::HashSet;
 
 const USB_KEYMAP_DECLARATION_IDENT: &str = "USB_KEYMAP_DECLARATION";
 const USB_KEYMAP_DECLARATION_END_PUNC: &str = ";";
 
-pub fn parse(input: TokenStream) -> Result<HashSet<KeyMap>> {
+/// Parse the rows of a preprocessed table, `table` locates them
+pub fn parse(input: TokenStream, table: &Preprocessed) -> Result<Vec<KeyMap>> {
     let mut iter = input.into_iter();
 
     // Check for ident
@@ -27,10 +31,11 @@ pub fn parse(input: TokenStream) -> Result<HashSet<KeyMap>> {
     // Parse through the keycodes
     let key_maps;
     if let Some(TokenTree::Group(usb_keymap_declaration_list)) = iter.next() {
-        key_maps = parse_keycode_list(usb_keymap_declaration_list.stream())?;
+        key_maps = parse_keycode_list(usb_keymap_declaration_list.stream(), table)?;
         if key_maps.is_empty() {
             return Err(anyhow!("`{}` list is empty", USB_KEYMAP_DECLARATION_IDENT));
         }
+        extra_columns(&key_maps)?;
     } else {
         return Err(anyhow!("Missing `{}` list", USB_KEYMAP_DECLARATION_IDENT));
     }
@@ -0,0 +1,399 @@
+use super::scan_rows::{scan_rows, RowOffsets};
+use crate::{
+    category::CATEGORIES,
+    key_map::{ExtraValue, KeyMap},
+    preprocess::{Location, Preprocessed, COMMENTED_ROW_IDENT},
+};
+use anyhow::{anyhow, Result};
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::{TokenStream, TokenTree};
+use std::{collections::BTreeMap, convert::TryFrom};
+
+const HEX_START: &str = "0x";
+
+fn parse_hex<T: TryFrom<u32>>(hex_string: String) -> Result<T, ()> {
+    if hex_string.starts_with(HEX_START) {
+        let num =
+            u32::from_str_radix(hex_string.trim_start_matches(HEX_START), 16).map_err(|_| ())?;
+        return T::try_from(num).map_err(|_| ());
+    }
+
+    Err(())
+}
+
+const STRING_LITERAL_QUOTE: &str = r#"""#;
+
+fn parse_literal_string_to_ident_string(literal_string: String) -> Result<String, ()> {
+    if literal_string.starts_with(STRING_LITERAL_QUOTE)
+        && literal_string.ends_with(STRING_LITERAL_QUOTE)
+    {
+        return Ok(String::from(
+            literal_string
+                .trim_start_matches(STRING_LITERAL_QUOTE)
+                .trim_end_matches(STRING_LITERAL_QUOTE),
+        ));
+    }
+
+    Err(())
+}
+
+const USB_KEYMAP_IDENT: &str = "USB_KEYMAP";
+const USB_KEYMAP_EXT_IDENT: &str = "USB_KEYMAP_EXT";
+const USB_KEYMAP_USB_CODE: &str = "USB_CODE";
+const USB_KEYMAP_EVDEV_CODE: &str = "EVDEV_CODE";
+const USB_KEYMAP_XKB_CODE: &str = "XKB_CODE";
+const USB_KEYMAP_WIN_CODE: &str = "WIN_CODE";
+const USB_KEYMAP_MAC_CODE: &str = "MAC_CODE";
+const USB_KEYMAP_DOM_CODE: &str = "DOM_CODE";
+const USB_KEYMAP_DOM_CODE_NULL_IDENT: &str = "NULL";
+const USB_KEYMAP_VARIANT: &str = "VARIANT";
+const USB_KEYMAP_ITEMS: usize = 7;
+
+/// Names of the `KeyMap` methods and associated constants that an extra column would clash with,
+/// besides the `from_*`, `with_*`, `*_code` and `*_to_id` families and the `is_*` methods of the
+/// categories
+const RESERVED_COLUMNS: &[&str] = &[
+    "all",
+    "candidates",
+    "categories",
+    "category",
+    "in_category",
+    "iter",
+    "in_usb_page",
+    "usb_page",
+    "usb_pages",
+    "modifiers",
+];
+
+fn is_reserved_column(name: &str) -> bool {
+    RESERVED_COLUMNS.contains(&name)
+        || name.starts_with("from_")
+        || name.starts_with("with_")
+        || CATEGORIES.iter().any(|category| category.method == name)
+        || name.ends_with("_code")
+        || name.ends_with("_to_id")
+}
+
+/// Error at an item of a row, or at the whole row without one
+struct RowError {
+    item: Option<usize>,
+    message: String,
+}
+
+impl RowError {
+    fn new(item: Option<usize>, message: String) -> RowError {
+        RowError { item, message }
+    }
+
+    /// Describe the error with its line and column in the table and the row it is in
+    fn describe(&self, row: &str, offsets: &RowOffsets, table: &Preprocessed) -> String {
+        let offset = match self.item {
+            Some(item) => offsets.items.get(item).copied(),
+            None => offsets.group,
+        };
+        let (location, column) = table.location(offset.unwrap_or(offsets.row));
+        format!("{}, column {}, {}: {}", location, column, row, self.message)
+    }
+}
+
+/// Keep the value of a result, or the error at item `item` of the row for later
+fn collect<T>(item: usize, result: Result<T, String>, errors: &mut Vec<RowError>) -> Option<T> {
+    result
+        .map_err(|message| errors.push(RowError::new(Some(item), message)))
+        .ok()
+}
+
+/// Name a row by its variant and W3 browser event code, if they could be parsed
+fn row_name(items: &[TokenTree]) -> String {
+    let variant = match items.get(6) {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return "row".to_string(),
+    };
+    match items
+        .get(5)
+        .and_then(|code| parse_literal_string_to_ident_string(code.to_string()).ok())
+    {
+        Some(code) => format!("row `{}` (`{}`)", variant, code),
+        None => format!("row `{}`", variant),
+    }
+}
+
+fn parse_code<T: TryFrom<u32>>(
+    token: &TokenTree,
+    field: &str,
+    type_name: &str,
+) -> Result<T, String> {
+    match token {
+        TokenTree::Literal(literal) => parse_hex(literal.to_string())
+            .map_err(|_| format!("`{}` could not be parsed into a `{}`", field, type_name)),
+        _ => Err(format!(
+            "`{}` does not contain a literal value for `{}`",
+            USB_KEYMAP_IDENT, field
+        )),
+    }
+}
+
+fn parse_dom_code(token: &TokenTree) -> Result<Option<String>, String> {
+    match token {
+        TokenTree::Literal(literal) => parse_literal_string_to_ident_string(literal.to_string())
+            .map(Some)
+            .map_err(|_| format!("`{}` contains a non-string literal", USB_KEYMAP_DOM_CODE)),
+        TokenTree::Ident(ident) if ident == USB_KEYMAP_DOM_CODE_NULL_IDENT => Ok(None),
+        TokenTree::Ident(_) => Err(format!(
+            "`{}` contains an ident that is not equal to `{}` (alternatively provide a string)",
+            USB_KEYMAP_DOM_CODE, USB_KEYMAP_DOM_CODE_NULL_IDENT
+        )),
+        _ => Err(format!(
+            "`{}` does not contain a literal value or `{}` for `{}`",
+            USB_KEYMAP_IDENT, USB_KEYMAP_DOM_CODE_NULL_IDENT, USB_KEYMAP_DOM_CODE
+        )),
+    }
+}
+
+fn parse_variant(token: &TokenTree) -> Result<String, String> {
+    match token {
+        TokenTree::Ident(ident) => Ok(ident.to_string()),
+        _ => Err(format!(
+            "`{}` does not contain an ident for `{}`",
+            USB_KEYMAP_IDENT, USB_KEYMAP_VARIANT
+        )),
+    }
+}
+
+/// Parse the extra `name = value` columns of a `USB_KEYMAP_EXT` row, values are either hex codes
+/// or strings
+fn parse_extra_columns(
+    items: Vec<TokenTree>,
+    errors: &mut Vec<RowError>,
+) -> BTreeMap<String, ExtraValue> {
+    let mut extra = BTreeMap::new();
+    let mut iter = items
+        .into_iter()
+        .enumerate()
+        .map(|(index, token)| (USB_KEYMAP_ITEMS + index, token));
+    while let Some((name_item, name_token)) = iter.next() {
+        let name = match &name_token {
+            TokenTree::Ident(ident) => ident.to_string(),
+            other => {
+                errors.push(RowError::new(
+                    Some(name_item),
+                    format!(
+                        "`{}` expected a column name, found `{}`",
+                        USB_KEYMAP_EXT_IDENT, other
+                    ),
+                ));
+                continue;
+            }
+        };
+        let column_error = |message: &str| {
+            RowError::new(
+                Some(name_item),
+                format!("`{}` column `{}` {}", USB_KEYMAP_EXT_IDENT, name, message),
+            )
+        };
+        let (value_item, value_token) = match iter.next() {
+            Some((item, TokenTree::Literal(literal))) => (item, literal),
+            _ => {
+                errors.push(column_error("is missing a value"));
+                break;
+            }
+        };
+        if name.to_snake_case() != name {
+            errors.push(column_error("must be a snake case name like `ps2_set2`"));
+            continue;
+        }
+        if is_reserved_column(&name) {
+            errors.push(column_error("clashes with a `KeyMap` method"));
+            continue;
+        }
+        let literal = value_token.to_string();
+        let value = if let Ok(text) = syn::parse_str::<syn::LitStr>(&literal) {
+            ExtraValue::Text(text.value())
+        } else if let Ok(code) = parse_hex::<u16>(literal) {
+            ExtraValue::Code(code)
+        } else {
+            errors.push(RowError::new(
+                Some(value_item),
+                format!(
+                    "`{}` column `{}` must be a `u16` hex value or a string",
+                    USB_KEYMAP_EXT_IDENT, name
+                ),
+            ));
+            continue;
+        };
+        if extra.insert(name.clone(), value).is_some() {
+            errors.push(column_error("is given more than once"));
+        }
+    }
+
+    extra
+}
+
+/// Parse the values of a row, collecting every error in it
+fn parse_row(
+    row_kind: &str,
+    location: Location,
+    mut items: Vec<TokenTree>,
+) -> Result<KeyMap, Vec<RowError>> {
+    if items.len() < USB_KEYMAP_ITEMS {
+        return Err(vec![RowError::new(
+            None,
+            format!(
+                "`{}` does not contain a full group of `({}, {}, {}, {}, {}, {}, {})`",
+                USB_KEYMAP_IDENT,
+                USB_KEYMAP_USB_CODE,
+                USB_KEYMAP_EVDEV_CODE,
+                USB_KEYMAP_XKB_CODE,
+                USB_KEYMAP_WIN_CODE,
+                USB_KEYMAP_MAC_CODE,
+                USB_KEYMAP_DOM_CODE,
+                USB_KEYMAP_VARIANT,
+            ),
+        )]);
+    }
+
+    let mut errors = vec![];
+    let extra = if row_kind == USB_KEYMAP_EXT_IDENT {
+        parse_extra_columns(items.split_off(USB_KEYMAP_ITEMS), &mut errors)
+    } else {
+        BTreeMap::new()
+    };
+    let usb = collect(
+        0,
+        parse_code::<u32>(&items[0], USB_KEYMAP_USB_CODE, "u32"),
+        &mut errors,
+    );
+    let evdev_code = collect(
+        1,
+        parse_code::<u16>(&items[1], USB_KEYMAP_EVDEV_CODE, "u16"),
+        &mut errors,
+    );
+    let xkb_code = collect(
+        2,
+        parse_code::<u16>(&items[2], USB_KEYMAP_XKB_CODE, "u16"),
+        &mut errors,
+    );
+    let win_code = collect(
+        3,
+        parse_code::<u16>(&items[3], USB_KEYMAP_WIN_CODE, "u16"),
+        &mut errors,
+    );
+    let mac_code = collect(
+        4,
+        parse_code::<u16>(&items[4], USB_KEYMAP_MAC_CODE, "u16"),
+        &mut errors,
+    );
+    let dom_code = collect(5, parse_dom_code(&items[5]), &mut errors);
+    let chromium_name = collect(6, parse_variant(&items[6]), &mut errors);
+
+    match (
+        usb,
+        evdev_code,
+        xkb_code,
+        win_code,
+        mac_code,
+        dom_code,
+        chromium_name,
+    ) {
+        (
+            Some(usb),
+            Some(evdev_code),
+            Some(xkb_code),
+            Some(win_code),
+            Some(mac_code),
+            Some(dom_code),
+            Some(chromium_name),
+        ) if errors.is_empty() => Ok(KeyMap {
+            index: 0,
+            location,
+            usb_page_code: (usb >> 16) as u16,
+            usb_code: usb as u16,
+            evdev_code,
+            xkb_code,
+            win_code,
+            mac_code,
+            dom_code,
+            variant: chromium_name.to_upper_camel_case(),
+            chromium_name,
+            extra,
+            commented: row_kind == COMMENTED_ROW_IDENT,
+        }),
+        _ => Err(errors),
+    }
+}
+
+/// Parse the list of keys in the order of the source table, including keys with a variant that
+/// was already seen
+///
+/// Rows that were commented out in the source table are added after the other keys.
+///
+/// Besides Chromium's `USB_KEYMAP` rows, `USB_KEYMAP_EXT` rows add named columns after the
+/// variant, like `USB_KEYMAP_EXT(0x070004, ..., US_A, ps2_set2 = 0x1c, label = "A")`.
+///
+/// Every malformed row is reported, with its line and column in the table.
+pub fn parse_keycode_list(input: TokenStream, table: &Preprocessed) -> Result<Vec<KeyMap>> {
+    let mut key_maps = Vec::new();
+    let mut commented_key_maps = Vec::new();
+    let mut errors = Vec::new();
+    let rows = scan_rows(
+        &table.text,
+        &[USB_KEYMAP_IDENT, USB_KEYMAP_EXT_IDENT, COMMENTED_ROW_IDENT],
+    );
+    let no_offsets = RowOffsets::default();
+    let mut row_index = 0;
+    let mut list_iter = input.into_iter();
+    while let Some(item) = list_iter.next() {
+        let row_kind = item.to_string();
+        if row_kind != USB_KEYMAP_IDENT
+            && row_kind != USB_KEYMAP_EXT_IDENT
+            && row_kind != COMMENTED_ROW_IDENT
+        {
+            continue;
+        }
+        let offsets = rows.get(row_index).unwrap_or(&no_offsets);
+        row_index += 1;
+        let group = match list_iter.next() {
+            Some(TokenTree::Group(group)) => group,
+            _ => {
+                errors.push(
+                    RowError::new(None, format!("Missing `{}` declaration", USB_KEYMAP_IDENT))
+                        .describe("row", offsets, table),
+                );
+                continue;
+            }
+        };
+        let items: Vec<TokenTree> = group
+            .stream()
+            .into_iter()
+            .filter(|i| !matches!(i, TokenTree::Punct(_)))
+            .collect();
+        let row = row_name(&items);
+
+        let location = table.location(offsets.row).0.clone();
+        match parse_row(&row_kind, location, items) {
+            Ok(key_map) if key_map.commented => commented_key_maps.push(key_map),
+            Ok(key_map) => key_maps.push(key_map),
+            Err(row_errors) => errors.extend(
+                row_errors
+                    .iter()
+                    .map(|err| err.describe(&row, offsets, table)),
+            ),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(anyhow!(
+            "{} error{} in the key table:\n{}",
+            errors.len(),
+            if errors.len() == 1 { "" } else { "s" },
+            errors.join("\n")
+        ));
+    }
+
+    key_maps.extend(commented_key_maps);
+    for (index, key_map) in key_maps.iter_mut().enumerate() {
+        key_map.index = index;
+    }
+
+    Ok(key_maps)
+}
@@ -0,0 +1,111 @@
+/// Byte offsets of a row in the text of a table
+#[derive(Debug, Default)]
+pub struct RowOffsets {
+    /// Offset of the name of the row, like `USB_KEYMAP`
+    pub row: usize,
+    /// Offset of the opening delimiter of the row, if it has one
+    pub group: Option<usize>,
+    /// Offset of every item of the row, without the punctuation between them
+    pub items: Vec<usize>,
+}
+
+enum Token {
+    Word,
+    Literal,
+    Open,
+    Close,
+    Punct,
+}
+
+/// Next token of `text` from `position`, skipping whitespace and comments, with its start and end
+fn next_token(text: &str, mut position: usize) -> Option<(usize, usize, Token)> {
+    loop {
+        let rest = &text[position..];
+        let trimmed = rest.trim_start();
+        position += rest.len() - trimmed.len();
+        if trimmed.starts_with("//") {
+            position += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else if trimmed.starts_with("/*") {
+            position += trimmed.find("*/").map_or(trimmed.len(), |end| end + 2);
+        } else {
+            break;
+        }
+    }
+
+    let rest = &text[position..];
+    let first = rest.chars().next()?;
+    let (len, token) = if first == '"' {
+        let mut escaped = false;
+        let end = rest[1..].find(|c| {
+            let end = !escaped && c == '"';
+            escaped = !escaped && c == '\\';
+            end
+        });
+        (end.map_or(rest.len(), |end| end + 2), Token::Literal)
+    } else if first.is_alphanumeric() || first == '_' {
+        let len = rest
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        let token = if first.is_ascii_digit() {
+            Token::Literal
+        } else {
+            Token::Word
+        };
+        (len, token)
+    } else {
+        let token = match first {
+            '(' | '[' | '{' => Token::Open,
+            ')' | ']' | '}' => Token::Close,
+            _ => Token::Punct,
+        };
+        (first.len_utf8(), token)
+    };
+    Some((position, position + len, token))
+}
+
+/// Find the rows named one of `names` in the declaration list of a table, in order
+///
+/// The compiler's spans only point at the macro call, so the lines and columns of the rows are
+/// found in the text instead, the same way as the tokens are parsed.
+pub fn scan_rows(text: &str, names: &[&str]) -> Vec<RowOffsets> {
+    let mut rows: Vec<RowOffsets> = vec![];
+    let mut depth = 0usize;
+    let mut after_name = false;
+    let mut in_row = false;
+    let mut position = 0;
+    while let Some((start, end, token)) = next_token(text, position) {
+        position = end;
+        let row_item = in_row && depth == 2;
+        match token {
+            Token::Open => {
+                if depth == 1 && after_name {
+                    if let Some(row) = rows.last_mut() {
+                        row.group = Some(start);
+                    }
+                    in_row = true;
+                }
+                depth += 1;
+            }
+            Token::Close => {
+                depth = depth.saturating_sub(1);
+                in_row &= depth > 1;
+            }
+            Token::Word if depth == 1 && names.contains(&&text[start..end]) => {
+                rows.push(RowOffsets {
+                    row: start,
+                    ..RowOffsets::default()
+                });
+                after_name = true;
+                continue;
+            }
+            _ => {}
+        }
+        if row_item && !matches!(token, Token::Close | Token::Punct) {
+            if let Some(row) = rows.last_mut() {
+                row.items.push(start);
+            }
+        }
+        after_name = false;
+    }
+    rows
+}
@@ -0,0 +1,215 @@
+use crate::key_map::KeyMap;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
+
+/// USB HID usage page of keyboard and keypad keys, preferred when a USB value is ambiguous
+const USB_KEYBOARD_PAGE: u16 = 0x0007;
+
+/// Platform values used by the source table for keys without a value
+const EVDEV_NO_CODE: u16 = 0x0000;
+const XKB_NO_CODE: u16 = 0x0000;
+const WIN_NO_CODE: u16 = 0x0000;
+const MAC_NO_CODE: u16 = 0xffff;
+
+/// Platforms with a numeric value for each key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Platform {
+    Usb,
+    Evdev,
+    Xkb,
+    Win,
+    Mac,
+}
+
+pub const PLATFORMS: [Platform; 5] = [
+    Platform::Usb,
+    Platform::Evdev,
+    Platform::Xkb,
+    Platform::Win,
+    Platform::Mac,
+];
+
+/// Platform values shared with keys that the source table gives no value, from the notes at the
+/// top of the table
+const DOCUMENTED_ALIASES: [(Platform, u16, &str); 6] = [
+    // [W1] Windows maps both Lang5 and F24 to 0x76
+    (Platform::Win, 0x0076, "Lang5"),
+    // [M1] OS X maps both Insert and Help to 0x72
+    (Platform::Mac, 0x0072, "Help"),
+    // [L1] Linux maps Keyboard Clear and Keypad Clear to KEY_DELETE
+    (Platform::Evdev, 0x006f, "NumpadClear"),
+    (Platform::Xkb, 0x0077, "NumpadClear"),
+    // [L3] Linux maps both Keyboard Pause and Media Pause to KEY_PAUSE
+    (Platform::Evdev, 0x0077, "MediaPause"),
+    (Platform::Xkb, 0x007f, "MediaPause"),
+];
+
+impl Platform {
+    /// Get the value that the source table uses for keys without a value on this platform
+    pub fn no_code(self) -> Option<u16> {
+        match self {
+            // Every USB value is a real usage, even if it is reserved
+            Platform::Usb => None,
+            Platform::Evdev => Some(EVDEV_NO_CODE),
+            Platform::Xkb => Some(XKB_NO_CODE),
+            Platform::Win => Some(WIN_NO_CODE),
+            Platform::Mac => Some(MAC_NO_CODE),
+        }
+    }
+
+    /// Get the value of a key on this platform, as written in the source table
+    pub fn raw_value(self, key_map: &KeyMap) -> u16 {
+        match self {
+            Platform::Usb => key_map.usb_code,
+            Platform::Evdev => key_map.evdev_code,
+            Platform::Xkb => key_map.xkb_code,
+            Platform::Win => key_map.win_code,
+            Platform::Mac => key_map.mac_code,
+        }
+    }
+
+    /// Get the value of a key on this platform to change it, as written in the source table
+    pub fn raw_value_mut(self, key_map: &mut KeyMap) -> &mut u16 {
+        match self {
+            Platform::Usb => &mut key_map.usb_code,
+            Platform::Evdev => &mut key_map.evdev_code,
+            Platform::Xkb => &mut key_map.xkb_code,
+            Platform::Win => &mut key_map.win_code,
+            Platform::Mac => &mut key_map.mac_code,
+        }
+    }
+
+    /// Whether the notes of the source table document the value as shared by several keys
+    pub fn is_documented_alias(self, value: u16) -> bool {
+        DOCUMENTED_ALIASES
+            .iter()
+            .any(|(platform, alias, _)| *platform == self && *alias == value)
+    }
+
+    /// Get the value of a key on this platform, or `None` if it has none
+    pub fn value(self, key_map: &KeyMap) -> Option<u16> {
+        let value = self.raw_value(key_map);
+        if Some(value) == self.no_code() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Get the name of the `KeyMap` field with the value on this platform
+    pub fn field(self) -> &'static str {
+        match self {
+            Platform::Usb => "usb",
+            Platform::Evdev => "evdev",
+            Platform::Xkb => "xkb",
+            Platform::Win => "win",
+            Platform::Mac => "mac",
+        }
+    }
+
+    /// Get the platform named in macro options, like `evdev`
+    pub fn from_name(name: &str) -> Option<Platform> {
+        PLATFORMS
+            .iter()
+            .copied()
+            .find(|platform| platform.field() == name)
+    }
+
+    /// Get the name of the `KeyMapping` variant for this platform
+    pub fn variant(self) -> Ident {
+        match self {
+            Platform::Usb => format_ident!("Usb"),
+            Platform::Evdev => format_ident!("Evdev"),
+            Platform::Xkb => format_ident!("Xkb"),
+            Platform::Win => format_ident!("Win"),
+            Platform::Mac => format_ident!("Mac"),
+        }
+    }
+
+    /// Get the name of the `KeyMappingError` variant for values without a key on this platform
+    pub fn error_variant(self) -> Ident {
+        format_ident!("Unknown{}", self.variant())
+    }
+
+    /// Get the name of the typed value for this platform, only USB has none
+    pub fn code_type(self) -> Option<Ident> {
+        match self {
+            Platform::Usb => None,
+            Platform::Evdev => Some(format_ident!("EvdevCode")),
+            Platform::Xkb => Some(format_ident!("XkbKeyCode")),
+            Platform::Win => Some(format_ident!("WinScanCode")),
+            Platform::Mac => Some(format_ident!("MacKeyCode")),
+        }
+    }
+
+    /// Get the short name of this platform, for generated error messages
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Platform::Usb => "USB HID",
+            Platform::Evdev => "evdev",
+            Platform::Xkb => "X11",
+            Platform::Win => "Windows",
+            Platform::Mac => "Mac",
+        }
+    }
+
+    /// Get a description of a value on this platform, for generated docs
+    pub fn description(self) -> &'static str {
+        match self {
+            Platform::Usb => "USB HID value",
+            Platform::Evdev => "Linux kernel evdev value",
+            Platform::Xkb => "X11 value",
+            Platform::Win => "Windows value",
+            Platform::Mac => "Mac value",
+        }
+    }
+
+    /// Get the name of the generated table of preferred keys for values on this platform
+    pub fn table(self) -> Ident {
+        format_ident!("{}_TO_ID", self.field().to_uppercase())
+    }
+
+    /// Get the `KeyMapping` variant matching a value on this platform
+    pub fn key_mapping(self, value: TokenStream) -> TokenStream {
+        let variant = self.variant();
+        quote! { KeyMapping::#variant(#value) }
+    }
+
+    /// Group keys by their value on this platform, with the preferred key first
+    ///
+    /// Keys on the USB keyboard page are preferred, then keys in the order of the source table,
+    /// then keys from rows that the source table comments out. Keys that the documented aliases add
+    /// come last.
+    pub fn candidates<'a>(self, key_maps: &[&'a KeyMap]) -> BTreeMap<u16, Vec<&'a KeyMap>> {
+        let mut candidates: BTreeMap<u16, Vec<&KeyMap>> = BTreeMap::new();
+        let mut sorted = key_maps.to_vec();
+        sorted.sort_by_key(|key_map| {
+            (
+                key_map.commented,
+                key_map.usb_page_code != USB_KEYBOARD_PAGE,
+                key_map.index,
+            )
+        });
+        for key_map in sorted {
+            if let Some(value) = self.value(key_map) {
+                candidates.entry(value).or_default().push(key_map);
+            }
+        }
+
+        for (platform, value, variant) in DOCUMENTED_ALIASES.iter() {
+            if *platform != self {
+                continue;
+            }
+            let Some(key_map) = key_maps.iter().find(|key_map| key_map.variant == *variant) else {
+                continue;
+            };
+            let keys = candidates.entry(*value).or_default();
+            if !keys.iter().any(|k| k.variant == key_map.variant) {
+                keys.push(key_map);
+            }
+        }
+
+        candidates
+    }
+}
@@ -0,0 +1,129 @@
+use heck::{ToShoutySnakeCase, ToSnakeCase};
+use proc_macro2::{Delimiter, Group, Ident, Literal, TokenStream, TokenTree};
+
+/// Names of the items that the generated code defines, renamed when a prefix is given
+const ITEM_NAMES: &[&str] = &[
+    "KeyModifiers",
+    "KeyCategory",
+    "KeyMapping",
+    "KeyMap",
+    "EvdevCode",
+    "XkbKeyCode",
+    "WinScanCode",
+    "MacKeyCode",
+    "MAX_CODE_ERROR_LEN",
+    "KeyMappingError",
+    "KeyMappingCode",
+    "KeyMappingId",
+    "get_usb_code",
+    "get_key_map",
+    "KeyIndex",
+    "find_index",
+    "find_id",
+    "USB_TO_ID",
+    "EVDEV_TO_ID",
+    "XKB_TO_ID",
+    "WIN_TO_ID",
+    "MAC_TO_ID",
+    "USB_INDEX",
+    "CODE_TO_ID",
+    "NULL_CODE_IDS",
+    "SHARED_CANDIDATES",
+    "KEY_MAPPING_ID_NAMES",
+];
+
+/// Rename every generated item with a prefix, so that the macro can be used more than once in a
+/// module
+pub fn prefix_items(tokens: TokenStream, prefix: &str) -> TokenStream {
+    if prefix.is_empty() {
+        return tokens;
+    }
+
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                if ITEM_NAMES.contains(&name.as_str()) {
+                    TokenTree::Ident(Ident::new(&prefixed(&name, prefix), ident.span()))
+                } else {
+                    TokenTree::Ident(ident)
+                }
+            }
+            TokenTree::Group(group) => {
+                let stream = match prefix_doc_attribute(&group, prefix) {
+                    Some(stream) => stream,
+                    None => prefix_items(group.stream(), prefix),
+                };
+                let mut renamed = Group::new(group.delimiter(), stream);
+                renamed.set_span(group.span());
+                TokenTree::Group(renamed)
+            }
+            other => other,
+        })
+        .collect()
+}
+
+fn prefixed(name: &str, prefix: &str) -> String {
+    if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        if name.chars().all(|c| !c.is_ascii_lowercase()) {
+            format!("{}_{}", prefix.to_shouty_snake_case(), name)
+        } else {
+            format!("{}{}", prefix, name)
+        }
+    } else {
+        format!("{}_{}", prefix.to_snake_case(), name)
+    }
+}
+
+/// Rename items in the doc of a `#[doc = "..."]` attribute, written as a doc comment or not
+fn prefix_doc_attribute(group: &Group, prefix: &str) -> Option<TokenStream> {
+    if group.delimiter() != Delimiter::Bracket {
+        return None;
+    }
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    match tokens.as_slice() {
+        [TokenTree::Ident(doc), TokenTree::Punct(eq), TokenTree::Literal(literal)]
+            if doc == "doc" && eq.as_char() == '=' =>
+        {
+            let mut renamed = TokenTree::Literal(prefix_doc(literal, prefix)?);
+            renamed.set_span(literal.span());
+            Some(
+                vec![tokens[0].clone(), tokens[1].clone(), renamed]
+                    .into_iter()
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Rename items in code spans of doc comments, like [`KeyMap::ALL`]
+fn prefix_doc(literal: &Literal, prefix: &str) -> Option<Literal> {
+    let doc = syn::parse2::<syn::LitStr>(TokenTree::Literal(literal.clone()).into())
+        .ok()?
+        .value();
+    if !doc.contains('`') {
+        return None;
+    }
+
+    let mut renamed = String::new();
+    let mut rest = doc.as_str();
+    while let Some(start) = rest.find('`') {
+        renamed.push_str(&rest[..=start]);
+        rest = &rest[start + 1..];
+        let end = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        let name = &rest[..end];
+        if ITEM_NAMES.contains(&name) {
+            renamed.push_str(&prefixed(name, prefix));
+        } else {
+            renamed.push_str(name);
+        }
+        rest = &rest[end..];
+    }
+    renamed.push_str(rest);
+
+    Some(Literal::string(&renamed))
+}
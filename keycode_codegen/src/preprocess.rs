@@ -0,0 +1,380 @@
+use anyhow::{anyhow, Result};
+use core::fmt;
+
+const COMMENTED_ROW: &str = "USB_KEYMAP(";
+/// Name given to commented out rows, so that the parser can add them after the other keys
+pub const COMMENTED_ROW_IDENT: &str = "USB_KEYMAP_COMMENTED";
+
+/// State of an `#if` block
+struct Conditional {
+    /// Whether the enclosing block is active
+    parent_active: bool,
+    /// Whether the current branch is active
+    active: bool,
+    /// Whether a branch of the block was taken, so that later `#elif` and `#else` are skipped
+    taken: bool,
+    seen_else: bool,
+}
+
+/// Deepest nesting of `#include` that is followed
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Where a line of a preprocessed table comes from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    /// Path of the included file, `None` for the table itself
+    pub file: Option<String>,
+    /// Line in its file, starting at 1
+    pub line: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "line {} of `{}`", self.line, file),
+            None => write!(f, "line {}", self.line),
+        }
+    }
+}
+
+/// Text of a preprocessed table, with where each of its lines comes from
+#[derive(Debug, Default)]
+pub struct Preprocessed {
+    pub text: String,
+    /// Byte offset in `text` where each line starts, with its location
+    lines: Vec<(usize, Location)>,
+}
+
+impl Preprocessed {
+    fn push_line(&mut self, line: &str, location: Location) {
+        self.lines.push((self.text.len(), location));
+        self.text.push_str(line);
+        self.text.push('\n');
+    }
+
+    /// Location of the line that contains the byte at `offset` in `text`, with the column of the
+    /// byte, starting at 1
+    pub fn location(&self, offset: usize) -> (&Location, usize) {
+        let index = self
+            .lines
+            .partition_point(|(start, _)| *start <= offset)
+            .saturating_sub(1);
+        let (start, location) = &self.lines[index];
+        let column = self.text[*start..offset].chars().count() + 1;
+        (location, column)
+    }
+}
+
+/// Evaluate the C preprocessor directives of a key table
+///
+/// Supports `#if`, `#ifdef`, `#ifndef`, `#elif`, `#else`, `#endif`, `#define`, `#undef` and
+/// `#include`, where `#if` expressions can use `defined`, `!`, `&&`, `||`, parentheses, numbers
+/// and defined names. `defines` holds the defined names with their values, which are empty for
+/// names defined without one. Lines that are skipped and directives are blanked, and included
+/// files are inlined, with the location of every line kept in the result. With `commented_rows`,
+/// the commented out `USB_KEYMAP` rows that the table documents as potentially interesting are
+/// kept as `USB_KEYMAP_COMMENTED` rows.
+pub fn preprocess(
+    data: &str,
+    defines: &mut Vec<(String, String)>,
+    commented_rows: bool,
+    include: &mut dyn FnMut(&str) -> Result<String>,
+) -> Result<Preprocessed> {
+    let mut preprocessor = Preprocessor {
+        defines,
+        commented_rows,
+        include,
+        files: vec![],
+        output: Preprocessed::default(),
+    };
+    preprocessor.file(data, None)?;
+    Ok(preprocessor.output)
+}
+
+struct Preprocessor<'a> {
+    defines: &'a mut Vec<(String, String)>,
+    commented_rows: bool,
+    include: &'a mut dyn FnMut(&str) -> Result<String>,
+    /// Paths of the files that are being included
+    files: Vec<String>,
+    output: Preprocessed,
+}
+
+impl<'a> Preprocessor<'a> {
+    fn file(&mut self, data: &str, file: Option<&str>) -> Result<()> {
+        let mut conditionals: Vec<Conditional> = vec![];
+        let mut in_commented_row = false;
+        for (number, line) in data.lines().enumerate() {
+            let location = Location {
+                file: file.map(str::to_string),
+                line: number + 1,
+            };
+            let active = conditionals.last().map_or(true, |c| c.active);
+            let trimmed = line.trim_start();
+            let error = |message: String| anyhow!("{}: {}", location, message);
+
+            if let Some(directive) = trimmed.strip_prefix('#') {
+                let directive = directive.trim_start();
+                let (name, argument) = match directive.find(|c: char| !c.is_ascii_alphanumeric()) {
+                    Some(end) => (&directive[..end], directive[end..].trim()),
+                    None => (directive, ""),
+                };
+                let argument = strip_comment(argument);
+                match name {
+                    "if" | "ifdef" | "ifndef" => {
+                        let condition = active
+                            && match name {
+                                "if" => evaluate(argument, self.defines).map_err(error)?,
+                                "ifdef" => is_defined(self.defines, argument),
+                                _ => !is_defined(self.defines, argument),
+                            };
+                        conditionals.push(Conditional {
+                            parent_active: active,
+                            active: condition,
+                            taken: condition,
+                            seen_else: false,
+                        });
+                    }
+                    "elif" | "else" => {
+                        let conditional = conditionals
+                            .last_mut()
+                            .ok_or_else(|| error(format!("`#{}` without an `#if`", name)))?;
+                        if conditional.seen_else {
+                            return Err(error(format!("`#{}` after an `#else`", name)));
+                        }
+                        let condition = if name == "elif" {
+                            conditional.parent_active
+                                && !conditional.taken
+                                && evaluate(argument, self.defines).map_err(error)?
+                        } else {
+                            conditional.seen_else = true;
+                            conditional.parent_active && !conditional.taken
+                        };
+                        conditional.active = condition;
+                        conditional.taken |= condition;
+                    }
+                    "endif" => {
+                        conditionals
+                            .pop()
+                            .ok_or_else(|| error("`#endif` without an `#if`".to_string()))?;
+                    }
+                    "define" if active => {
+                        let (define, value) = argument
+                            .split_once(char::is_whitespace)
+                            .unwrap_or((argument, ""));
+                        self.defines.retain(|(name, _)| name != define);
+                        self.defines
+                            .push((define.to_string(), value.trim().to_string()));
+                    }
+                    "undef" if active => self.defines.retain(|(name, _)| name != argument),
+                    "include" if active => {
+                        let path = argument.trim_matches(|c| c == '"' || c == '<' || c == '>');
+                        if self.files.iter().any(|file| file == path) {
+                            return Err(error(format!("`{}` includes itself", path)));
+                        }
+                        if self.files.len() >= MAX_INCLUDE_DEPTH {
+                            return Err(error(format!(
+                                "Could not include `{}`: includes are nested too deeply",
+                                path
+                            )));
+                        }
+                        let included = (self.include)(path).map_err(|err| {
+                            error(format!("Could not include `{}`: {}", path, err))
+                        })?;
+                        self.files.push(path.to_string());
+                        self.file(&included, Some(path))?;
+                        self.files.pop();
+                        continue;
+                    }
+                    "define" | "undef" | "include" | "pragma" | "error" | "warning" => {}
+                    _ => return Err(error(format!("Unsupported directive `#{}`", name))),
+                }
+                self.output.push_line("", location);
+                continue;
+            }
+
+            if !active {
+                self.output.push_line("", location);
+                continue;
+            }
+
+            if self.commented_rows {
+                let uncommented = trimmed.strip_prefix("//").map(str::trim_start);
+                match uncommented {
+                    Some(row) if row.starts_with(COMMENTED_ROW) => {
+                        let row =
+                            format!("{}{}", COMMENTED_ROW_IDENT, &row[COMMENTED_ROW.len() - 1..]);
+                        in_commented_row = !row.contains(')');
+                        self.output.push_line(&row, location);
+                        continue;
+                    }
+                    Some(row) if in_commented_row => {
+                        in_commented_row = !row.contains(')');
+                        self.output.push_line(row, location);
+                        continue;
+                    }
+                    _ => in_commented_row = false,
+                }
+            }
+
+            self.output.push_line(line, location);
+        }
+
+        if !conditionals.is_empty() {
+            return Err(match file {
+                Some(file) => anyhow!("`#if` is missing an `#endif` in `{}`", file),
+                None => anyhow!("`#if` is missing an `#endif`"),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn strip_comment(argument: &str) -> &str {
+    match argument.find("//") {
+        Some(start) => argument[..start].trim(),
+        None => argument,
+    }
+}
+
+/// Deepest nesting of names defined as other names that is evaluated
+const MAX_DEPTH: usize = 16;
+
+fn is_defined(defines: &[(String, String)], name: &str) -> bool {
+    defines.iter().any(|(define, _)| define == name)
+}
+
+/// Evaluate an `#if` expression
+fn evaluate(expression: &str, defines: &[(String, String)]) -> Result<bool, String> {
+    evaluate_nested(expression, defines, 0)
+}
+
+fn evaluate_nested(
+    expression: &str,
+    defines: &[(String, String)],
+    depth: usize,
+) -> Result<bool, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("`{}` is defined too deeply in `#if`", expression));
+    }
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        defines,
+        depth,
+    };
+    let value = parser.or()?;
+    if parser.position != tokens.len() {
+        return Err(format!(
+            "Unexpected `{}` in `#if {}`",
+            tokens[parser.position], expression
+        ));
+    }
+    Ok(value)
+}
+
+fn tokenize(expression: &str) -> Result<Vec<String>, String> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' | ')' | '!' => tokens.push(c.to_string()),
+            '&' | '|' if chars.peek() == Some(&c) => {
+                chars.next();
+                tokens.push(format!("{}{}", c, c));
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut token = c.to_string();
+                while let Some(c) = chars
+                    .peek()
+                    .filter(|c| c.is_ascii_alphanumeric() || **c == '_')
+                {
+                    token.push(*c);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+            c => return Err(format!("Unsupported `{}` in `#if {}`", c, expression)),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [String],
+    position: usize,
+    defines: &'a [(String, String)],
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.position)?;
+        self.position += 1;
+        Some(token)
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        if self.tokens.get(self.position).map(String::as_str) == Some(expected) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn or(&mut self) -> Result<bool, String> {
+        let mut value = self.and()?;
+        while self.eat("||") {
+            value |= self.and()?;
+        }
+        Ok(value)
+    }
+
+    fn and(&mut self) -> Result<bool, String> {
+        let mut value = self.unary()?;
+        while self.eat("&&") {
+            value &= self.unary()?;
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<bool, String> {
+        if self.eat("!") {
+            return Ok(!self.unary()?);
+        }
+        match self.next() {
+            Some("(") => {
+                let value = self.or()?;
+                if !self.eat(")") {
+                    return Err("Missing `)` in `#if`".to_string());
+                }
+                Ok(value)
+            }
+            Some("defined") => {
+                let parenthesized = self.eat("(");
+                let name = self
+                    .next()
+                    .ok_or_else(|| "`defined` is missing a name".to_string())?;
+                if parenthesized && !self.eat(")") {
+                    return Err("Missing `)` after `defined`".to_string());
+                }
+                Ok(is_defined(self.defines, name))
+            }
+            Some(token) if token.starts_with(|c: char| c.is_ascii_digit()) => token
+                .parse::<i64>()
+                .map(|value| value != 0)
+                .map_err(|_| format!("Unsupported number `{}` in `#if`", token)),
+            // Names count as their value if they are defined, `1` if they are defined without a
+            // value and `0` otherwise
+            Some(token) => match self.defines.iter().find(|(define, _)| define == token) {
+                Some((_, value)) if value.is_empty() => Ok(true),
+                Some((_, value)) => evaluate_nested(value, self.defines, self.depth + 1),
+                None => Ok(false),
+            },
+            None => Err("`#if` is missing an expression".to_string()),
+        }
+    }
+}
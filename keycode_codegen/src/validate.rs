@@ -0,0 +1,171 @@
+use crate::{
+    key_map::KeyMap,
+    platform::{Platform, PLATFORMS},
+};
+use anyhow::{anyhow, Result};
+
+/// Linux X11 values are the evdev values plus 8
+const XKB_EVDEV_OFFSET: u16 = 8;
+
+fn describe(key_map: &KeyMap, message: String) -> String {
+    format!(
+        "{}, row `{}`: {}",
+        key_map.location, key_map.chromium_name, message
+    )
+}
+
+fn same_values(a: &KeyMap, b: &KeyMap) -> bool {
+    (
+        a.usb_page_code,
+        a.usb_code,
+        a.evdev_code,
+        a.xkb_code,
+        a.win_code,
+        a.mac_code,
+        &a.dom_code,
+        &a.extra,
+    ) == (
+        b.usb_page_code,
+        b.usb_code,
+        b.evdev_code,
+        b.xkb_code,
+        b.win_code,
+        b.mac_code,
+        &b.dom_code,
+        &b.extra,
+    )
+}
+
+/// Check the keys of a table, returning them without the rows that repeat a key
+///
+/// Keys with the same variant, W3 browser event code or USB HID usage are errors, as are platform
+/// values that several keys share, unless the notes of the source table document them like
+/// [W1] and [M1]. Rows that repeat another row exactly are dropped with a warning. Rows that the
+/// source table comments out are dropped when they clash with another key, since they only
+/// document possible keys, and their platform values may be shared. Keys whose X11 value is not
+/// their evdev value plus 8 are warnings.
+pub fn validate(key_maps: Vec<KeyMap>, warnings: &mut Vec<String>) -> Result<Vec<KeyMap>> {
+    let mut errors = vec![];
+    let mut kept: Vec<KeyMap> = vec![];
+    for key_map in key_maps {
+        if key_map.commented {
+            let clashes = kept.iter().any(|other| {
+                other.variant == key_map.variant
+                    || (other.usb_page_code, other.usb_code)
+                        == (key_map.usb_page_code, key_map.usb_code)
+                    || (key_map.dom_code.is_some() && other.dom_code == key_map.dom_code)
+            });
+            if !clashes {
+                kept.push(KeyMap {
+                    index: kept.len(),
+                    ..key_map
+                });
+            }
+            continue;
+        }
+
+        if let Some(other) = kept.iter().find(|other| other.variant == key_map.variant) {
+            if same_values(other, &key_map) {
+                warnings.push(describe(
+                    &key_map,
+                    format!("repeats the row on {} and is skipped", other.location),
+                ));
+            } else {
+                errors.push(describe(
+                    &key_map,
+                    format!(
+                        "has the variant of the row on {} with different values",
+                        other.location
+                    ),
+                ));
+            }
+            continue;
+        }
+
+        if let Some(code) = &key_map.dom_code {
+            if let Some(other) = kept
+                .iter()
+                .find(|other| other.dom_code.as_ref() == Some(code))
+            {
+                errors.push(describe(
+                    &key_map,
+                    format!(
+                        "has the W3 browser event code `{}` of `{}` on {}",
+                        code, other.chromium_name, other.location
+                    ),
+                ));
+            }
+        }
+        if let Some(other) = kept.iter().find(|other| {
+            (other.usb_page_code, other.usb_code) == (key_map.usb_page_code, key_map.usb_code)
+        }) {
+            errors.push(describe(
+                &key_map,
+                format!(
+                    "has the USB HID usage {:#06x}:{:#06x} of `{}` on {}",
+                    key_map.usb_page_code, key_map.usb_code, other.chromium_name, other.location
+                ),
+            ));
+        }
+        for platform in PLATFORMS.iter().copied().filter(|p| *p != Platform::Usb) {
+            let value = match platform.value(&key_map) {
+                Some(value) if !platform.is_documented_alias(value) => value,
+                _ => continue,
+            };
+            if let Some(other) = kept
+                .iter()
+                .find(|other| !other.commented && platform.value(other) == Some(value))
+            {
+                errors.push(describe(
+                    &key_map,
+                    format!(
+                        "has the {} {:#06x} of `{}` on {}",
+                        platform.description(),
+                        value,
+                        other.chromium_name,
+                        other.location
+                    ),
+                ));
+            }
+        }
+        match (
+            Platform::Evdev.value(&key_map),
+            Platform::Xkb.value(&key_map),
+        ) {
+            (Some(evdev), Some(xkb)) if evdev.checked_add(XKB_EVDEV_OFFSET) != Some(xkb) => {
+                warnings.push(describe(
+                    &key_map,
+                    format!(
+                        "has X11 value {:#06x}, not its evdev value {:#06x} plus {}",
+                        xkb, evdev, XKB_EVDEV_OFFSET
+                    ),
+                ))
+            }
+            (Some(_), None) => warnings.push(describe(
+                &key_map,
+                "has an evdev value but no X11 value".into(),
+            )),
+            (None, Some(_)) => warnings.push(describe(
+                &key_map,
+                "has an X11 value but no evdev value".into(),
+            )),
+            _ => {}
+        }
+
+        kept.push(KeyMap {
+            index: kept.len(),
+            ..key_map
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(anyhow!(
+            "{} error{} in the key table:\n{}",
+            errors.len(),
+            if errors.len() == 1 { "" } else { "s" },
+            errors.join("\n")
+        ));
+    }
+
+    Ok(kept)
+}
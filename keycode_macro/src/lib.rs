@@ -1,35 +1,84 @@
 extern crate proc_macro;
 
-mod generate;
-mod key_map;
-mod parse;
-
-use self::{generate::*, parse::*};
-use core::str::FromStr;
+use anyhow::Result;
+use keycode_codegen::{generate_tokens, Options};
 use proc_macro::TokenStream;
 use quote::quote;
 
+/// Generate the key mapping types from Chromium's `keycode_converter_data.inc`
+///
+/// Without options the bundled copy of the table is used and every platform is generated. Options
+/// are comma separated and all optional:
+///
+/// * `path = "keys.inc"`: read the table from a file, relative to `CARGO_MANIFEST_DIR`
+/// * `prefix = "Vendor"`: prefix the names of every generated item, like `VendorKeyMap`
+/// * `platforms = [usb, evdev]`: only generate these platforms, `usb` is required
+/// * `extensions = ["vendor.inc"]`: merge more tables on top, relative to `CARGO_MANIFEST_DIR`
+/// * `defines = [OS_ANDROID]`: names that are defined for `#if` directives in the tables
+/// * `commented_rows = true`: add the keys that the source table comments out as potentially
+///   interesting, after the other keys
+///
+/// Extension tables use the same `USB_KEYMAP_DECLARATION` form. Their rows add keys or fill in
+/// missing values of keys with the same variant, and `USB_KEYMAP_EXT` rows can add named columns
+/// after the variant, like `ps2_set2 = 0x1c` or `label = "A"`, which become `KeyMap` methods.
+/// Values that conflict with the table fail to compile.
+///
+/// The same code can be generated from a build script with the `keycode_codegen` crate.
 #[proc_macro]
-pub fn parse_keycode_converter_data(_input: TokenStream) -> TokenStream {
-    let output = if let Ok(input) =
-        TokenStream::from_str(&include_str!("../keycode_converter_data.inc").to_string())
-    {
-        let input = proc_macro2::TokenStream::from(input);
-
-        match parse(input) {
-            Ok(key_maps) => generate(key_maps),
-            Err(err) => {
-                let err_string = err.to_string();
-                quote! {
-                    compile_error!(#err_string)
-                }
+pub fn parse_keycode_converter_data(input: TokenStream) -> TokenStream {
+    let output = match expand(proc_macro2::TokenStream::from(input)) {
+        Ok(output) => output,
+        Err(err) => {
+            let err_string = err.to_string();
+            quote! {
+                compile_error!(#err_string);
             }
         }
-    } else {
-        quote! {
-            compile_error!("Could not parse `keycode_converter_data.inc` file into a `TokenStream`")
-        }
     };
 
     TokenStream::from(output)
 }
+
+fn expand(input: proc_macro2::TokenStream) -> Result<proc_macro2::TokenStream> {
+    let generated = generate_tokens(&Options::parse(input)?)?;
+    let tokens = generated.tokens;
+    let tracked_paths = generated.tracked_paths;
+
+    // Stable proc macros can't emit warnings, but using a deprecated item warns with its note
+    let warnings = generated.warnings.iter().map(|warning| {
+        quote! {
+            const _: () = {
+                #[deprecated(note = #warning)]
+                const KEY_TABLE_WARNING: () = ();
+                KEY_TABLE_WARNING
+            };
+        }
+    });
+
+    // Rebuild when the tables change
+    Ok(quote! {
+        #(const _: &str = include_str!(#tracked_paths);)*
+        #(#warnings)*
+        #tokens
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn macro_and_codegen_outputs_are_identical() {
+        let input = quote! { prefix = "Small", platforms = [usb, evdev] };
+        let options = Options::parse(input.clone()).unwrap();
+
+        let macro_output = expand(input).unwrap().to_string();
+        let source = keycode_codegen::generate_source(&options).unwrap();
+        let codegen_output = proc_macro2::TokenStream::from_str(&source)
+            .unwrap()
+            .to_string();
+
+        assert_eq!(macro_output, codegen_output);
+    }
+}
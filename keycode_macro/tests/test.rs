@@ -1,4 +1,105 @@
-use core::str::FromStr;
 use keycode_macro::parse_keycode_converter_data;
 
 parse_keycode_converter_data!();
+
+parse_keycode_converter_data!(
+    path = "../keycode_codegen/keycode_converter_data.inc",
+    prefix = "Small",
+    platforms = [usb, evdev]
+);
+
+parse_keycode_converter_data!(prefix = "Vendor", extensions = ["tests/vendor_keys.inc"]);
+
+parse_keycode_converter_data!(prefix = "Commented", commented_rows = true);
+
+#[test]
+fn discriminants_follow_the_source_table() {
+    assert_eq!(KeyMappingId::None as u16, 0);
+    assert_eq!(KeyMappingId::Hyper as u16, 1);
+    assert_eq!(KeyMappingCode::Hyper as u16, 0);
+    assert!((KeyMappingId::UsA as u16) < (KeyMappingId::UsB as u16));
+    assert_eq!(KeyMappingId::UsB as u16, KeyMappingId::UsA as u16 + 1);
+}
+
+#[test]
+fn options_prefix_items_and_select_platforms() {
+    let a = SmallKeyMap::from(SmallKeyMappingId::UsA);
+    assert_eq!(a.usb, 0x0004);
+    assert_eq!(a.evdev, 0x001e);
+    assert_eq!(a.evdev_code().map(SmallEvdevCode::get), Some(0x001e));
+    assert_eq!(
+        SmallKeyMap::from_key_mapping(SmallKeyMapping::Evdev(0x001e)),
+        Ok(a)
+    );
+    assert_eq!(
+        SmallKeyMap::from_key_mapping(SmallKeyMapping::Evdev(0)),
+        Err(SmallKeyMappingError::UnknownEvdev(0))
+    );
+    assert_eq!(SmallKeyMap::ALL.len(), KeyMap::ALL.len());
+    assert_eq!(SMALL_MAX_CODE_ERROR_LEN, MAX_CODE_ERROR_LEN);
+    assert_eq!(
+        SmallKeyMappingId::Numpad1.category(),
+        SmallKeyCategory::Numpad
+    );
+}
+
+#[test]
+fn extensions_add_keys_and_columns() {
+    assert_eq!(VendorKeyMap::ALL.len(), KeyMap::ALL.len() + 2);
+
+    let macro1 = VendorKeyMap::from(VendorKeyMappingId::VendorMacro1);
+    assert_eq!(macro1.usb_page(), 0xff00);
+    assert_eq!(macro1.usb, 0x0001);
+    assert_eq!(macro1.code, None);
+    assert_eq!(macro1.label(), Some("M1"));
+    assert_eq!(macro1.ps2_set2(), None);
+    assert_eq!(
+        VendorKeyMap::from_usb_code(0xff00, 0x0002).map(|key| key.id),
+        Ok(VendorKeyMappingId::VendorMacro2)
+    );
+    assert_eq!(
+        VendorKeyMap::from_key_mapping(VendorKeyMapping::Evdev(0x0290)),
+        Ok(macro1)
+    );
+
+    let a = VendorKeyMap::from(VendorKeyMappingId::UsA);
+    assert_eq!(a.ps2_set2(), Some(0x1c));
+    assert_eq!(a.label(), Some("A"));
+    assert_eq!(VendorKeyMap::from(VendorKeyMappingId::UsB).label(), None);
+
+    // Escapes in string columns are unescaped
+    assert_eq!(
+        VendorKeyMap::from(VendorKeyMappingId::VendorMacro2).label(),
+        Some("\"M2\" \\")
+    );
+}
+
+#[test]
+fn commented_rows_are_added_after_the_other_keys() {
+    assert!(CommentedKeyMap::ALL.len() > KeyMap::ALL.len());
+    assert_eq!(CommentedKeyMappingId::UsA as u16, KeyMappingId::UsA as u16);
+    assert!(CommentedKeyMappingId::MediaPause as usize >= KeyMap::ALL.len());
+    assert!(CommentedKeyMap::from(CommentedKeyMappingId::LockingCapsLock).is_lock());
+    // Rows that are split over several commented lines
+    assert_eq!(
+        CommentedKeyMap::from(CommentedKeyMappingId::NumpadDoubleVerticalBar).usb,
+        0x00ca
+    );
+    assert_eq!(
+        CommentedKeyMap::from_usb_code(0x0c, 0x00b1).map(|key| key.id),
+        Ok(CommentedKeyMappingId::MediaPause)
+    );
+
+    // Values that a commented row shares with another key still find the other key
+    assert_eq!(
+        CommentedKeyMap::from_key_mapping(CommentedKeyMapping::Evdev(0x0080)).map(|key| key.id),
+        Ok(CommentedKeyMappingId::BrowserStop)
+    );
+    assert_eq!(
+        CommentedKeyMap::candidates(CommentedKeyMapping::Evdev(0x0080)),
+        &[
+            CommentedKeyMappingId::BrowserStop,
+            CommentedKeyMappingId::Stop
+        ]
+    );
+}